@@ -3,10 +3,10 @@ use leptos_router::*;
 use leptos::ev::MouseEvent;
 use reqwasm::http::Request;
 use serde::{Deserialize, Serialize};
-use leptos_charts::{BarChart, BarChartData, ChartOptions, Extent2};
+use leptos_charts::{AccessibilityOptions, Annotation, AnnotationAxis, Annotations, AreaChart, BarChart, BarChartData, Binning, BoxPlot, Candle, CandlestickChart, CandlestickData, ChartExport, ChartOptions, ColorPallet, ColorRamp, Curve, Datum, DistributionData, Extent2, Heatmap, HeatmapData, HeatmapOptions, Histogram, HistogramData, LegendOptions, LegendPosition, LineChart, LineChartData, LineOptions, Orientation, PieChart, PieChartData, PieChartItem, PieOptions, RadarChart, RadarChartData, ResponsiveOptions, ScatterChart, ScatterChartData, ScatterOptions, ScatterPoint, ScatterSeries, Selection, Series, Theme, ViolinPlot, Whiskers, provide_theme};
 use std::str::FromStr;
 
-use crate::charts::{ColorPallet, Dataset, PieChart, PieChartData, PieChartItem, Record};
+use crate::charts::{Dataset, Record};
 use crate::qrcode::QRCode;
 
 
@@ -106,25 +106,54 @@ pub fn App(cx: Scope) -> impl IntoView {
     let pie_chart_data_1 = create_rw_signal(cx, PieChartData {
         items: Clone::clone(&items),
         caption: String::from("Examples PieChart (Material)"),
-        color_pallet: ColorPallet::Material,
+    });
+
+    let pie_chart_options_1 = create_rw_signal(cx, ChartOptions {
+        extent: Extent2::new(300_f32, 300_f32),
+        color_pallet: Some(ColorPallet::Material),
+        ..ChartOptions::default()
     });
 
     let pie_chart_data_2 = create_rw_signal(cx, PieChartData {
         items: Clone::clone(&items),
         caption: String::from("Examples PieChart (OrangeFire)"),
-        color_pallet: ColorPallet::OrangeFire,
+    });
+
+    let pie_chart_options_2 = create_rw_signal(cx, ChartOptions {
+        extent: Extent2::new(300_f32, 300_f32),
+        color_pallet: Some(ColorPallet::OrangeFire),
+        pie: PieOptions {
+            pad_angle: 0.02,
+            corner_radius: 4.0,
+            ..PieOptions::default()
+        },
+        ..ChartOptions::default()
     });
 
     let pie_chart_data_3 = create_rw_signal(cx, PieChartData {
         items: Clone::clone(&items),
         caption: String::from("Examples PieChart (DarkBlue)"),
-        color_pallet: ColorPallet::DarkBlue,
+    });
+
+    let pie_chart_options_3 = create_rw_signal(cx, ChartOptions {
+        extent: Extent2::new(300_f32, 300_f32),
+        color_pallet: Some(ColorPallet::DarkBlue),
+        ..ChartOptions::default()
     });
 
     let pie_chart_data_4 = create_rw_signal(cx, PieChartData {
         items: Clone::clone(&items),
         caption: String::from("Examples PieChart (Default)"),
-        color_pallet: ColorPallet::Default,
+    });
+
+    let pie_chart_options_4 = create_rw_signal(cx, ChartOptions {
+        extent: Extent2::new(300_f32, 300_f32),
+        color_pallet: Some(ColorPallet::Default),
+        pie: PieOptions {
+            inner_radius: 0.0,
+            ..PieOptions::default()
+        },
+        ..ChartOptions::default()
     });
 
     let dataset = create_rw_signal(cx, Dataset {
@@ -152,6 +181,19 @@ pub fn App(cx: Scope) -> impl IntoView {
 
     let bar_chart_options = create_rw_signal(cx, ChartOptions {
         extent: Extent2::new(500_f32, 500_f32),
+        accessibility: AccessibilityOptions {
+            data_table: true,
+            ..AccessibilityOptions::default()
+        },
+        annotations: Annotations {
+            title: Some(String::from("Historical revenue")),
+            footnote: Some(String::from("Source: internal accounting")),
+            marks: vec![
+                Annotation::ReferenceLine { axis: AnnotationAxis::Value, value: 6.0, label: Some(String::from("Target")) },
+            ],
+            ..Annotations::default()
+        },
+        ..ChartOptions::default()
     });
 
     let bar_chart_data = create_rw_signal(cx, BarChartData::new(
@@ -159,9 +201,148 @@ pub fn App(cx: Scope) -> impl IntoView {
         vec![8.0, 3.5, 10.0, 5.0]
     ));
 
-    // let bar_chart_options = create_rw_signal(cx, BarChartOptions {
-    //     title: String::from("Historical revenue")
-    // });
+    let bar_chart_selection = create_rw_signal(cx, None::<Selection>);
+    let bar_chart_export = ChartExport::new(cx);
+
+    let line_chart_options = create_rw_signal(cx, ChartOptions {
+        line: LineOptions {
+            curve: Curve::MonotoneCubic,
+            markers: true,
+            ..LineOptions::default()
+        },
+        legend: LegendOptions {
+            position: Some(LegendPosition::Right),
+        },
+        ..ChartOptions::default()
+    });
+
+    let area_chart_options = create_rw_signal(cx, ChartOptions {
+        line: LineOptions {
+            stacked: true,
+            ..LineOptions::default()
+        },
+        legend: LegendOptions {
+            position: Some(LegendPosition::Bottom),
+        },
+        responsive: Some(ResponsiveOptions {
+            min_extent: Extent2::new(240.0, 160.0),
+            ..ResponsiveOptions::default()
+        }),
+        ..ChartOptions::default()
+    });
+
+    let line_chart_data = create_rw_signal(cx, LineChartData::new(
+        (0..12).map(|value| value as f64).collect(),
+        vec![
+            Series::new("North", vec![3.0, 4.5, 4.0, 6.0, 7.5, f32::NAN, 8.0, 7.0, 9.5, 10.0, 9.0, 11.0]),
+            Series::new("South", vec![2.0, 2.5, 3.5, 3.0, 4.0, 5.5, 5.0, 6.5, 6.0, 7.5, 8.0, 8.5]),
+        ]
+    ));
+
+    let scatter_chart_options = create_rw_signal(cx, ChartOptions {
+        scatter: ScatterOptions {
+            color_ramp: Some(ColorRamp::Viridis),
+            ..ScatterOptions::default()
+        },
+        ..ChartOptions::default()
+    });
+
+    let scatter_chart_data = create_rw_signal(cx, ScatterChartData::new(vec![
+        ScatterSeries::new("Samples", (0..10_000)
+            .map(|index| {
+                let t = index as f32 * 0.013;
+                let x = t.sin() * 40.0 + (t * 3.7).cos() * 10.0;
+                let y = t.cos() * 25.0 + (t * 1.3).sin() * 15.0;
+                ScatterPoint::new(x, y).with_color((t * 0.5).sin())
+            })
+            .collect()),
+    ]));
+
+    let heatmap_options = create_rw_signal(cx, ChartOptions {
+        extent: Extent2::new(760.0, 260.0),
+        heatmap: HeatmapOptions {
+            show_values: true,
+            ..HeatmapOptions::default()
+        },
+        ..ChartOptions::default()
+    });
+
+    let weekdays = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    let heatmap_data = create_rw_signal(cx, HeatmapData::new(
+        weekdays.iter().map(|day| String::from(*day)).collect(),
+        (0..24).map(|hour| format!("{}", hour)).collect(),
+        (0..weekdays.len())
+            .map(|day| {
+                (0..24)
+                    .map(|hour| {
+                        let daytime = (-((hour as f32 - 14.0) / 4.0).powi(2)).exp();
+                        let weekend = if day >= 5 { 0.5 } else { 1.0 };
+                        (daytime * weekend * 100.0).round()
+                    })
+                    .collect()
+            })
+            .collect(),
+    ));
+
+    let histogram_options = create_rw_signal(cx, ChartOptions::default());
+
+    let histogram_data = create_rw_signal(cx, HistogramData::new((0..2_000)
+        .map(|index| {
+            // Sums of pseudo random values are roughly normally distributed.
+            (0..6).map(|offset| ((index * 7 + offset * 13) as f32 * 12.9898).sin().abs()).sum::<f32>() * 10.0
+        })
+        .collect()));
+
+    let distribution_options = create_rw_signal(cx, ChartOptions::default());
+
+    let distribution_data = create_rw_signal(cx, DistributionData::new(
+        vec![String::from("eu-west"), String::from("us-east"), String::from("ap-south")],
+        (0..3)
+            .map(|region| {
+                (0..300)
+                    .map(|index| {
+                        // Latencies with a long tail, growing with the distance of the region.
+                        let noise = ((index * 31 + region * 17) as f32 * 12.9898).sin().abs();
+                        40.0 + region as f32 * 25.0 + noise.powi(4) * 200.0
+                    })
+                    .collect()
+            })
+            .collect(),
+    ));
+
+    let candlestick_options = create_rw_signal(cx, ChartOptions::default());
+
+    let candlestick_data = create_rw_signal(cx, CandlestickData::new({
+        // Daily prices starting on monday 2023-01-02, leaving out weekends.
+        const DAY: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+        let mut close = 100.0;
+        (0..120)
+            .filter(|day| day % 7 < 5)
+            .map(|day| {
+                let noise = |offset: i32| ((day * 7 + offset * 13) as f32 * 12.9898).sin();
+                let open = close;
+                close = open * (1.0 + noise(0) * 0.03);
+                let high = open.max(close) * (1.0 + noise(1).abs() * 0.015);
+                let low = open.min(close) * (1.0 - noise(2).abs() * 0.015);
+                Candle::new(1_672_617_600_000.0 + day as f64 * DAY, open, high, low, close)
+                    .with_volume((noise(3).abs() * 9_000.0 + 1_000.0).round())
+            })
+            .collect()
+    }));
+
+    let radar_options = create_rw_signal(cx, ChartOptions::default());
+
+    let radar_data = create_rw_signal(cx, RadarChartData::new(
+        ["Speed", "Reliability", "Comfort", "Safety", "Efficiency", "Price"].iter().map(|axis| String::from(*axis)).collect(),
+        vec![
+            Series::new("Model A", vec![8.0, 6.5, 7.0, 9.0, 5.5, 4.0]),
+            Series::new("Model B", vec![6.0, 8.5, 6.0, 7.5, 8.0, 7.0]),
+            Series::new("Model C", vec![4.5, 7.0, 9.0, 6.0, 6.5, 8.5]),
+        ],
+    ));
+
+    let theme = create_rw_signal(cx, Theme::light());
+    provide_theme(cx, theme.read_only());
 
     view! { cx,
         // <h1>"Hello Leptos"</h1>
@@ -170,17 +351,25 @@ pub fn App(cx: Scope) -> impl IntoView {
         // <button on:click=reset_callback>"Reset"</button>
         // <p>"Counter: " { move || format!("{}", count.value().get().map(|counter| counter.value).unwrap_or(0)) }</p>
         <div style="margin-bottom: 20px;">
-            <a href="/piechart">"PieChart"</a>" | "<a href="/barchart">"BarChart"</a>" | "<a href="/qr">"QR Code"</a>
+            <a href="/piechart">"PieChart"</a>" | "<a href="/barchart">"BarChart"</a>" | "<a href="/linechart">"LineChart"</a>" | "<a href="/scatterchart">"ScatterChart"</a>" | "<a href="/heatmap">"Heatmap"</a>" | "<a href="/histogram">"Histogram"</a>" | "<a href="/distribution">"Distributions"</a>" | "<a href="/candlestick">"Candlestick"</a>" | "<a href="/radar">"Radar"</a>" | "<a href="/qr">"QR Code"</a>
+            " | "
+            <select on:change=move |event| {
+                let value = event_target_value(&event);
+                theme.set(if value == "dark" { Theme::dark() } else { Theme::light() });
+            }>
+                <option value="light">"Light"</option>
+                <option value="dark">"Dark"</option>
+            </select>
         </div>
         <Router>
             <Routes>
                 <Route
                     path="/piechart"
                     view=move |cx| view! { cx,
-                            <PieChart data=pie_chart_data_1.read_only() />
-                            <PieChart data=pie_chart_data_2.read_only() />
-                            <PieChart data=pie_chart_data_3.read_only() />
-                            <PieChart data=pie_chart_data_4.read_only() />
+                            <PieChart options=pie_chart_options_1.read_only() data=pie_chart_data_1.read_only() />
+                            <PieChart options=pie_chart_options_2.read_only() data=pie_chart_data_2.read_only() />
+                            <PieChart options=pie_chart_options_3.read_only() data=pie_chart_data_3.read_only() />
+                            <PieChart options=pie_chart_options_4.read_only() data=pie_chart_data_4.read_only() />
                         }
                 />
                 <Route
@@ -198,6 +387,18 @@ pub fn App(cx: Scope) -> impl IntoView {
                                     options.extent.height = f32::from_str(&value).unwrap();
                                 });
                             }/></div>
+                            <div>"Orientation: "<select on:change=move |event| {
+                                let orientation = match event_target_value(&event).as_str() {
+                                    "horizontal" => Orientation::Horizontal,
+                                    _ => Orientation::Vertical,
+                                };
+                                bar_chart_options.update(|options| {
+                                    options.orientation = orientation;
+                                });
+                            }>
+                                <option value="vertical">"Vertical"</option>
+                                <option value="horizontal">"Horizontal"</option>
+                            </select></div>
                             <div>"Values: "<input type="text" value="8.0, 3.5, 10.0, 5.0" on:input=move |event| {
                                 let result = event_target_value(&event)
                                     .split(",")
@@ -212,8 +413,143 @@ pub fn App(cx: Scope) -> impl IntoView {
                                     });
                                 }
                             }/></div>
+                            <div>"Reduce motion: "<input type="checkbox" on:change=move |event| {
+                                let checked = event_target_checked(&event);
+                                bar_chart_options.update(|options| {
+                                    options.transition.reduced_motion = checked;
+                                });
+                            }/></div>
                             <br/>
-                            <BarChart options=bar_chart_options.read_only() data=bar_chart_data.read_only() />
+                            <BarChart
+                                options=bar_chart_options.read_only()
+                                data=bar_chart_data.read_only()
+                                selected=bar_chart_selection
+                                export=bar_chart_export
+                                on_select=|datum: Option<Datum>| match datum {
+                                    Some(datum) => log::info!("selected {} = {}", datum.label, datum.value),
+                                    None => log::info!("selection cleared"),
+                                }
+                            />
+                            <div>
+                                <button on:click=move |_| bar_chart_export.download_svg("bar_chart.svg")>"Download SVG"</button>
+                                <button on:click=move |_| bar_chart_export.download_png("bar_chart.png", 2.0)>"Download PNG"</button>
+                            </div>
+                            <p>{move || bar_chart_selection.get().map(|selection| format!("Selected bar: {}", selection.index))}</p>
+                        }
+                />
+                <Route
+                    path="/linechart"
+                    view=move |cx| view! { cx,
+                            <LineChart
+                                options=line_chart_options.read_only()
+                                data=line_chart_data.read_only()
+                                tooltip=|cx, datum: Datum| view! { cx,
+                                    <div>
+                                        <b>{datum.series_name}</b>
+                                        <div>"x = "{datum.label}", y = "{datum.value}</div>
+                                    </div>
+                                }.into_view(cx)
+                            />
+                            <div style="resize: both; overflow: hidden; width: 600px; height: 360px; border: 1px dashed #999;">
+                                <AreaChart options=area_chart_options.read_only() data=line_chart_data.read_only() />
+                            </div>
+                        }
+                />
+                <Route
+                    path="/scatterchart"
+                    view=move |cx| view! { cx,
+                            <ScatterChart options=scatter_chart_options.read_only() data=scatter_chart_data.read_only() />
+                        }
+                />
+                <Route
+                    path="/heatmap"
+                    view=move |cx| view! { cx,
+                            <Heatmap options=heatmap_options.read_only() data=heatmap_data.read_only() />
+                        }
+                />
+                <Route
+                    path="/histogram"
+                    view=move |cx| view! { cx,
+                            <div>"Binning: "<select on:change=move |event| {
+                                let binning = match event_target_value(&event).as_str() {
+                                    "freedman-diaconis" => Binning::FreedmanDiaconis,
+                                    "scott" => Binning::Scott,
+                                    "count" => Binning::Count(40),
+                                    "width" => Binning::Width(2.5),
+                                    _ => Binning::Sturges,
+                                };
+                                histogram_options.update(|options| {
+                                    options.histogram.binning = binning;
+                                });
+                            }>
+                                <option value="sturges">"Sturges"</option>
+                                <option value="freedman-diaconis">"Freedman-Diaconis"</option>
+                                <option value="scott">"Scott"</option>
+                                <option value="count">"40 bins"</option>
+                                <option value="width">"Width 2.5"</option>
+                            </select></div>
+                            <div>"Density: "<input type="checkbox" on:change=move |event| {
+                                let checked = event_target_checked(&event);
+                                histogram_options.update(|options| {
+                                    options.histogram.density = checked;
+                                });
+                            }/></div>
+                            <div>"Cumulative: "<input type="checkbox" on:change=move |event| {
+                                let checked = event_target_checked(&event);
+                                histogram_options.update(|options| {
+                                    options.histogram.cumulative = checked;
+                                });
+                            }/></div>
+                            <Histogram options=histogram_options.read_only() data=histogram_data.read_only() />
+                        }
+                />
+                <Route
+                    path="/distribution"
+                    view=move |cx| view! { cx,
+                            <div>"Whiskers: "<select on:change=move |event| {
+                                let whiskers = match event_target_value(&event).as_str() {
+                                    "min-max" => Whiskers::MinMax,
+                                    _ => Whiskers::default(),
+                                };
+                                distribution_options.update(|options| {
+                                    options.distribution.whiskers = whiskers;
+                                });
+                            }>
+                                <option value="iqr">"1.5 × IQR"</option>
+                                <option value="min-max">"Min / max"</option>
+                            </select></div>
+                            <BoxPlot options=distribution_options.read_only() data=distribution_data.read_only() />
+                            <ViolinPlot options=distribution_options.read_only() data=distribution_data.read_only() />
+                        }
+                />
+                <Route
+                    path="/candlestick"
+                    view=move |cx| view! { cx,
+                            <div>"Volume: "<input type="checkbox" checked on:change=move |event| {
+                                let checked = event_target_checked(&event);
+                                candlestick_options.update(|options| {
+                                    options.candlestick.volume = checked;
+                                });
+                            }/></div>
+                            <div>"Collapse gaps: "<input type="checkbox" on:change=move |event| {
+                                let checked = event_target_checked(&event);
+                                candlestick_options.update(|options| {
+                                    options.candlestick.collapse_gaps = checked;
+                                });
+                            }/></div>
+                            <CandlestickChart options=candlestick_options.read_only() data=candlestick_data.read_only() />
+                        }
+                />
+                <Route
+                    path="/radar"
+                    view=move |cx| view! { cx,
+                            <div>"Opacity: "<input type="range" min="0" max="1" step="0.05" value="0.25" on:input=move |event| {
+                                let opacity = event_target_value(&event).parse().unwrap_or(0.25);
+                                radar_options.update(|options| {
+                                    options.radar.opacity = opacity;
+                                });
+                            }/></div>
+                            <RadarChart options=radar_options.read_only() data=radar_data.read_only() />
                         }
                 />
                 <Route
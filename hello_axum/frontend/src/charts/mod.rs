@@ -1,8 +1,6 @@
-mod pie;
 mod bar;
 
 pub use bar::{BarChart, BarChartOptions,  Dataset, Record};
-pub use pie::{PieChart, PieChartData, PieChartItem};
 
 const DEFAULT_COLOR_PALLET: [u32; 16] = [0xf94144, 0xf3722c, 0xf8961e, 0xf9844a, 0xf9c74f, 0x90be6d, 0x43aa8b, 0x4d908e, 0x577590, 0x277da1, 0x2d3057, 0x2b2d42, 0x333a56, 0x4e6e58, 0xbcbd8b, 0xaa5042];
 const MATERIAL_COLOR_PALLET: [u32; 16] = [0xf44336, 0xe81e63, 0x9c27b0, 0x673ab7, 0x3f51b5, 0x2196f3, 0x03a9f4, 0x00bcd4, 0x009688, 0x4caf50, 0x8bc34a, 0xcddc39, 0xffeb3b, 0xffc107, 0xff9800, 0xff5722];
@@ -0,0 +1,142 @@
+use leptos::{component, create_memo, IntoView, Memo, ReadSignal, Scope, Signal, SignalGet, SignalWith, view};
+
+use crate::ChartOptions;
+use crate::interaction::Interaction;
+
+/// Hides content visually while keeping it available to screen readers.
+const VISUALLY_HIDDEN: &str = "position: absolute; width: 1px; height: 1px; margin: -1px; padding: 0; \
+    overflow: hidden; clip: rect(0 0 0 0); white-space: nowrap; border: 0;";
+
+/// What screen readers announce for a chart.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AccessibilityOptions {
+    /// Short name of the chart, a summary of the data if `None`.
+    pub label: Option<String>,
+    /// Longer description, instructions for the keyboard navigation if `None`.
+    pub description: Option<String>,
+    /// Renders the data as a table only visible to screen readers.
+    pub data_table: bool,
+}
+
+/// The data of a chart as a table, one row per category and one column per series.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DataTable {
+    pub caption: String,
+    pub header: Vec<String>,
+    /// The first cell of every row labels the row.
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Describes a single value, prefixed by the name of its series if there is one.
+pub(crate) fn describe_value(series_name: &str, label: &str, value: f32) -> String {
+    if series_name.is_empty() {
+        format!("{}: {}", label, value)
+    }
+    else {
+        format!("{}, {}: {}", series_name, label, value)
+    }
+}
+
+/// The data of a chart as told to screen readers.
+pub(crate) trait Describe {
+
+    /// Settings of the chart the description depends on, e.g. whether points are drawn as bubbles.
+    type Context;
+
+    /// Describes the data in a sentence.
+    fn summary(&self, context: &Self::Context) -> String;
+
+    /// Returns the data as a table with the given caption.
+    fn table(&self, caption: String, context: &Self::Context) -> DataTable;
+}
+
+/// Returns the label and description of a chart along with the table of its data, captioned with
+/// the label. `marks` names the marks navigated with the arrow keys, e.g. "bars".
+pub(crate) fn describe_chart<D, S>(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: S,
+    context: impl Fn() -> D::Context + Copy + 'static,
+    marks: impl Fn() -> &'static str + 'static,
+) -> (Memo<(String, String)>, Memo<DataTable>)
+where D: Describe + 'static, S: SignalWith<D> + Copy + 'static {
+    let description = create_memo(cx, move |_| {
+        let summary = data.with(|data| data.summary(&context()));
+        options.with(|options| describe(&options.accessibility, summary, marks()))
+    });
+    let table = create_memo(cx, move |_| {
+        description.with(|(label, _)| data.with(|data| data.table(Clone::clone(label), &context())))
+    });
+    (description, table)
+}
+
+/// Label, description and navigation hint shared by the charts.
+fn describe(options: &AccessibilityOptions, summary: String, marks: &str) -> (String, String) {
+    let label = options.label.clone().unwrap_or(summary);
+    let description = options.description.clone().unwrap_or_else(|| {
+        format!("Use the arrow keys to move between the {}, Enter to select one and Escape to leave.", marks)
+    });
+    (label, description)
+}
+
+/// A live region announcing the mark the keyboard focus moved to. Marks under the pointer are
+/// not announced, screen readers would read out every mark the pointer crosses.
+#[component]
+pub(crate) fn Announcer(
+    cx: Scope,
+    interaction: Interaction,
+) -> impl IntoView {
+
+    let text = create_memo(cx, move |_| {
+        interaction.hover.focused()
+            .and_then(|index| interaction.targets.datum(index))
+            .map(|datum| datum.description())
+    });
+
+    view! { cx,
+        <div style=VISUALLY_HIDDEN aria-live="polite" aria-atomic="true">
+            {move || text.get()}
+        </div>
+    }
+}
+
+/// A table of the chart's data for screen readers, rendered if `visible` is set.
+#[component]
+pub(crate) fn HiddenTable(
+    cx: Scope,
+    table: Memo<DataTable>,
+    #[prop(into)]
+    visible: Signal<bool>,
+) -> impl IntoView {
+
+    move || {
+        visible.get().then(|| table.with(|table| {
+            let header = table.header.iter()
+                .map(|cell| view! { cx, <th scope="col">{Clone::clone(cell)}</th> })
+                .collect::<Vec<_>>();
+            let rows = table.rows.iter()
+                .map(|row| {
+                    let cells = row.iter()
+                        .enumerate()
+                        .map(|(index, cell)| {
+                            if index == 0 {
+                                view! { cx, <th scope="row">{Clone::clone(cell)}</th> }.into_view(cx)
+                            }
+                            else {
+                                view! { cx, <td>{Clone::clone(cell)}</td> }.into_view(cx)
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    view! { cx, <tr>{cells}</tr> }
+                })
+                .collect::<Vec<_>>();
+            view! { cx,
+                <table style=VISUALLY_HIDDEN>
+                    <caption>{Clone::clone(&table.caption)}</caption>
+                    <thead><tr>{header}</tr></thead>
+                    <tbody>{rows}</tbody>
+                </table>
+            }
+        }))
+    }
+}
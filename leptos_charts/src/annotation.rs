@@ -0,0 +1,298 @@
+use leptos::{component, For, IntoView, Memo, ReadSignal, Scope, SignalWith, view};
+use nalgebra::{Matrix3, Point2, Vector2};
+
+use crate::ChartOptions;
+use crate::theme::{use_theme, Theme};
+
+/// Space in pixels between titles and the edge of the chart.
+const TITLE_PADDING: f32 = 6.0;
+/// Height of a line of text relative to its font size.
+const LINE_HEIGHT: f32 = 1.4;
+const ARROW_HEAD_SIZE: f32 = 6.0;
+const BAND_OPACITY: f32 = 0.15;
+
+/// Titles of a chart and marks drawn over its plot.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Annotations {
+    pub title: Option<String>,
+    pub subtitle: Option<String>,
+    /// Text below the chart, e.g. the source of the data.
+    pub footnote: Option<String>,
+    /// Marks in data coordinates, following the scales of the chart. Pie, radar charts and heatmaps do not show them.
+    pub marks: Vec<Annotation>,
+}
+
+/// The axis a reference line or band is placed on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnnotationAxis {
+    /// The categories of bar charts, `x` of line and scatter charts.
+    Domain,
+    Value,
+}
+
+/// A mark in data coordinates. On bar charts domain coordinates are category indices, with
+/// fractional values lying between the centres of neighbouring bands.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Annotation {
+    /// A line across the plot at `value`, e.g. a target.
+    ReferenceLine { axis: AnnotationAxis, value: f64, label: Option<String> },
+    /// A shaded range of values across the plot.
+    Band { axis: AnnotationAxis, from: f64, to: f64, label: Option<String> },
+    Text { at: (f64, f64), text: String },
+    /// An arrow pointing from `from` to `to`, labelled at its start.
+    Arrow { from: (f64, f64), to: (f64, f64), text: Option<String> },
+}
+
+/// An annotation placed in screen space.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PlacedAnnotation {
+    Line { from: Point2<f32>, to: Point2<f32>, label: Option<String> },
+    Band { min: Point2<f32>, max: Point2<f32>, label: Option<String> },
+    Text { at: Point2<f32>, text: String },
+    Arrow { from: Point2<f32>, to: Point2<f32>, text: Option<String> },
+}
+
+impl Annotations {
+
+    /// Returns the height taken by title and subtitle above the chart.
+    pub(crate) fn header_height(&self, theme: &Theme) -> f32 {
+        let title = self.title.as_ref().map_or(0.0, |_| theme.title_font_size * LINE_HEIGHT);
+        let subtitle = self.subtitle.as_ref().map_or(0.0, |_| theme.font_size * 1.2 * LINE_HEIGHT);
+        if title + subtitle > 0.0 { title + subtitle + TITLE_PADDING } else { 0.0 }
+    }
+
+    /// Returns the height taken by the footnote below the chart.
+    pub(crate) fn footer_height(&self, theme: &Theme) -> f32 {
+        self.footnote.as_ref().map_or(0.0, |_| theme.font_size * LINE_HEIGHT + TITLE_PADDING)
+    }
+
+    /// Places the marks in screen space. `map_domain` and `map_value` map data coordinates to chart
+    /// space, which spans `chart_extent` from the origin and is mapped to the screen by `view_matrix`.
+    /// Bands are clipped to the plot, lines and bands outside it are left out.
+    pub(crate) fn place(
+        &self,
+        map_domain: impl Fn(f64) -> f32,
+        map_value: impl Fn(f64) -> f32,
+        chart_extent: (f32, f32),
+        view_matrix: &Matrix3<f32>,
+    ) -> Vec<PlacedAnnotation> {
+        let (width, height) = chart_extent;
+        let to_screen = |x: f32, y: f32| view_matrix.transform_point(&Point2::new(x, y));
+        let point = |(x, y): (f64, f64)| to_screen(map_domain(x), map_value(y));
+        let map = |axis: AnnotationAxis, value: f64| match axis {
+            AnnotationAxis::Domain => map_domain(value),
+            AnnotationAxis::Value => map_value(value),
+        };
+        // Returns the range of chart space along the axis within the plot, `None` if it lies outside.
+        let clip = |axis: AnnotationAxis, start: f32, end: f32| {
+            let length = match axis {
+                AnnotationAxis::Domain => width,
+                AnnotationAxis::Value => height,
+            };
+            let (low, high) = (start.min(end).max(0.0), start.max(end).min(length));
+            (low <= high).then_some((low, high))
+        };
+        // Returns the corners of the range along the axis, spanning the whole plot across it.
+        let span = |axis: AnnotationAxis, (from, to): (f32, f32)| match axis {
+            AnnotationAxis::Domain => (to_screen(from, 0.0), to_screen(to, height)),
+            AnnotationAxis::Value => (to_screen(0.0, from), to_screen(width, to)),
+        };
+        self.marks.iter()
+            .filter_map(|mark| match mark {
+                Annotation::ReferenceLine { axis, value, label } => {
+                    let position = map(*axis, *value);
+                    let (from, to) = span(*axis, clip(*axis, position, position)?);
+                    Some(PlacedAnnotation::Line { from, to, label: Clone::clone(label) })
+                },
+                Annotation::Band { axis, from, to, label } => {
+                    let (start, end) = span(*axis, clip(*axis, map(*axis, *from), map(*axis, *to))?);
+                    Some(PlacedAnnotation::Band {
+                        min: Point2::new(start.x.min(end.x), start.y.min(end.y)),
+                        max: Point2::new(start.x.max(end.x), start.y.max(end.y)),
+                        label: Clone::clone(label),
+                    })
+                },
+                Annotation::Text { at, text } => Some(PlacedAnnotation::Text { at: point(*at), text: Clone::clone(text) }),
+                Annotation::Arrow { from, to, text } => Some(PlacedAnnotation::Arrow { from: point(*from), to: point(*to), text: Clone::clone(text) }),
+            })
+            .collect()
+    }
+}
+
+/// Title and subtitle above and the footnote below a chart of `options.extent`.
+#[component]
+pub(crate) fn Titles(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+    let width = move || options.with(|options| options.extent.width);
+    let height = move || options.with(|options| options.extent.height);
+    let text = move |select: fn(&Annotations) -> &Option<String>| {
+        move || options.with(|options| Clone::clone(select(&options.annotations)))
+    };
+    let title = text(|annotations| &annotations.title);
+    let subtitle = text(|annotations| &annotations.subtitle);
+    let footnote = text(|annotations| &annotations.footnote);
+
+    let title_y = move || theme.with(|theme| TITLE_PADDING + theme.title_font_size);
+    let subtitle_y = move || theme.with(|theme| {
+        let title = if title().is_some() { theme.title_font_size * LINE_HEIGHT } else { 0.0 };
+        TITLE_PADDING + title + theme.font_size * 1.2
+    });
+    let footnote_y = move || height() - TITLE_PADDING;
+
+    view! { cx,
+        <g
+            fill=move || theme.with(|theme| Clone::clone(&theme.text))
+            font-family=move || theme.with(|theme| Clone::clone(&theme.font_family))
+            pointer-events="none"
+        >
+            {move || title().map(|title| view! { cx,
+                <text x=move || width() * 0.5 y=title_y text-anchor="middle" font-size=move || theme.with(|theme| theme.title_font_size) font-weight="bold">
+                    {title}
+                </text>
+            })}
+            {move || subtitle().map(|subtitle| view! { cx,
+                <text x=move || width() * 0.5 y=subtitle_y text-anchor="middle" font-size=move || theme.with(|theme| theme.font_size * 1.2)>
+                    {subtitle}
+                </text>
+            })}
+            {move || footnote().map(|footnote| view! { cx,
+                <text x=TITLE_PADDING y=footnote_y font-size=move || theme.with(|theme| theme.font_size) opacity="0.7">
+                    {footnote}
+                </text>
+            })}
+        </g>
+    }
+}
+
+/// Draws annotations placed in screen space.
+#[component]
+pub(crate) fn AnnotationLayer(
+    cx: Scope,
+    annotations: Memo<Vec<PlacedAnnotation>>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+    let color = move || theme.with(|theme| Clone::clone(&theme.axis));
+    let font_size = move || theme.with(|theme| theme.font_size);
+
+    let annotation_view = move |cx, index: usize| {
+        move || annotations.with(|annotations| annotations.get(index).cloned()).map(|annotation| match annotation {
+            PlacedAnnotation::Line { from, to, label } => view! { cx,
+                <g>
+                    <line x1=from.x y1=from.y x2=to.x y2=to.y stroke=color stroke-width="1" stroke-dasharray="6 3"/>
+                    {label.map(|label| view! { cx,
+                        <text x=to.x.max(from.x) - 2.0 y=to.y.min(from.y) - 3.0 text-anchor="end" font-size=font_size fill=color>{label}</text>
+                    })}
+                </g>
+            },
+            PlacedAnnotation::Band { min, max, label } => view! { cx,
+                <g>
+                    <rect x=min.x y=min.y width=max.x - min.x height=max.y - min.y fill=color fill-opacity=BAND_OPACITY/>
+                    {label.map(|label| view! { cx,
+                        <text x=min.x + 3.0 y=min.y + font_size() + 2.0 font-size=font_size fill=color>{label}</text>
+                    })}
+                </g>
+            },
+            PlacedAnnotation::Text { at, text } => view! { cx,
+                <g>
+                    <text x=at.x y=at.y text-anchor="middle" font-size=font_size fill=color>{text}</text>
+                </g>
+            },
+            PlacedAnnotation::Arrow { from, to, text } => {
+                let direction = (to - from).try_normalize(f32::EPSILON).unwrap_or_default();
+                let normal = Vector2::new(-direction.y, direction.x);
+                let base = to - direction * ARROW_HEAD_SIZE;
+                let left = base + normal * ARROW_HEAD_SIZE * 0.5;
+                let right = base - normal * ARROW_HEAD_SIZE * 0.5;
+                let head = format!("M {} {} L {} {} L {} {} Z", to.x, to.y, left.x, left.y, right.x, right.y);
+                view! { cx,
+                    <g>
+                        <line x1=from.x y1=from.y x2=base.x y2=base.y stroke=color stroke-width="1"/>
+                        <path d=head fill=color/>
+                        {text.map(|text| view! { cx,
+                            <text x=from.x y=from.y - 4.0 text-anchor="middle" font-size=font_size fill=color>{text}</text>
+                        })}
+                    </g>
+                }
+            },
+        })
+    };
+
+    view! { cx,
+        <g pointer-events="none" font-family=move || theme.with(|theme| Clone::clone(&theme.font_family))>
+            <For each=move || (0..annotations.with(|annotations| annotations.len())).collect::<Vec<_>>() key=|index| *index view=annotation_view/>
+        </g>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotations(marks: Vec<Annotation>) -> Annotations {
+        Annotations { marks, ..Default::default() }
+    }
+
+    /// Places marks on a plot of 100 by 50 pixels at (40, 10), with values growing upwards.
+    fn place(marks: Vec<Annotation>) -> Vec<PlacedAnnotation> {
+        let view_matrix = Matrix3::new(
+            1.0, 0.0, 40.0,
+            0.0, -1.0, 60.0,
+            0.0, 0.0, 1.0,
+        );
+        annotations(marks).place(|x| x as f32 * 10.0, |y| y as f32 * 5.0, (100.0, 50.0), &view_matrix)
+    }
+
+    #[test]
+    fn marks_follow_the_scales_and_the_view() {
+        let placed = place(vec![
+            Annotation::Text { at: (2.0, 4.0), text: String::from("peak") },
+            Annotation::Arrow { from: (0.0, 0.0), to: (10.0, 10.0), text: None },
+            Annotation::ReferenceLine { axis: AnnotationAxis::Value, value: 6.0, label: None },
+            Annotation::ReferenceLine { axis: AnnotationAxis::Domain, value: 3.0, label: None },
+        ]);
+        assert_eq!(placed, vec![
+            PlacedAnnotation::Text { at: Point2::new(60.0, 40.0), text: String::from("peak") },
+            PlacedAnnotation::Arrow { from: Point2::new(40.0, 60.0), to: Point2::new(140.0, 10.0), text: None },
+            PlacedAnnotation::Line { from: Point2::new(40.0, 30.0), to: Point2::new(140.0, 30.0), label: None },
+            PlacedAnnotation::Line { from: Point2::new(70.0, 60.0), to: Point2::new(70.0, 10.0), label: None },
+        ]);
+    }
+
+    #[test]
+    fn bands_and_lines_are_clipped_to_the_plot() {
+        let placed = place(vec![
+            Annotation::Band { axis: AnnotationAxis::Value, from: -2.0, to: 4.0, label: None },
+            Annotation::Band { axis: AnnotationAxis::Domain, from: 15.0, to: 8.0, label: None },
+            Annotation::Band { axis: AnnotationAxis::Domain, from: 11.0, to: 12.0, label: None },
+            Annotation::ReferenceLine { axis: AnnotationAxis::Value, value: 12.0, label: None },
+            Annotation::ReferenceLine { axis: AnnotationAxis::Domain, value: -1.0, label: None },
+        ]);
+        assert_eq!(placed, vec![
+            PlacedAnnotation::Band { min: Point2::new(40.0, 40.0), max: Point2::new(140.0, 60.0), label: None },
+            PlacedAnnotation::Band { min: Point2::new(120.0, 10.0), max: Point2::new(140.0, 60.0), label: None },
+        ]);
+    }
+
+    #[test]
+    fn titles_and_footnote_take_room_only_when_set() {
+        let theme = Theme { font_size: 10.0, title_font_size: 20.0, ..Theme::light() };
+        let mut annotations = annotations(Vec::new());
+        assert_eq!(annotations.header_height(&theme), 0.0);
+        assert_eq!(annotations.footer_height(&theme), 0.0);
+
+        annotations.title = Some(String::from("Sales"));
+        assert!((annotations.header_height(&theme) - 34.0).abs() < 1e-4);
+        annotations.subtitle = Some(String::from("by region"));
+        assert!((annotations.header_height(&theme) - 50.8).abs() < 1e-4);
+        annotations.title = None;
+        assert!((annotations.header_height(&theme) - 22.8).abs() < 1e-4);
+
+        annotations.footnote = Some(String::from("Source: census"));
+        assert!((annotations.footer_height(&theme) - 20.0).abs() < 1e-4);
+    }
+}
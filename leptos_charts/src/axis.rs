@@ -0,0 +1,296 @@
+use leptos::{component, IntoView, Memo, Scope, SignalGet, SignalWith, view};
+use nalgebra::{Matrix3, Point2};
+
+use crate::Extent2;
+use crate::theme::use_theme;
+
+pub(crate) const TICK_SIZE: f32 = 6.0;
+pub(crate) const LABEL_PADDING: f32 = 3.0;
+
+/// Approximate advance of a single glyph relative to the font size, used to reserve space
+/// for labels before they are rendered.
+pub(crate) const GLYPH_ASPECT: f32 = 0.6;
+
+pub(crate) const PIXELS_PER_TICK: f32 = 50.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AxisPosition {
+    Left,
+    Bottom,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Coordinate {
+    X,
+    Y,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tick {
+    /// Position of the tick along the axis in chart space.
+    pub position: f32,
+    pub label: String,
+}
+
+/// Returns a step of 1, 2 or 5 times a power of ten close to `range / count`.
+pub fn nice_step(range: f32, count: usize) -> f32 {
+    let raw_step = range / count.max(1) as f32;
+    let magnitude = 10f32.powf(raw_step.log10().floor());
+    let fraction = raw_step / magnitude;
+    let nice_fraction = if fraction <= 1.0 {
+        1.0
+    }
+    else if fraction <= 2.0 {
+        2.0
+    }
+    else if fraction <= 5.0 {
+        5.0
+    }
+    else {
+        10.0
+    };
+    nice_fraction * magnitude
+}
+
+/// Extends `min..max` outwards to the nearest multiples of a nice step.
+pub fn nice_range(min: f32, max: f32, count: usize) -> (f32, f32) {
+    if !min.is_finite() || !max.is_finite() || min > max {
+        return (0.0, 1.0)
+    }
+    if min == max {
+        return if min == 0.0 { (0.0, 1.0) } else { nice_range(min.min(0.0), max.max(0.0), count) }
+    }
+    let step = nice_step(max - min, count);
+    ((min / step).floor() * step, (max / step).ceil() * step)
+}
+
+/// Computes the nice tick values covering `min..max`.
+pub fn ticks(min: f32, max: f32, count: usize) -> Vec<f32> {
+    if !min.is_finite() || !max.is_finite() || min >= max {
+        return Vec::new()
+    }
+    let step = nice_step(max - min, count);
+    let start = (min / step).ceil() as i64;
+    let end = (max / step).floor() as i64;
+    (start..=end)
+        .map(|index| index as f32 * step)
+        .collect()
+}
+
+/// Formats a tick value with just enough decimals to distinguish neighbouring ticks.
+pub fn format_tick(value: f32, step: f32) -> String {
+    let decimals = (-step.abs().log10().floor()).max(0.0) as usize;
+    format!("{:.*}", decimals, value)
+}
+
+/// Returns the number of ticks that fit an axis of the given length.
+pub(crate) fn tick_count(length: f32) -> usize {
+    (length / PIXELS_PER_TICK).floor().max(2.0) as usize
+}
+
+/// Returns the margin required to fit a tick and a label of the given width.
+pub(crate) fn label_margin(label_width: f32) -> f32 {
+    TICK_SIZE + LABEL_PADDING * 2.0 + label_width
+}
+
+/// Returns the approximate rendered width of the widest label.
+pub(crate) fn label_width<'a>(labels: impl Iterator<Item = &'a String>, font_size: f32) -> f32 {
+    labels.map(|label| label.chars().count()).max().unwrap_or_default() as f32 * font_size * GLYPH_ASPECT
+}
+
+/// Returns the margin at the top and right of a plot, leaving room for labels overhanging its edges.
+pub(crate) fn edge_margin(font_size: f32) -> f32 {
+    font_size
+}
+
+/// Returns the margin below a plot required to fit the ticks and labels of a bottom axis.
+pub(crate) fn bottom_margin(font_size: f32) -> f32 {
+    TICK_SIZE + LABEL_PADDING * 2.0 + font_size
+}
+
+#[component]
+pub fn Axis(
+    cx: Scope,
+    /// Side of the plot the axis is rendered at.
+    position: AxisPosition,
+    /// Coordinate of the chart space the ticks are placed along.
+    coordinate: Coordinate,
+    view_matrix: Memo<Matrix3<f32>>,
+    /// Extent of the plot in chart space, i.e. before applying the view matrix.
+    chart_extent: Memo<Extent2<f32>>,
+    ticks: Memo<Vec<Tick>>,
+    #[prop(optional)]
+    grid: bool,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+
+    let transform = move |point: Point2<f32>| {
+        view_matrix.with(|view_matrix| view_matrix.transform_point(&point))
+    };
+
+    let chart_point = move |position: f32, offset: f32| match coordinate {
+        Coordinate::X => Point2::new(position, offset),
+        Coordinate::Y => Point2::new(offset, position),
+    };
+
+    // The axis sits at whichever end of the other coordinate ends up closest to its side of the plot,
+    // gridlines extend towards the opposite end.
+    let offsets = move || {
+        let depth = chart_extent.with(|extent| match coordinate {
+            Coordinate::X => extent.height,
+            Coordinate::Y => extent.width,
+        });
+        let (start, end) = (transform(chart_point(0.0, 0.0)), transform(chart_point(0.0, depth)));
+        let start_is_closer = match position {
+            AxisPosition::Left => start.x <= end.x,
+            AxisPosition::Bottom => start.y >= end.y,
+        };
+        if start_is_closer { (0.0, depth) } else { (depth, 0.0) }
+    };
+
+    let domain_line = move || {
+        let length = chart_extent.with(|extent| match coordinate {
+            Coordinate::X => extent.width,
+            Coordinate::Y => extent.height,
+        });
+        let (offset, _) = offsets();
+        let start = transform(chart_point(0.0, offset));
+        let end = transform(chart_point(length, offset));
+        format!("M {} {} L {} {}", start.x, start.y, end.x, end.y)
+    };
+
+    let tick_views = move || {
+        let (offset, grid_offset) = offsets();
+        let theme = theme.get();
+        ticks.with(|ticks| {
+            ticks.iter()
+                .map(|tick| {
+                    let anchor = transform(chart_point(tick.position, offset));
+                    let grid_end = transform(chart_point(tick.position, grid_offset));
+                    let (tick_end, label_x, label_y, text_anchor, baseline) = match position {
+                        AxisPosition::Left => (
+                            Point2::new(anchor.x - TICK_SIZE, anchor.y),
+                            anchor.x - TICK_SIZE - LABEL_PADDING,
+                            anchor.y,
+                            "end",
+                            "middle",
+                        ),
+                        AxisPosition::Bottom => (
+                            Point2::new(anchor.x, anchor.y + TICK_SIZE),
+                            anchor.x,
+                            anchor.y + TICK_SIZE + LABEL_PADDING,
+                            "middle",
+                            "hanging",
+                        ),
+                    };
+                    let grid_line = grid.then(|| view! { cx,
+                        <line
+                            x1=anchor.x y1=anchor.y
+                            x2=grid_end.x y2=grid_end.y
+                            stroke=Clone::clone(&theme.grid)
+                            stroke-width=theme.grid_stroke_width
+                        />
+                    });
+                    view! { cx,
+                        <g>
+                            {grid_line}
+                            <line
+                                x1=anchor.x y1=anchor.y
+                                x2=tick_end.x y2=tick_end.y
+                                stroke=Clone::clone(&theme.axis)
+                                stroke-width=theme.axis_stroke_width
+                            />
+                            <text
+                                x=label_x
+                                y=label_y
+                                text-anchor=text_anchor
+                                dominant-baseline=baseline
+                                fill=Clone::clone(&theme.text)
+                                font-family=Clone::clone(&theme.font_family)
+                                font-size=theme.font_size
+                            >
+                                {Clone::clone(&tick.label)}
+                            </text>
+                        </g>
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    view! { cx,
+        <g>
+            {tick_views}
+            <path
+                d=domain_line
+                stroke=move || theme.with(|theme| Clone::clone(&theme.axis))
+                stroke-width=move || theme.with(|theme| theme.axis_stroke_width)
+                fill="none"
+            />
+        </g>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() <= expected.abs() * 1e-5, "{} is not {}", actual, expected);
+    }
+
+    #[test]
+    fn steps_are_one_two_or_five_times_a_power_of_ten() {
+        assert_close(nice_step(100.0, 10), 10.0);
+        assert_close(nice_step(15.0, 10), 2.0);
+        assert_close(nice_step(30.0, 10), 5.0);
+        assert_close(nice_step(70.0, 10), 10.0);
+        assert_close(nice_step(0.3, 5), 0.1);
+        assert_close(nice_step(1200.0, 5), 500.0);
+        assert_close(nice_step(10.0, 0), 10.0);
+    }
+
+    #[test]
+    fn ranges_extend_to_multiples_of_the_step() {
+        assert_eq!(nice_range(0.0, 97.0, 10), (0.0, 100.0));
+        assert_eq!(nice_range(-13.0, 42.0, 5), (-20.0, 60.0));
+        assert_eq!(nice_range(-42.0, -13.0, 5), (-50.0, -10.0));
+    }
+
+    #[test]
+    fn empty_and_invalid_ranges_fall_back_to_a_span_including_zero() {
+        assert_eq!(nice_range(0.0, 0.0, 5), (0.0, 1.0));
+        assert_eq!(nice_range(7.0, 7.0, 5), (0.0, 8.0));
+        assert_eq!(nice_range(-7.0, -7.0, 5), (-8.0, 0.0));
+        assert_eq!(nice_range(3.0, 1.0, 5), (0.0, 1.0));
+        assert_eq!(nice_range(f32::NAN, 1.0, 5), (0.0, 1.0));
+        assert_eq!(nice_range(0.0, f32::INFINITY, 5), (0.0, 1.0));
+    }
+
+    #[test]
+    fn ticks_are_the_multiples_of_the_step_within_the_range() {
+        assert_eq!(ticks(0.0, 100.0, 5), vec![0.0, 20.0, 40.0, 60.0, 80.0, 100.0]);
+        assert_eq!(ticks(-13.0, 42.0, 10), vec![-10.0, 0.0, 10.0, 20.0, 30.0, 40.0]);
+        assert!(ticks(1.0, 1.0, 5).is_empty());
+        assert!(ticks(2.0, 1.0, 5).is_empty());
+        assert!(ticks(f32::NAN, 1.0, 5).is_empty());
+    }
+
+    #[test]
+    fn tick_labels_have_the_decimals_of_the_step() {
+        assert_eq!(format_tick(40.0, 20.0), "40");
+        assert_eq!(format_tick(2.0, 1.0), "2");
+        assert_eq!(format_tick(0.5, 0.5), "0.5");
+        assert_eq!(format_tick(0.25, 0.05), "0.25");
+        assert_eq!(format_tick(-1.5, 0.5), "-1.5");
+    }
+
+    #[test]
+    fn longer_axes_get_more_ticks() {
+        assert_eq!(tick_count(0.0), 2);
+        assert_eq!(tick_count(120.0), 2);
+        assert_eq!(tick_count(500.0), 10);
+        assert_eq!(tick_count(549.0), 10);
+    }
+}
@@ -0,0 +1,830 @@
+use std::collections::BTreeSet;
+
+use leptos::{component, create_memo, create_rw_signal, For, IntoView, Memo, ReadSignal, RwSignal, Scope, SignalGet, SignalWith, view};
+use nalgebra::{Matrix3, Point2, Rotation2, Vector2};
+
+use crate::{BarLayout, ChartOptions, ColorPallet, Extent2, Margin, Orientation, Series};
+use crate::a11y::{describe_chart, describe_value, DataTable, Describe};
+use crate::annotation::{AnnotationLayer, Titles};
+use crate::axis::{self, label_margin, Axis, AxisPosition, Coordinate, Tick};
+use crate::color::ColorScale;
+use crate::scale::{BandScale, LinearScale, Scale};
+use crate::export::ChartExport;
+use crate::frame::ChartFrame;
+use crate::interaction::{ChartCallback, ChartEvents, Highlight, Interaction, SelectCallback, Selection};
+use crate::legend::LegendItem;
+use crate::responsive::resolve_options;
+use crate::theme::use_theme;
+use crate::tooltip::{Datum, HitShape, HitTest, Metric, Target, TooltipRenderer};
+use crate::transition::{create_keyed_lookup, create_tween, unique_keys, Keyed, Tween};
+
+const DEFAULT_TICK_COUNT: usize = 5;
+const BAR_PADDING: f32 = 0.1;
+/// Maximum distance in pixels between the pointer and a bar to count as hovering it.
+const HIT_DISTANCE: f32 = 4.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarChartData {
+    domain: Vec<String>,
+    series: Vec<Series>,
+    min_value: f32,
+    max_value: f32,
+    baseline: f32,
+    stacked_range: (f32, f32),
+}
+
+/// The extent of a single bar along the value axis and its slot within the category.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BarSegment {
+    pub category: usize,
+    pub series: usize,
+    pub slot: usize,
+    pub slots: usize,
+    pub start: f32,
+    pub end: f32,
+}
+
+/// A bar in chart space, keyed by its domain label, numbered if repeated, and series for transitions.
+#[derive(Debug, Clone, PartialEq)]
+struct BarGeometry {
+    series: usize,
+    category: usize,
+    x: f32,
+    width: f32,
+    start: f32,
+    end: f32,
+    color: String,
+    /// Value of the bar in words for screen readers.
+    description: String,
+}
+
+impl Tween for BarGeometry {
+
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        BarGeometry {
+            x: self.x.tween(&to.x, t),
+            width: self.width.tween(&to.width, t),
+            start: self.start.tween(&to.start, t),
+            end: self.end.tween(&to.end, t),
+            ..Clone::clone(to)
+        }
+    }
+
+    /// Bars grow from and shrink to their start.
+    fn collapsed(&self) -> Self {
+        BarGeometry { end: self.start, ..Clone::clone(self) }
+    }
+}
+
+impl BarChartData {
+
+    pub fn new(domain: Vec<String>, values: Vec<f32>) -> Self {
+        Self::with_series(domain, vec![Series::new(String::new(), values)])
+    }
+
+    pub fn with_series(domain: Vec<String>, series: Vec<Series>) -> Self {
+        let mut data = BarChartData {
+            domain,
+            series,
+            min_value: 0.0,
+            max_value: 0.0,
+            baseline: 0.0,
+            stacked_range: (0.0, 0.0),
+        };
+        data.update();
+        data
+    }
+
+    pub fn set_domain(&mut self, domain: Vec<String>) {
+        self.domain = domain
+    }
+
+    /// Replaces all series by a single unnamed series with the given values.
+    pub fn set_values(&mut self, values: Vec<f32>) {
+        self.set_series(vec![Series::new(String::new(), values)]);
+    }
+
+    pub fn set_series(&mut self, series: Vec<Series>) {
+        self.series = series;
+        self.update();
+    }
+
+    /// Sets the value bars grow from. Values below the baseline are drawn downwards.
+    ///
+    /// Values are positions on the value axis in every layout: a bar spans from the baseline to
+    /// its value when grouped, and stacked bars add their distances from the baseline.
+    pub fn set_baseline(&mut self, baseline: f32) {
+        self.baseline = baseline;
+        self.update();
+    }
+
+    pub fn domain(&self) -> &[String] {
+        &self.domain
+    }
+
+    pub fn series(&self) -> &[Series] {
+        &self.series
+    }
+
+    pub fn baseline(&self) -> f32 {
+        self.baseline
+    }
+
+    /// Returns the range covered by the bars in the given layout including the baseline,
+    /// extended to nice tick values.
+    pub fn value_range(&self, layout: BarLayout) -> (f32, f32) {
+        let (min, max) = match layout {
+            BarLayout::Grouped => (self.min_value.min(self.baseline), self.max_value.max(self.baseline)),
+            BarLayout::Stacked => self.stacked_range,
+            BarLayout::Normalized => {
+                let has_negatives = self.values().any(|value| value < self.baseline);
+                let has_positives = self.values().any(|value| value > self.baseline);
+                (if has_negatives { -1.0 } else { 0.0 }, if has_positives || !has_negatives { 1.0 } else { 0.0 })
+            },
+        };
+        LinearScale::new((min, max), (0.0, 1.0))
+            .nice(DEFAULT_TICK_COUNT)
+            .domain()
+    }
+
+    /// Returns the lowest and the highest value bars are coloured by, `(0.0, 1.0)` if there are none.
+    pub(crate) fn color_range(&self) -> (f32, f32) {
+        if self.min_value > self.max_value { (0.0, 1.0) } else { (self.min_value, self.max_value) }
+    }
+
+    /// Returns a copy in which the series at the `hidden` indices have no values, leaving the
+    /// indices and therefore the colours of the other series unchanged.
+    pub(crate) fn without_series(&self, hidden: &BTreeSet<usize>) -> Self {
+        let mut data = Clone::clone(self);
+        if !hidden.is_empty() {
+            data.series.iter_mut()
+                .enumerate()
+                .filter(|(index, _)| hidden.contains(index))
+                .for_each(|(_, series)| series.values.iter_mut().for_each(|value| *value = f32::NAN));
+            data.update();
+        }
+        data
+    }
+
+    /// Returns a copy in which the categories at the `hidden` indices have no values.
+    pub(crate) fn without_categories(&self, hidden: &BTreeSet<usize>) -> Self {
+        let mut data = Clone::clone(self);
+        if !hidden.is_empty() {
+            data.series.iter_mut()
+                .flat_map(|series| series.values.iter_mut().enumerate())
+                .filter(|(index, _)| hidden.contains(index))
+                .for_each(|(_, value)| *value = f32::NAN);
+            data.update();
+        }
+        data
+    }
+
+    /// Returns the labels of the categories numbered by their repetitions, to key their bars.
+    pub(crate) fn category_keys(&self) -> Vec<(String, usize)> {
+        unique_keys(self.domain.iter().cloned())
+    }
+
+    /// Whether the bars are told apart by their category rather than their series, which is the
+    /// case for a single unnamed series. Its bars are coloured one by one and its legend lists
+    /// the categories.
+    pub(crate) fn colors_by_category(&self) -> bool {
+        matches!(self.series.as_slice(), [series] if series.name.is_empty())
+    }
+
+    /// Computes the bars of all series in value space.
+    pub(crate) fn segments(&self, layout: BarLayout) -> Vec<BarSegment> {
+        let slots = match layout {
+            BarLayout::Grouped => self.series.len().max(1),
+            BarLayout::Stacked | BarLayout::Normalized => 1,
+        };
+        let origin = match layout {
+            BarLayout::Normalized => 0.0,
+            _ => self.baseline,
+        };
+        (0..self.domain.len())
+            .flat_map(|category| {
+                let total = self.series.iter()
+                    .map(|series| self.offset_at(series, category).abs())
+                    .sum::<f32>();
+                let (mut positive, mut negative) = (origin, origin);
+                self.series.iter()
+                    .enumerate()
+                    .map(|(index, series)| {
+                        let value = Self::value_at(series, category);
+                        let (slot, start, end) = match layout {
+                            BarLayout::Grouped => {
+                                let present = series.values.get(category).map_or(false, |value| !value.is_nan());
+                                (index, origin, if present { value } else { origin })
+                            },
+                            BarLayout::Stacked | BarLayout::Normalized => {
+                                let offset = self.offset_at(series, category);
+                                let offset = if layout == BarLayout::Normalized && total > 0.0 { offset / total } else { offset };
+                                let stack = if offset >= 0.0 { &mut positive } else { &mut negative };
+                                let start = *stack;
+                                *stack += offset;
+                                (0, start, *stack)
+                            },
+                        };
+                        BarSegment { category, series: index, slot, slots, start, end }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Returns the fill of the bar of `series` in `category`, coloured by value if a scale is given.
+    pub(crate) fn bar_color(&self, series: usize, category: usize, color_pallet: ColorPallet, value_colors: Option<&ColorScale>) -> String {
+        match value_colors {
+            Some(scale) => {
+                let value = self.series.get(series).and_then(|series| series.values.get(category)).cloned();
+                scale.color(value.unwrap_or(f32::NAN))
+            },
+            None if self.colors_by_category() => color_pallet.color_of(category, self.domain.len()),
+            None => color_pallet.color_of(series, self.series.len()),
+        }
+    }
+
+    fn update(&mut self) {
+        let (min_value, max_value) = Self::min_and_max(self.values());
+        self.min_value = min_value;
+        self.max_value = max_value;
+        self.stacked_range = self.segments(BarLayout::Stacked).iter()
+            .fold((self.baseline, self.baseline), |(min, max), segment| {
+                (min.min(segment.end), max.max(segment.end))
+            });
+    }
+
+    fn values(&self) -> impl Iterator<Item = f32> + '_ {
+        self.series.iter()
+            .flat_map(|series| series.values.iter().cloned())
+            .filter(|value| !value.is_nan())
+    }
+
+    /// Returns the value of `series` for `category`, treating missing values as zero.
+    fn value_at(series: &Series, category: usize) -> f32 {
+        series.values.get(category).cloned().filter(|value| !value.is_nan()).unwrap_or(0.0)
+    }
+
+    /// Returns how far the value of `series` for `category` reaches from the baseline, treating
+    /// missing values as zero.
+    fn offset_at(&self, series: &Series, category: usize) -> f32 {
+        series.values.get(category).filter(|value| !value.is_nan()).map_or(0.0, |value| value - self.baseline)
+    }
+
+    fn min_and_max(values: impl Iterator<Item = f32>) -> (f32, f32) {
+        values.fold((f32::MAX, f32::MIN), |(min, max), value| (min.min(value), max.max(value)))
+    }
+}
+
+impl Describe for BarChartData {
+
+    type Context = ();
+
+    fn summary(&self, _: &()) -> String {
+        let mut summary = format!("Bar chart of {} categories", self.domain.len());
+        if self.series.len() > 1 {
+            summary.push_str(&format!(" in {} series", self.series.len()));
+        }
+        if self.values().next().is_some() {
+            summary.push_str(&format!(", values from {} to {}", self.min_value, self.max_value));
+        }
+        summary.push('.');
+        summary
+    }
+
+    /// Returns the values as a table with one row per category and one column per series.
+    fn table(&self, caption: String, _: &()) -> DataTable {
+        let header = std::iter::once(String::new())
+            .chain(self.series.iter().map(|series| {
+                if series.name.is_empty() { String::from("Value") } else { Clone::clone(&series.name) }
+            }))
+            .collect();
+        let rows = self.domain.iter()
+            .enumerate()
+            .map(|(category, label)| {
+                std::iter::once(Clone::clone(label))
+                    .chain(self.series.iter().map(|series| {
+                        series.values.get(category)
+                            .filter(|value| !value.is_nan())
+                            .map_or_else(String::new, |value| format!("{}", value))
+                    }))
+                    .collect()
+            })
+            .collect();
+        DataTable { caption, header, rows }
+    }
+}
+
+#[component]
+pub fn BarChart(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<BarChartData>,
+    /// Renders the content of the tooltip shown for the bar under the pointer.
+    #[prop(optional, into)]
+    tooltip: Option<TooltipRenderer>,
+    /// Called when a bar is selected by click or keyboard, or with `None` when the selection is cleared.
+    #[prop(optional, into)]
+    on_select: Option<SelectCallback>,
+    /// Called when the pointer or the keyboard focus moves onto a bar.
+    #[prop(optional, into)]
+    on_hover: Option<ChartCallback>,
+    /// Called on every click on a bar.
+    #[prop(optional, into)]
+    on_click: Option<ChartCallback>,
+    /// Handle to export the chart as SVG or PNG.
+    #[prop(optional)]
+    export: Option<ChartExport>,
+    /// The selected bar, set by the chart and highlighted when set elsewhere.
+    #[prop(optional)]
+    selected: Option<RwSignal<Option<Selection>>>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+    let size = create_rw_signal(cx, None);
+    let options = resolve_options(cx, options, size.read_only());
+    let font_size = move || theme.with(|theme| theme.font_size);
+    let chart_width = move || options.with(|options| options.extent.width);
+    let chart_height = move || options.with(|options| options.extent.height);
+    let orientation = move || options.with(|options| options.orientation);
+
+    let hidden = create_rw_signal(cx, BTreeSet::new());
+
+    // Entries hide series, or the categories of a single unnamed series. Several unnamed series
+    // have nothing to list.
+    let legend = create_memo(cx, move |_| {
+        let color_pallet = options.with(|options| theme.with(|theme| options.categorical_pallet(theme)));
+        data.with(|data| {
+            if data.colors_by_category() {
+                data.domain.iter()
+                    .enumerate()
+                    .map(|(index, label)| LegendItem::new(Clone::clone(label), color_pallet.color_of(index, data.domain.len())))
+                    .collect::<Vec<_>>()
+            }
+            else if data.series.iter().all(|series| series.name.is_empty()) {
+                Vec::new()
+            }
+            else {
+                data.series.iter()
+                    .enumerate()
+                    .map(|(index, series)| LegendItem::new(Clone::clone(&series.name), color_pallet.color_of(index, data.series.len())))
+                    .collect::<Vec<_>>()
+            }
+        })
+    });
+
+    let data = create_memo(cx, move |_| {
+        hidden.with(|hidden| {
+            data.with(|data| if data.colors_by_category() { data.without_categories(hidden) } else { data.without_series(hidden) })
+        })
+    });
+
+    let domain_label_width = create_memo(cx, move |_| {
+        data.with(|data| {
+            axis::label_width(data.domain.iter(), font_size())
+        })
+    });
+
+    let value_labels = create_memo(cx, move |_| {
+        let length = match orientation() {
+            Orientation::Vertical => chart_height() - axis::edge_margin(font_size()) - axis::bottom_margin(font_size()),
+            Orientation::Horizontal => chart_width() - axis::edge_margin(font_size()) - label_margin(domain_label_width.get()),
+        };
+        let count = axis::tick_count(length);
+        let layout = options.with(|options| options.bar.layout);
+        let scale = data.with(|data| LinearScale::new(data.value_range(layout), (0.0, length)));
+        let step = scale.tick_step(count);
+        scale.ticks(count).into_iter()
+            .map(|value| {
+                let label = match layout {
+                    BarLayout::Normalized => format!("{}%", axis::format_tick(value * 100.0, step * 100.0)),
+                    _ => axis::format_tick(value, step),
+                };
+                (value, label)
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let margin = create_memo(cx, move |_| {
+        let left = match orientation() {
+            Orientation::Vertical => value_labels.with(|labels| {
+                axis::label_width(labels.iter().map(|(_, label)| label), font_size())
+            }),
+            Orientation::Horizontal => domain_label_width.get(),
+        };
+        let (header, footer) = options.with(|options| {
+            theme.with(|theme| (options.annotations.header_height(theme), options.annotations.footer_height(theme)))
+        });
+        let edge = axis::edge_margin(font_size());
+        Margin::new(edge + header, edge, axis::bottom_margin(font_size()) + footer, label_margin(left))
+    });
+
+    let plot_extent = create_memo(cx, move |_| {
+        options.with(|options| margin.with(|margin| margin.shrink(&options.extent)))
+    });
+
+    // Bars are laid out in chart space with the categories along x and the values along y,
+    // the view matrix takes care of rotating them for horizontal charts.
+    let chart_extent = create_memo(cx, move |_| {
+        let plot_extent = plot_extent.get();
+        match orientation() {
+            Orientation::Vertical => plot_extent,
+            Orientation::Horizontal => Extent2::new(plot_extent.height, plot_extent.width),
+        }
+    });
+
+    let value_scale = create_memo(cx, move |_| {
+        let height = chart_extent.with(|extent| extent.height);
+        let layout = options.with(|options| options.bar.layout);
+        data.with(|data| LinearScale::new(data.value_range(layout), (0.0, height)))
+    });
+
+    let domain_scale = create_memo(cx, move |_| {
+        let width = chart_extent.with(|extent| extent.width);
+        data.with(|data| {
+            BandScale::new(Clone::clone(&data.domain), (0.0, width))
+                .with_padding_inner(BAR_PADDING)
+                .with_padding_outer(BAR_PADDING * 0.5)
+        })
+    });
+
+    let view_matrix = create_memo(cx, move |_| {
+        let mut view_matrix = Matrix3::<f32>::new(
+            1.0, 0.0, 0.0,
+            0.0, -1.0, 0.0,
+            0.0, 0.0, 1.0
+        );
+
+        let (left, top) = margin.with(|margin| (margin.left, margin.top));
+        let (x_start, _) = domain_scale.with(|scale| scale.range());
+        let (y_start, y_end) = value_scale.with(|scale| scale.range());
+
+        match orientation() {
+            Orientation::Vertical => {
+                view_matrix.append_translation_mut(&Vector2::new(left - x_start, top + y_end));
+            },
+            Orientation::Horizontal => {
+                // Rotating clockwise and flipping maps categories onto y and values onto x.
+                view_matrix *= Rotation2::new(-std::f32::consts::FRAC_PI_2).to_homogeneous();
+                view_matrix.append_translation_mut(&Vector2::new(left - y_start, top - x_start));
+            },
+        }
+
+        view_matrix
+    });
+
+    let value_ticks = create_memo(cx, move |_| {
+        value_scale.with(|scale| {
+            value_labels.with(|labels| {
+                labels.iter()
+                    .map(|(value, label)| Tick {
+                        position: scale.map(*value),
+                        label: Clone::clone(label),
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+    });
+
+    let domain_ticks = create_memo(cx, move |_| {
+        domain_scale.with(|scale| {
+            scale.ticks(0).into_iter()
+                .map(|index| Tick {
+                    position: scale.center(index),
+                    label: Clone::clone(&scale.domain()[index]),
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let value_colors = create_memo(cx, move |_| {
+        options.with(|options| Clone::clone(&options.bar.value_colors))
+            .map(|ramp| data.with(|data| ColorScale::sequential(data.color_range(), ramp)))
+    });
+
+    let targets = create_memo(cx, move |_| {
+        let value_colors = value_colors.get();
+        let (layout, color_pallet) = options.with(|options| (options.bar.layout, theme.with(|theme| options.categorical_pallet(theme))));
+        let band_starts = domain_scale.with(|scale| {
+            scale.ticks(0).into_iter().map(|index| scale.map(index)).collect::<Vec<_>>()
+        });
+        let bandwidth = domain_scale.with(|scale| scale.bandwidth());
+        let view_matrix = view_matrix.get();
+        data.with(|data| {
+            value_scale.with(|scale| {
+                data.segments(layout).into_iter()
+                    .filter_map(|segment| {
+                        let series = data.series.get(segment.series)?;
+                        let value = series.values.get(segment.category).cloned().filter(|value| !value.is_nan())?;
+                        let bar_width = bandwidth / segment.slots as f32;
+                        let left = band_starts.get(segment.category)? + segment.slot as f32 * bar_width;
+                        let start = view_matrix.transform_point(&Point2::new(left, scale.map(segment.start)));
+                        let end = view_matrix.transform_point(&Point2::new(left + bar_width, scale.map(segment.end)));
+                        Some(Target {
+                            anchor: view_matrix.transform_point(&Point2::new(left + bar_width * 0.5, scale.map(segment.end))),
+                            shape: HitShape::Rect {
+                                min: Point2::new(start.x.min(end.x), start.y.min(end.y)),
+                                max: Point2::new(start.x.max(end.x), start.y.max(end.y)),
+                            },
+                            datum: Datum {
+                                series: segment.series,
+                                index: segment.category,
+                                series_name: Clone::clone(&series.name),
+                                label: Clone::clone(&data.domain[segment.category]),
+                                value,
+                                color: data.bar_color(segment.series, segment.category, color_pallet, value_colors.as_ref()),
+                            },
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+    });
+
+    let interaction = Interaction::new(cx, targets, ChartEvents { on_select, on_hover, on_click }, selected);
+
+    // Domain coordinates of annotations are category indices, spaced like the centres of the bands.
+    let annotations = create_memo(cx, move |_| {
+        let extent = chart_extent.with(|extent| (extent.width, extent.height));
+        domain_scale.with(|domain_scale| {
+            value_scale.with(|value_scale| {
+                let (first, step) = (domain_scale.center(0), domain_scale.center(1) - domain_scale.center(0));
+                options.with(|options| view_matrix.with(|view_matrix| {
+                    options.annotations.place(|x| first + x as f32 * step, |y| value_scale.map(y as f32), extent, view_matrix)
+                }))
+            })
+        })
+    });
+
+    let (description, table) = describe_chart(cx, options, data, || (), || "bars");
+
+    let bar_geometry = create_memo(cx, move |_| {
+        let value_colors = value_colors.get();
+        let (layout, color_pallet) = options.with(|options| (options.bar.layout, theme.with(|theme| options.categorical_pallet(theme))));
+        let (band_starts, bandwidth) = domain_scale.with(|scale| {
+            (scale.ticks(0).into_iter().map(|index| scale.map(index)).collect::<Vec<_>>(), scale.bandwidth())
+        });
+        data.with(|data| {
+            value_scale.with(|scale| {
+                let category_keys = data.category_keys();
+                let bars = data.segments(layout).into_iter()
+                    .filter_map(|segment| {
+                        let band_start = band_starts.get(segment.category)?;
+                        let bar_width = bandwidth / segment.slots as f32;
+                        let key = (Clone::clone(category_keys.get(segment.category)?), segment.series);
+                        Some((key, BarGeometry {
+                            series: segment.series,
+                            category: segment.category,
+                            x: band_start + segment.slot as f32 * bar_width,
+                            width: bar_width,
+                            start: scale.map(segment.start),
+                            end: scale.map(segment.end),
+                            color: data.bar_color(segment.series, segment.category, color_pallet, value_colors.as_ref()),
+                            description: data.series.get(segment.series)
+                                .map(|series| {
+                                    let value = series.values.get(segment.category).cloned().unwrap_or(f32::NAN);
+                                    describe_value(&series.name, &data.domain[segment.category], value)
+                                })
+                                .unwrap_or_default(),
+                        }))
+                    })
+                    .collect::<Vec<_>>();
+                Keyed(bars)
+            })
+        })
+    });
+
+    let bar_geometry = create_tween(cx, bar_geometry, options);
+
+    // Bars are rendered once per key and updated in place, a changed value or extent only
+    // touches the attributes of the affected bars.
+    let bar_keys = create_memo(cx, move |_| {
+        bar_geometry.with(|Keyed(bars)| bars.iter().map(|(key, _)| Clone::clone(key)).collect::<Vec<_>>())
+    });
+
+    let bar_lookup = create_keyed_lookup(cx, bar_geometry);
+
+    let bar_view = move |cx, key: ((String, usize), usize)| {
+        let geometry = bar_lookup(cx, key);
+        let highlight = Highlight::new(cx, interaction, move || {
+            geometry.with(|geometry| geometry.as_ref().map(|bar| (bar.series, bar.category)))
+        });
+        view! { cx,
+            <Bar view_matrix=view_matrix geometry=geometry highlight=highlight/>
+        }
+    };
+
+    let baseline = move || {
+        let width = chart_extent.with(|extent| extent.width);
+        let baseline = match options.with(|options| options.bar.layout) {
+            BarLayout::Normalized => 0.0,
+            _ => data.with(|data| data.baseline),
+        };
+        let y = value_scale.with(|scale| scale.map(baseline));
+        view_matrix.with(|view_matrix| {
+            let start = view_matrix.transform_point(&Point2::new(0.0, y));
+            let end = view_matrix.transform_point(&Point2::new(width, y));
+            format!("M {} {} L {} {}", start.x, start.y, end.x, end.y)
+        })
+    };
+
+    let axis_positions = move || match orientation() {
+        Orientation::Vertical => (AxisPosition::Left, AxisPosition::Bottom),
+        Orientation::Horizontal => (AxisPosition::Bottom, AxisPosition::Left),
+    };
+
+    let value_axis = move || {
+        let (position, _) = axis_positions();
+        view! { cx,
+            <Axis
+                position=position
+                coordinate=Coordinate::Y
+                view_matrix=view_matrix
+                chart_extent=chart_extent
+                ticks=value_ticks
+                grid=true
+            />
+        }
+    };
+
+    let domain_axis = move || {
+        let (_, position) = axis_positions();
+        view! { cx,
+            <Axis
+                position=position
+                coordinate=Coordinate::X
+                view_matrix=view_matrix
+                chart_extent=chart_extent
+                ticks=domain_ticks
+            />
+        }
+    };
+
+    view! { cx,
+        <ChartFrame
+            options=options
+            legend=legend
+            hidden=hidden
+            size=size
+            export=export
+            interaction=interaction
+            description=description
+            table=table
+            tooltip=tooltip
+            hit_test=HitTest::nearest(HIT_DISTANCE, Metric::Euclidean)
+        >
+            {value_axis}
+            <g>
+                <For each=move || bar_keys.get() key=|key| Clone::clone(key) view=bar_view/>
+            </g>
+            <path
+                d=baseline
+                stroke=move || theme.with(|theme| Clone::clone(&theme.axis))
+                stroke-width=move || theme.with(|theme| theme.axis_stroke_width)
+                fill="none"
+            />
+            {domain_axis}
+            <AnnotationLayer annotations=annotations/>
+            <Titles options=options/>
+        </ChartFrame>
+    }
+}
+
+#[component]
+fn Bar(
+    cx: Scope,
+    view_matrix: Memo<Matrix3<f32>>,
+    geometry: Memo<Option<BarGeometry>>,
+    highlight: Highlight,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+
+    let d = move || {
+        let Some(BarGeometry { x, width, start, end, .. }) = geometry.get() else {
+            return String::new()
+        };
+        view_matrix.with(|view_matrix| {
+            let points = vec![
+                Point2::new(x, start),
+                Point2::new(x, end),
+                Point2::new(x + width, end),
+                Point2::new(x + width, start),
+            ];
+            let commands = vec!["M", "L", "L", "L"];
+            let path = points.iter()
+                .map(|point| view_matrix.transform_point(point))
+                .zip(commands.iter())
+                .map(|(point, command)| format!("{} {} {} ", command, point.x, point.y))
+                .collect::<String>();
+
+            path
+        })
+    };
+
+    let color = move || geometry.with(|geometry| geometry.as_ref().map(|bar| Clone::clone(&bar.color)));
+    let description = move || geometry.with(|geometry| geometry.as_ref().map(|bar| Clone::clone(&bar.description)));
+
+    view! { cx,
+        <path
+            d=d
+            stroke=move || if highlight.is_highlighted() { theme.with(|theme| Clone::clone(&theme.highlight)) } else { String::from("none") }
+            stroke-width="2"
+            stroke-dasharray=move || highlight.dash_array()
+            fill=color
+            role="graphics-symbol"
+            aria-label=description
+        />
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(series: Vec<Vec<f32>>, baseline: f32) -> BarChartData {
+        let domain = (0..series[0].len()).map(|category| format!("{}", category)).collect();
+        let series = series.into_iter().enumerate().map(|(index, values)| Series::new(format!("{}", index), values)).collect();
+        let mut data = BarChartData::with_series(domain, series);
+        data.set_baseline(baseline);
+        data
+    }
+
+    fn extents(data: &BarChartData, layout: BarLayout) -> Vec<(f32, f32)> {
+        data.segments(layout).iter().map(|segment| (segment.start, segment.end)).collect()
+    }
+
+    #[test]
+    fn grouped_bars_span_from_the_baseline_to_their_value() {
+        let data = data(vec![vec![5.0, 1.0], vec![3.0, f32::NAN]], 2.0);
+        let segments = data.segments(BarLayout::Grouped);
+        assert_eq!(extents(&data, BarLayout::Grouped), vec![(2.0, 5.0), (2.0, 3.0), (2.0, 1.0), (2.0, 2.0)]);
+        assert_eq!(segments.iter().map(|segment| (segment.slot, segment.slots)).collect::<Vec<_>>(), vec![(0, 2), (1, 2), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn a_single_stacked_series_matches_the_grouped_layout() {
+        let data = data(vec![vec![5.0, -1.0, 2.0]], 2.0);
+        assert_eq!(extents(&data, BarLayout::Stacked), extents(&data, BarLayout::Grouped));
+        assert_eq!(data.value_range(BarLayout::Stacked), data.value_range(BarLayout::Grouped));
+    }
+
+    #[test]
+    fn stacked_bars_add_their_distances_from_the_baseline() {
+        let data = data(vec![vec![5.0, 1.0], vec![4.0, 0.0], vec![f32::NAN, 3.0]], 2.0);
+        assert_eq!(extents(&data, BarLayout::Stacked), vec![(2.0, 5.0), (5.0, 7.0), (7.0, 7.0), (2.0, 1.0), (1.0, -1.0), (2.0, 3.0)]);
+        assert_eq!(data.value_range(BarLayout::Stacked), (-2.0, 8.0));
+    }
+
+    #[test]
+    fn normalized_bars_share_the_unit_range() {
+        let data = data(vec![vec![3.0, -1.0], vec![1.0, 3.0]], 0.0);
+        assert_eq!(extents(&data, BarLayout::Normalized), vec![(0.0, 0.75), (0.75, 1.0), (0.0, -0.25), (0.0, 0.75)]);
+        assert_eq!(data.value_range(BarLayout::Normalized), (-1.0, 1.0));
+    }
+
+    #[test]
+    fn hidden_series_leave_the_stack() {
+        let data = data(vec![vec![1.0], vec![2.0], vec![3.0]], 0.0).without_series(&BTreeSet::from([1]));
+        assert_eq!(extents(&data, BarLayout::Stacked), vec![(0.0, 1.0), (1.0, 1.0), (1.0, 4.0)]);
+    }
+
+    #[test]
+    fn a_single_unnamed_series_is_coloured_by_category() {
+        let domain = vec![String::from("a"), String::from("b"), String::from("c")];
+        let data = BarChartData::new(Clone::clone(&domain), vec![1.0, 2.0, 3.0]);
+        assert!(data.colors_by_category());
+        let colors = (0..3).map(|category| data.bar_color(0, category, ColorPallet::Default, None)).collect::<Vec<_>>();
+        assert_eq!(colors, (0..3).map(|index| ColorPallet::Default.color(index)).collect::<Vec<_>>());
+
+        let named = BarChartData::with_series(domain, vec![Series::new("a", vec![1.0, 2.0, 3.0])]);
+        assert!(!named.colors_by_category());
+        assert_eq!(named.bar_color(0, 2, ColorPallet::Default, None), ColorPallet::Default.color(0));
+    }
+
+    #[test]
+    fn hidden_categories_have_no_values() {
+        let data = BarChartData::new(vec![String::from("a"), String::from("b")], vec![1.0, 5.0]).without_categories(&BTreeSet::from([1]));
+        assert!(data.series()[0].values[1].is_nan());
+        assert_eq!(data.value_range(BarLayout::Grouped), (0.0, 1.0));
+    }
+
+    #[test]
+    fn bars_are_coloured_over_the_range_of_their_values() {
+        assert_eq!(data(vec![vec![3.0, f32::NAN], vec![-2.0, 7.0]], 0.0).color_range(), (-2.0, 7.0));
+        assert_eq!(data(vec![vec![f32::NAN]], 0.0).color_range(), (0.0, 1.0));
+        assert_eq!(BarChartData::new(Vec::new(), Vec::new()).color_range(), (0.0, 1.0));
+    }
+
+    #[test]
+    fn bars_of_repeated_categories_have_keys_of_their_own() {
+        let domain = vec![String::from("a"), String::from("b"), String::from("a")];
+        let data = BarChartData::with_series(domain, vec![Series::new("x", vec![1.0, 2.0, 3.0]), Series::new("y", vec![4.0, 5.0, 6.0])]);
+        let category_keys = data.category_keys();
+        assert_eq!(category_keys, vec![(String::from("a"), 0), (String::from("b"), 0), (String::from("a"), 1)]);
+        let keys = data.segments(BarLayout::Grouped).iter()
+            .map(|segment| (Clone::clone(&category_keys[segment.category]), segment.series))
+            .collect::<BTreeSet<_>>();
+        assert_eq!(keys.len(), 6);
+    }
+}
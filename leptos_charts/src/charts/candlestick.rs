@@ -0,0 +1,688 @@
+use std::collections::BTreeSet;
+
+use leptos::{component, create_memo, create_rw_signal, For, IntoView, Memo, ReadSignal, RwSignal, Scope, Signal, SignalGet, SignalWith, view};
+use nalgebra::{Matrix3, Point2, Vector2};
+
+use crate::{ChartOptions, Margin};
+use crate::a11y::{describe_chart, DataTable, Describe};
+use crate::annotation::{AnnotationLayer, Titles};
+use crate::axis::{self, label_margin, Axis, AxisPosition, Coordinate, Tick};
+use crate::scale::{LinearScale, Scale, TimeInterval, TimeScale};
+use crate::export::ChartExport;
+use crate::frame::ChartFrame;
+use crate::interaction::{ChartCallback, ChartEvents, Highlight, Interaction, SelectCallback, Selection};
+use crate::responsive::resolve_options;
+use crate::theme::use_theme;
+use crate::tooltip::{Datum, HitShape, HitTest, Metric, Target, TooltipRenderer};
+use crate::transition::{create_keyed_lookup, create_tween, unique_keys, Keyed, Tween};
+
+const DEFAULT_TICK_COUNT: usize = 5;
+/// Space between neighbouring candles as a fraction of the period.
+const CANDLE_PADDING: f32 = 0.3;
+/// Share of the plot height taken by the volume bars.
+const VOLUME_SHARE: f32 = 0.2;
+/// Space in pixels between the candles and the volume bars.
+const VOLUME_GAP: f32 = 8.0;
+const VOLUME_OPACITY: f32 = 0.5;
+/// Maximum horizontal distance in pixels between the pointer and a candle to count as hovering it.
+const HIT_DISTANCE: f32 = 4.0;
+const DAY: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Prices of a single trading period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candle {
+    /// Start of the period in milliseconds since the unix epoch.
+    pub time: f64,
+    pub open: f32,
+    pub high: f32,
+    pub low: f32,
+    pub close: f32,
+    pub volume: Option<f32>,
+}
+
+impl Candle {
+
+    pub fn new(time: f64, open: f32, high: f32, low: f32, close: f32) -> Self {
+        Candle { time, open, high, low, close, volume: None }
+    }
+
+    pub fn with_volume(self, volume: f32) -> Self {
+        Candle { volume: Some(volume), ..self }
+    }
+
+    /// Whether the price closed at or above its opening.
+    pub fn is_up(&self) -> bool {
+        self.close >= self.open
+    }
+}
+
+/// Trading periods in chronological order. Periods without trading, e.g. weekends, are left out
+/// and show as gaps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandlestickData {
+    candles: Vec<Candle>,
+    /// Shortest time between consecutive candles, kept with them as labels depend on it.
+    period: f64,
+}
+
+/// A candle in chart space, keyed by its time, numbered if repeated, for transitions.
+#[derive(Debug, Clone, PartialEq)]
+struct CandleGeometry {
+    index: usize,
+    x: f32,
+    width: f32,
+    open: f32,
+    high: f32,
+    low: f32,
+    close: f32,
+    /// Top of the volume bar, at the bottom of the volume bars if there is none.
+    volume: f32,
+    volume_base: f32,
+    up: bool,
+    /// Prices of the period in words for screen readers.
+    description: String,
+}
+
+impl Tween for CandleGeometry {
+
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        CandleGeometry {
+            x: self.x.tween(&to.x, t),
+            width: self.width.tween(&to.width, t),
+            open: self.open.tween(&to.open, t),
+            high: self.high.tween(&to.high, t),
+            low: self.low.tween(&to.low, t),
+            close: self.close.tween(&to.close, t),
+            volume: self.volume.tween(&to.volume, t),
+            volume_base: self.volume_base.tween(&to.volume_base, t),
+            ..Clone::clone(to)
+        }
+    }
+
+    /// Candles grow from and shrink to the middle of their body.
+    fn collapsed(&self) -> Self {
+        let middle = (self.open + self.close) * 0.5;
+        CandleGeometry {
+            open: middle,
+            high: middle,
+            low: middle,
+            close: middle,
+            volume: self.volume_base,
+            ..Clone::clone(self)
+        }
+    }
+}
+
+impl CandlestickData {
+
+    pub fn new(candles: Vec<Candle>) -> Self {
+        let mut data = CandlestickData { candles: Vec::new(), period: DAY };
+        data.set_candles(candles);
+        data
+    }
+
+    /// Replaces the candles, sorting them by time.
+    pub fn set_candles(&mut self, mut candles: Vec<Candle>) {
+        candles.sort_by(|a, b| a.time.total_cmp(&b.time));
+        self.period = candles.windows(2)
+            .map(|pair| pair[1].time - pair[0].time)
+            .filter(|period| *period > 0.0)
+            .min_by(f64::total_cmp)
+            .unwrap_or(DAY);
+        self.candles = candles;
+    }
+
+    pub fn candles(&self) -> &[Candle] {
+        &self.candles
+    }
+
+    /// Returns the length of a period, the shortest time between consecutive candles.
+    pub fn period(&self) -> f64 {
+        self.period
+    }
+
+    /// Returns the range from the lowest low to the highest high, extended to nice tick values.
+    pub fn price_range(&self) -> (f32, f32) {
+        if self.candles.is_empty() {
+            return (0.0, 1.0)
+        }
+        let (min, max) = self.candles.iter()
+            .fold((f32::MAX, f32::MIN), |(min, max), candle| (min.min(candle.low), max.max(candle.high)));
+        LinearScale::new((min, max), (0.0, 1.0))
+            .nice(DEFAULT_TICK_COUNT)
+            .domain()
+    }
+
+    /// Returns the highest volume, `None` if no candle has a volume.
+    pub fn max_volume(&self) -> Option<f32> {
+        self.candles.iter()
+            .filter_map(|candle| candle.volume)
+            .reduce(f32::max)
+    }
+
+    /// Returns the times of the candles in milliseconds numbered by their repetitions, to key
+    /// the candles.
+    pub(crate) fn candle_keys(&self) -> Vec<(i64, usize)> {
+        unique_keys(self.candles.iter().map(|candle| candle.time as i64))
+    }
+
+    /// Formats the time of a candle with the precision of a period.
+    pub(crate) fn label(&self, time: f64) -> String {
+        TimeInterval::for_span(self.period, 1).format(time)
+    }
+
+    /// Describes a candle in words, e.g. for screen readers.
+    pub(crate) fn describe(&self, candle: &Candle) -> String {
+        let mut description = format!(
+            "{}: open {}, high {}, low {}, close {}",
+            self.label(candle.time), candle.open, candle.high, candle.low, candle.close
+        );
+        if let Some(volume) = candle.volume {
+            description.push_str(&format!(", volume {}", volume));
+        }
+        description
+    }
+}
+
+impl Describe for CandlestickData {
+
+    type Context = ();
+
+    fn summary(&self, _: &()) -> String {
+        let (Some(first), Some(last)) = (self.candles.first(), self.candles.last()) else {
+            return String::from("Candlestick chart without data.")
+        };
+        let (min, max) = self.candles.iter()
+            .fold((f32::MAX, f32::MIN), |(min, max), candle| (min.min(candle.close), max.max(candle.close)));
+        format!(
+            "Candlestick chart of {} periods from {} to {}, closing prices from {} to {}.",
+            self.candles.len(), self.label(first.time), self.label(last.time), min, max
+        )
+    }
+
+    /// Returns the candles as a table with one row per period.
+    fn table(&self, caption: String, _: &()) -> DataTable {
+        let mut header = ["Time", "Open", "High", "Low", "Close"].iter().map(|cell| String::from(*cell)).collect::<Vec<_>>();
+        let volumes = self.max_volume().is_some();
+        if volumes {
+            header.push(String::from("Volume"));
+        }
+        let rows = self.candles.iter()
+            .map(|candle| {
+                let mut row = vec![self.label(candle.time)];
+                row.extend([candle.open, candle.high, candle.low, candle.close].iter().map(|value| format!("{}", value)));
+                if volumes {
+                    row.push(candle.volume.map_or_else(String::new, |volume| format!("{}", volume)));
+                }
+                row
+            })
+            .collect();
+        DataTable { caption, header, rows }
+    }
+}
+
+/// Maps the candles onto the x-axis, either by their time or, with gaps collapsed, by their order.
+#[derive(Debug, Clone, PartialEq)]
+enum TimeAxis {
+    Continuous(TimeScale),
+    Collapsed { times: Vec<f64>, scale: LinearScale },
+}
+
+impl TimeAxis {
+
+    fn new(data: &CandlestickData, collapse_gaps: bool, width: f32) -> Self {
+        let times = data.candles.iter().map(|candle| candle.time).collect::<Vec<_>>();
+        if collapse_gaps {
+            let scale = LinearScale::new((-0.5, times.len() as f32 - 0.5), (0.0, width));
+            TimeAxis::Collapsed { times, scale }
+        }
+        else {
+            let half_period = data.period() * 0.5;
+            let domain = match (times.first(), times.last()) {
+                (Some(first), Some(last)) => (first - half_period, last + half_period),
+                _ => (0.0, DAY),
+            };
+            TimeAxis::Continuous(TimeScale::new(domain, (0.0, width)))
+        }
+    }
+
+    /// Returns the centre of the candle at `index`.
+    fn map(&self, index: usize, time: f64) -> f32 {
+        match self {
+            TimeAxis::Continuous(scale) => scale.map(time),
+            TimeAxis::Collapsed { scale, .. } => scale.map(index as f32),
+        }
+    }
+
+    /// Returns the distance between the centres of neighbouring candles of a `period`.
+    fn step(&self, period: f64) -> f32 {
+        match self {
+            TimeAxis::Continuous(scale) => scale.map(scale.domain().0 + period) - scale.map(scale.domain().0),
+            TimeAxis::Collapsed { scale, .. } => scale.map(1.0) - scale.map(0.0),
+        }
+    }
+
+    fn ticks(&self, count: usize) -> Vec<Tick> {
+        match self {
+            TimeAxis::Continuous(scale) => {
+                let interval = scale.tick_interval(count);
+                scale.ticks(count).into_iter()
+                    .map(|time| Tick { position: scale.map(time), label: interval.format(time) })
+                    .collect()
+            },
+            // Ticks mark the first candle of every interval, as the time between candles is left out.
+            TimeAxis::Collapsed { times, scale } => {
+                let (Some(first), Some(last)) = (times.first(), times.last()) else {
+                    return Vec::new()
+                };
+                let interval = TimeInterval::for_span(last - first, count);
+                times.iter()
+                    .enumerate()
+                    .skip(1)
+                    .filter(|(index, time)| interval.floor(**time) != interval.floor(times[index - 1]))
+                    .map(|(index, time)| Tick { position: scale.map(index as f32), label: interval.format(*time) })
+                    .collect()
+            },
+        }
+    }
+}
+
+#[component]
+pub fn CandlestickChart(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<CandlestickData>,
+    /// Renders the content of the tooltip shown for the candle under the pointer, with the close as value.
+    #[prop(optional, into)]
+    tooltip: Option<TooltipRenderer>,
+    /// Called when a candle is selected by click or keyboard, or with `None` when the selection is cleared.
+    #[prop(optional, into)]
+    on_select: Option<SelectCallback>,
+    /// Called when the pointer or the keyboard focus moves onto a candle.
+    #[prop(optional, into)]
+    on_hover: Option<ChartCallback>,
+    /// Called on every click on a candle.
+    #[prop(optional, into)]
+    on_click: Option<ChartCallback>,
+    /// Handle to export the chart as SVG or PNG.
+    #[prop(optional)]
+    export: Option<ChartExport>,
+    /// The selected candle, set by the chart and highlighted when set elsewhere.
+    #[prop(optional)]
+    selected: Option<RwSignal<Option<Selection>>>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+    let size = create_rw_signal(cx, None);
+    let options = resolve_options(cx, options, size.read_only());
+    let font_size = move || theme.with(|theme| theme.font_size);
+    let chart_height = move || options.with(|options| options.extent.height);
+
+    // Candles are coloured by direction rather than by series, there are no series to hide.
+    let hidden = create_rw_signal(cx, BTreeSet::new());
+    let legend = Signal::derive(cx, Vec::new);
+
+    let max_volume = create_memo(cx, move |_| {
+        options.with(|options| options.candlestick.volume)
+            .then(|| data.with(|data| data.max_volume()))
+            .flatten()
+            .map(|max| LinearScale::new((0.0, max), (0.0, 1.0)).nice(2).domain().1)
+    });
+
+    let price_labels = create_memo(cx, move |_| {
+        let length = chart_height() - axis::edge_margin(font_size()) - axis::bottom_margin(font_size());
+        let length = if max_volume.get().is_some() { length * (1.0 - VOLUME_SHARE) } else { length };
+        let count = axis::tick_count(length);
+        let scale = data.with(|data| LinearScale::new(data.price_range(), (0.0, length)));
+        let step = scale.tick_step(count);
+        scale.ticks(count).into_iter()
+            .map(|value| (value, axis::format_tick(value, step)))
+            .collect::<Vec<_>>()
+    });
+
+    let margin = create_memo(cx, move |_| {
+        let volume_label = max_volume.get().map(|max| axis::format_tick(max, max));
+        let left = price_labels.with(|labels| {
+            axis::label_width(labels.iter().map(|(_, label)| label).chain(volume_label.iter()), font_size())
+        });
+        let (header, footer) = options.with(|options| {
+            theme.with(|theme| (options.annotations.header_height(theme), options.annotations.footer_height(theme)))
+        });
+        let edge = axis::edge_margin(font_size());
+        Margin::new(edge + header, edge, axis::bottom_margin(font_size()) + footer, label_margin(left))
+    });
+
+    let chart_extent = create_memo(cx, move |_| {
+        options.with(|options| margin.with(|margin| margin.shrink(&options.extent)))
+    });
+
+    // Volume bars take the bottom of the plot, the candles the space above them.
+    let volume_height = move || {
+        max_volume.get().map_or(0.0, |_| chart_extent.with(|extent| extent.height) * VOLUME_SHARE)
+    };
+
+    let price_scale = create_memo(cx, move |_| {
+        let height = chart_extent.with(|extent| extent.height);
+        let bottom = if max_volume.get().is_some() { volume_height() + VOLUME_GAP } else { 0.0 };
+        data.with(|data| LinearScale::new(data.price_range(), (bottom.min(height), height)))
+    });
+
+    let volume_scale = create_memo(cx, move |_| {
+        LinearScale::new((0.0, max_volume.get().unwrap_or(1.0)), (0.0, volume_height()))
+    });
+
+    let time_axis = create_memo(cx, move |_| {
+        let width = chart_extent.with(|extent| extent.width);
+        let collapse_gaps = options.with(|options| options.candlestick.collapse_gaps);
+        data.with(|data| TimeAxis::new(data, collapse_gaps, width))
+    });
+
+    let view_matrix = create_memo(cx, move |_| {
+        let mut view_matrix = Matrix3::<f32>::new(
+            1.0, 0.0, 0.0,
+            0.0, -1.0, 0.0,
+            0.0, 0.0, 1.0
+        );
+        let (left, top) = margin.with(|margin| (margin.left, margin.top));
+        let height = chart_extent.with(|extent| extent.height);
+        view_matrix.append_translation_mut(&Vector2::new(left, top + height));
+        view_matrix
+    });
+
+    let value_ticks = create_memo(cx, move |_| {
+        let prices = price_scale.with(|scale| {
+            price_labels.with(|labels| {
+                labels.iter()
+                    .map(|(value, label)| Tick { position: scale.map(*value), label: Clone::clone(label) })
+                    .collect::<Vec<_>>()
+            })
+        });
+        let volume = max_volume.get().map(|max| Tick {
+            position: volume_scale.with(|scale| scale.map(max)),
+            label: axis::format_tick(max, max),
+        });
+        prices.into_iter().chain(volume).collect::<Vec<_>>()
+    });
+
+    let time_ticks = create_memo(cx, move |_| {
+        let count = axis::tick_count(chart_extent.with(|extent| extent.width));
+        time_axis.with(|time_axis| time_axis.ticks(count))
+    });
+
+    let candle_geometry = create_memo(cx, move |_| {
+        let volumes = max_volume.get().is_some();
+        time_axis.with(|time_axis| {
+            price_scale.with(|price_scale| {
+                volume_scale.with(|volume_scale| {
+                    data.with(|data| {
+                        let width = time_axis.step(data.period()) * (1.0 - CANDLE_PADDING);
+                        let candle_keys = data.candle_keys();
+                        let candles = data.candles.iter()
+                            .enumerate()
+                            .map(|(index, candle)| {
+                                let volume = candle.volume.filter(|_| volumes).unwrap_or(0.0);
+                                (candle_keys[index], CandleGeometry {
+                                    index,
+                                    x: time_axis.map(index, candle.time) - width * 0.5,
+                                    width,
+                                    open: price_scale.map(candle.open),
+                                    high: price_scale.map(candle.high),
+                                    low: price_scale.map(candle.low),
+                                    close: price_scale.map(candle.close),
+                                    volume: volume_scale.map(volume),
+                                    volume_base: volume_scale.map(0.0),
+                                    up: candle.is_up(),
+                                    description: data.describe(candle),
+                                })
+                            })
+                            .collect::<Vec<_>>();
+                        Keyed(candles)
+                    })
+                })
+            })
+        })
+    });
+
+    let candle_color = move |up: bool| theme.with(|theme| Clone::clone(if up { &theme.up } else { &theme.down }));
+
+    let targets = create_memo(cx, move |_| {
+        view_matrix.with(|view_matrix| {
+            data.with(|data| {
+                candle_geometry.with(|Keyed(candles)| {
+                    candles.iter()
+                        .map(|(_, geometry)| {
+                            let candle = &data.candles[geometry.index];
+                            let start = view_matrix.transform_point(&Point2::new(geometry.x, geometry.low.min(geometry.volume_base)));
+                            let end = view_matrix.transform_point(&Point2::new(geometry.x + geometry.width, geometry.high));
+                            Target {
+                                anchor: view_matrix.transform_point(&Point2::new(geometry.x + geometry.width * 0.5, geometry.high)),
+                                shape: HitShape::Rect {
+                                    min: Point2::new(start.x.min(end.x), start.y.min(end.y)),
+                                    max: Point2::new(start.x.max(end.x), start.y.max(end.y)),
+                                },
+                                datum: Datum {
+                                    series: 0,
+                                    index: geometry.index,
+                                    series_name: String::new(),
+                                    label: data.label(candle.time),
+                                    value: candle.close,
+                                    color: candle_color(geometry.up),
+                                },
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+        })
+    });
+
+    let interaction = Interaction::new(cx, targets, ChartEvents { on_select, on_hover, on_click }, selected);
+
+    // Domain coordinates of annotations are timestamps, or candle indices with gaps collapsed.
+    let annotations = create_memo(cx, move |_| {
+        let extent = chart_extent.with(|extent| (extent.width, extent.height));
+        time_axis.with(|time_axis| {
+            price_scale.with(|price_scale| {
+                let map_domain = |x: f64| match time_axis {
+                    TimeAxis::Continuous(scale) => scale.map(x),
+                    TimeAxis::Collapsed { scale, .. } => scale.map(x as f32),
+                };
+                options.with(|options| view_matrix.with(|view_matrix| {
+                    options.annotations.place(map_domain, |y| price_scale.map(y as f32), extent, view_matrix)
+                }))
+            })
+        })
+    });
+
+    let (description, table) = describe_chart(cx, options, data, || (), || "candles");
+
+    let candle_geometry = create_tween(cx, candle_geometry, options);
+
+    let candle_keys = create_memo(cx, move |_| {
+        candle_geometry.with(|Keyed(candles)| candles.iter().map(|(key, _)| *key).collect::<Vec<_>>())
+    });
+
+    let candle_lookup = create_keyed_lookup(cx, candle_geometry);
+
+    let candle_view = move |cx, key: (i64, usize)| {
+        let geometry = candle_lookup(cx, key);
+        let highlight = Highlight::new(cx, interaction, move || {
+            geometry.with(|geometry| geometry.as_ref().map(|candle| (0, candle.index)))
+        });
+        let color = Signal::derive(cx, move || geometry.with(|geometry| candle_color(geometry.as_ref().map_or(true, |candle| candle.up))));
+        let volumes = Signal::derive(cx, move || max_volume.get().is_some());
+        view! { cx,
+            <CandleMark view_matrix=view_matrix geometry=geometry color=color volume=volumes highlight=highlight/>
+        }
+    };
+
+    view! { cx,
+        <ChartFrame
+            options=options
+            legend=legend
+            hidden=hidden
+            size=size
+            export=export
+            interaction=interaction
+            description=description
+            table=table
+            tooltip=tooltip
+            hit_test=HitTest::nearest(HIT_DISTANCE, Metric::Horizontal)
+        >
+            <Axis
+                position=AxisPosition::Left
+                coordinate=Coordinate::Y
+                view_matrix=view_matrix
+                chart_extent=chart_extent
+                ticks=value_ticks
+                grid=true
+            />
+            <g>
+                <For each=move || candle_keys.get() key=|key| *key view=candle_view/>
+            </g>
+            <Axis
+                position=AxisPosition::Bottom
+                coordinate=Coordinate::X
+                view_matrix=view_matrix
+                chart_extent=chart_extent
+                ticks=time_ticks
+            />
+            <AnnotationLayer annotations=annotations/>
+            <Titles options=options/>
+        </ChartFrame>
+    }
+}
+
+/// The wick, body and volume bar of a single period.
+#[component]
+fn CandleMark(
+    cx: Scope,
+    view_matrix: Memo<Matrix3<f32>>,
+    geometry: Memo<Option<CandleGeometry>>,
+    color: Signal<String>,
+    /// Whether the volume bar is drawn.
+    volume: Signal<bool>,
+    highlight: Highlight,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+
+    let rect = move |geometry: &CandleGeometry, start: f32, end: f32| {
+        view_matrix.with(|view_matrix| {
+            let min = view_matrix.transform_point(&Point2::new(geometry.x, start));
+            let max = view_matrix.transform_point(&Point2::new(geometry.x + geometry.width, end));
+            format!("M {} {} L {} {} L {} {} L {} {} Z", min.x, min.y, min.x, max.y, max.x, max.y, max.x, min.y)
+        })
+    };
+
+    let wick = move || {
+        geometry.with(|geometry| {
+            geometry.as_ref().map_or_else(String::new, |geometry| {
+                view_matrix.with(|view_matrix| {
+                    let center = geometry.x + geometry.width * 0.5;
+                    let low = view_matrix.transform_point(&Point2::new(center, geometry.low));
+                    let high = view_matrix.transform_point(&Point2::new(center, geometry.high));
+                    format!("M {} {} L {} {}", low.x, low.y, high.x, high.y)
+                })
+            })
+        })
+    };
+
+    let body = move || {
+        geometry.with(|geometry| geometry.as_ref().map_or_else(String::new, |geometry| rect(geometry, geometry.open, geometry.close)))
+    };
+
+    let volume_bar = move || {
+        geometry.with(|geometry| {
+            geometry.as_ref()
+                .filter(|_| volume.get())
+                .map_or_else(String::new, |geometry| rect(geometry, geometry.volume_base, geometry.volume))
+        })
+    };
+
+    let description = move || geometry.with(|geometry| geometry.as_ref().map(|candle| Clone::clone(&candle.description)));
+
+    // Bodies of unchanged prices have no height, their outline still shows them as a line.
+    view! { cx,
+        <g role="graphics-symbol" aria-label=description>
+            <path d=volume_bar fill=color fill-opacity=VOLUME_OPACITY/>
+            <path d=wick stroke=color stroke-width="1"/>
+            <path
+                d=body
+                fill=color
+                stroke=move || if highlight.is_highlighted() { theme.with(|theme| Clone::clone(&theme.highlight)) } else { color.get() }
+                stroke-width=move || if highlight.is_highlighted() { 2.0 } else { 1.0 }
+                stroke-dasharray=move || highlight.dash_array()
+            />
+        </g>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: f64 = DAY / 24.0;
+    /// Monday, 14 August 2023.
+    const MONDAY: f64 = 19583.0 * DAY;
+
+    fn candle(time: f64) -> Candle {
+        Candle::new(time, 1.0, 2.0, 0.5, 1.5)
+    }
+
+    fn data(days: &[f64]) -> CandlestickData {
+        CandlestickData::new(days.iter().map(|day| candle(day * DAY)).collect())
+    }
+
+    fn assert_close(actual: &[f32], expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len(), "{:?} != {:?}", actual, expected);
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!((actual - expected).abs() < 1e-3, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn the_period_is_the_shortest_positive_gap() {
+        let data = data(&[3.0, 0.0, 1.0, 1.0, 6.0]);
+        assert_eq!(data.candles().iter().map(|candle| candle.time / DAY).collect::<Vec<_>>(), vec![0.0, 1.0, 1.0, 3.0, 6.0]);
+        assert_eq!(data.period(), DAY);
+        assert_eq!(CandlestickData::new((0..4).map(|hour| candle(hour as f64 * HOUR)).collect()).period(), HOUR);
+        assert_eq!(self::data(&[2.0]).period(), DAY);
+        assert_eq!(self::data(&[2.0, 2.0]).period(), DAY);
+    }
+
+    #[test]
+    fn continuous_axes_keep_the_gaps() {
+        let data = data(&[0.0, 1.0, 4.0, 5.0]);
+        let axis = TimeAxis::new(&data, false, 120.0);
+        let centres = data.candles().iter().enumerate().map(|(index, candle)| axis.map(index, candle.time)).collect::<Vec<_>>();
+        assert_close(&centres, &[10.0, 30.0, 90.0, 110.0]);
+        assert_close(&[axis.step(data.period())], &[20.0]);
+    }
+
+    #[test]
+    fn collapsed_axes_place_candles_by_their_order() {
+        let data = data(&[0.0, 1.0, 4.0, 5.0]);
+        let axis = TimeAxis::new(&data, true, 100.0);
+        let centres = data.candles().iter().enumerate().map(|(index, candle)| axis.map(index, candle.time)).collect::<Vec<_>>();
+        assert_close(&centres, &[12.5, 37.5, 62.5, 87.5]);
+        assert_close(&[axis.step(data.period())], &[25.0]);
+    }
+
+    #[test]
+    fn collapsed_ticks_mark_the_first_candle_of_every_interval() {
+        let days = (0..3).flat_map(|week| (0..5).map(move |day| (week * 7 + day) as f64)).collect::<Vec<_>>();
+        let data = CandlestickData::new(days.iter().map(|day| candle(MONDAY + day * DAY)).collect());
+        let ticks = TimeAxis::new(&data, true, 150.0).ticks(3);
+        assert_close(&ticks.iter().map(|tick| tick.position).collect::<Vec<_>>(), &[55.0, 105.0]);
+        let labels = [7.0, 14.0].iter().map(|day| TimeInterval::Week.format(MONDAY + day * DAY)).collect::<Vec<_>>();
+        assert_eq!(ticks.iter().map(|tick| Clone::clone(&tick.label)).collect::<Vec<_>>(), labels);
+        assert!(TimeAxis::new(&CandlestickData::new(Vec::new()), true, 150.0).ticks(3).is_empty());
+    }
+
+    #[test]
+    fn candles_at_the_same_time_have_keys_of_their_own() {
+        let data = CandlestickData::new(vec![candle(2.0), candle(1.0), candle(1.0)]);
+        assert_eq!(data.candle_keys(), vec![(1, 0), (1, 1), (2, 0)]);
+    }
+}
@@ -0,0 +1,656 @@
+use std::collections::BTreeSet;
+
+use leptos::{component, create_memo, create_rw_signal, For, IntoView, Memo, ReadSignal, RwSignal, Scope, Signal, SignalGet, SignalWith, view};
+use nalgebra::{Matrix3, Point2, Vector2};
+
+use crate::{ChartOptions, Margin};
+use crate::a11y::{describe_chart, DataTable, Describe};
+use crate::annotation::{AnnotationLayer, Titles};
+use crate::axis::{self, label_margin, Axis, AxisPosition, Coordinate, Tick};
+use crate::scale::{BandScale, LinearScale, Scale};
+use crate::stats::{self, BoxStats, Whiskers};
+use crate::export::ChartExport;
+use crate::frame::ChartFrame;
+use crate::interaction::{ChartCallback, ChartEvents, Highlight, Interaction, SelectCallback, Selection};
+use crate::responsive::resolve_options;
+use crate::theme::use_theme;
+use crate::tooltip::{Datum, HitShape, HitTest, Metric, Target, TooltipRenderer};
+use crate::transition::{create_keyed_lookup, create_tween, unique_keys, Keyed, Tween};
+
+const DEFAULT_TICK_COUNT: usize = 5;
+const BAND_PADDING: f32 = 0.3;
+/// Maximum distance in pixels between the pointer and a distribution to count as hovering it.
+const HIT_DISTANCE: f32 = 4.0;
+/// Width of the caps of the whiskers relative to the width of the box.
+const CAP_WIDTH: f32 = 0.5;
+/// Number of heights the density of a violin is estimated at.
+const VIOLIN_RESOLUTION: usize = 48;
+/// Width of the box drawn inside of violins relative to the width of the band.
+const INNER_BOX_WIDTH: f32 = 0.08;
+
+/// Raw samples per category, each category is drawn as the distribution of its samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistributionData {
+    domain: Vec<String>,
+    /// One vector of samples per category of the domain.
+    samples: Vec<Vec<f32>>,
+}
+
+/// A distribution in chart space, keyed by its domain label, numbered if repeated, for transitions.
+#[derive(Debug, Clone, PartialEq)]
+struct DistributionGeometry {
+    category: usize,
+    x: f32,
+    width: f32,
+    lower_whisker: f32,
+    first_quartile: f32,
+    median: f32,
+    third_quartile: f32,
+    upper_whisker: f32,
+    outliers: Vec<f32>,
+    /// Heights and half widths of the outline of a violin, empty for box plots.
+    violin: Vec<(f32, f32)>,
+    color: String,
+    /// The distribution in words for screen readers.
+    description: String,
+}
+
+impl Tween for DistributionGeometry {
+
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        DistributionGeometry {
+            x: self.x.tween(&to.x, t),
+            width: self.width.tween(&to.width, t),
+            lower_whisker: self.lower_whisker.tween(&to.lower_whisker, t),
+            first_quartile: self.first_quartile.tween(&to.first_quartile, t),
+            median: self.median.tween(&to.median, t),
+            third_quartile: self.third_quartile.tween(&to.third_quartile, t),
+            upper_whisker: self.upper_whisker.tween(&to.upper_whisker, t),
+            outliers: self.outliers.tween(&to.outliers, t),
+            violin: self.violin.tween(&to.violin, t),
+            ..Clone::clone(to)
+        }
+    }
+
+    /// Distributions grow from and shrink to their median.
+    fn collapsed(&self) -> Self {
+        let median = self.median;
+        DistributionGeometry {
+            lower_whisker: median,
+            first_quartile: median,
+            third_quartile: median,
+            upper_whisker: median,
+            outliers: self.outliers.iter().map(|_| median).collect(),
+            violin: self.violin.iter().map(|_| (median, 0.0)).collect(),
+            ..Clone::clone(self)
+        }
+    }
+}
+
+impl DistributionData {
+
+    pub fn new(domain: Vec<String>, samples: Vec<Vec<f32>>) -> Self {
+        DistributionData { domain, samples }
+    }
+
+    pub fn set_domain(&mut self, domain: Vec<String>) {
+        self.domain = domain
+    }
+
+    pub fn set_samples(&mut self, samples: Vec<Vec<f32>>) {
+        self.samples = samples
+    }
+
+    pub fn domain(&self) -> &[String] {
+        &self.domain
+    }
+
+    pub fn samples(&self) -> &[Vec<f32>] {
+        &self.samples
+    }
+
+    /// Returns the finite samples of every category in ascending order.
+    pub(crate) fn sorted(&self) -> Vec<Vec<f32>> {
+        (0..self.domain.len())
+            .map(|category| self.samples.get(category).map_or_else(Vec::new, |samples| stats::sorted(samples)))
+            .collect()
+    }
+
+    /// Returns the labels of the categories numbered by their repetitions, to key their
+    /// distributions.
+    pub(crate) fn category_keys(&self) -> Vec<(String, usize)> {
+        unique_keys(self.domain.iter().cloned())
+    }
+
+    /// Returns the range covered by the samples, extended to nice tick values.
+    pub fn value_range(&self) -> (f32, f32) {
+        let (min, max) = self.samples.iter()
+            .flatten()
+            .filter(|sample| sample.is_finite())
+            .fold((f32::MAX, f32::MIN), |(min, max), sample| (min.min(*sample), max.max(*sample)));
+        LinearScale::new((min, max), (0.0, 1.0))
+            .nice(DEFAULT_TICK_COUNT)
+            .domain()
+    }
+}
+
+impl Describe for DistributionData {
+
+    type Context = (bool, Whiskers);
+
+    fn summary(&self, (violins, _): &(bool, Whiskers)) -> String {
+        let count = self.samples.iter().flatten().filter(|sample| sample.is_finite()).count();
+        format!(
+            "{} of {} categories with {} samples.",
+            if *violins { "Violin plot" } else { "Box plot" }, self.domain.len(), count
+        )
+    }
+
+    /// Returns the statistics of the distributions as a table with one row per category.
+    fn table(&self, caption: String, (_, whiskers): &(bool, Whiskers)) -> DataTable {
+        let header = ["", "Samples", "Lower whisker", "First quartile", "Median", "Third quartile", "Upper whisker", "Outliers"]
+            .iter()
+            .map(|cell| String::from(*cell))
+            .collect();
+        let rows = self.domain.iter()
+            .zip(self.sorted())
+            .map(|(label, sorted)| {
+                let mut row = vec![Clone::clone(label), format!("{}", sorted.len())];
+                if let Some(stats) = BoxStats::new(&sorted, *whiskers) {
+                    row.extend([stats.lower_whisker, stats.first_quartile, stats.median, stats.third_quartile, stats.upper_whisker]
+                        .iter()
+                        .map(|value| format!("{}", value)));
+                    row.push(stats.outliers.iter().map(|value| format!("{}", value)).collect::<Vec<_>>().join(", "));
+                }
+                row
+            })
+            .collect();
+        DataTable { caption, header, rows }
+    }
+}
+
+/// Describes a distribution in words, e.g. for screen readers.
+fn describe_distribution(label: &str, stats: &BoxStats) -> String {
+    let mut description = format!(
+        "{}: median {}, quartiles {} to {}, whiskers {} to {}",
+        label, stats.median, stats.first_quartile, stats.third_quartile, stats.lower_whisker, stats.upper_whisker
+    );
+    if !stats.outliers.is_empty() {
+        description.push_str(&format!(", {} outliers", stats.outliers.len()));
+    }
+    description
+}
+
+/// Box plots of the samples of every category, showing quartiles, whiskers and outliers.
+#[component]
+pub fn BoxPlot(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<DistributionData>,
+    /// Renders the content of the tooltip shown for the box under the pointer, with the median as value.
+    #[prop(optional, into)]
+    tooltip: Option<TooltipRenderer>,
+    /// Called when a box is selected by click or keyboard, or with `None` when the selection is cleared.
+    #[prop(optional, into)]
+    on_select: Option<SelectCallback>,
+    /// Called when the pointer or the keyboard focus moves onto a box.
+    #[prop(optional, into)]
+    on_hover: Option<ChartCallback>,
+    /// Called on every click on a box.
+    #[prop(optional, into)]
+    on_click: Option<ChartCallback>,
+    /// Handle to export the chart as SVG or PNG.
+    #[prop(optional)]
+    export: Option<ChartExport>,
+    /// The selected box, set by the chart and highlighted when set elsewhere.
+    #[prop(optional)]
+    selected: Option<RwSignal<Option<Selection>>>,
+) -> impl IntoView {
+    let events = ChartEvents { on_select, on_hover, on_click };
+    view! { cx,
+        <Distributions options=options data=data violins=false tooltip=tooltip events=events selected=selected export=export />
+    }
+}
+
+/// Kernel density estimates of the samples of every category, mirrored around the centre of their
+/// band, with the quartiles and the median inside.
+#[component]
+pub fn ViolinPlot(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<DistributionData>,
+    /// Renders the content of the tooltip shown for the violin under the pointer, with the median as value.
+    #[prop(optional, into)]
+    tooltip: Option<TooltipRenderer>,
+    /// Called when a violin is selected by click or keyboard, or with `None` when the selection is cleared.
+    #[prop(optional, into)]
+    on_select: Option<SelectCallback>,
+    /// Called when the pointer or the keyboard focus moves onto a violin.
+    #[prop(optional, into)]
+    on_hover: Option<ChartCallback>,
+    /// Called on every click on a violin.
+    #[prop(optional, into)]
+    on_click: Option<ChartCallback>,
+    /// Handle to export the chart as SVG or PNG.
+    #[prop(optional)]
+    export: Option<ChartExport>,
+    /// The selected violin, set by the chart and highlighted when set elsewhere.
+    #[prop(optional)]
+    selected: Option<RwSignal<Option<Selection>>>,
+) -> impl IntoView {
+    let events = ChartEvents { on_select, on_hover, on_click };
+    view! { cx,
+        <Distributions options=options data=data violins=true tooltip=tooltip events=events selected=selected export=export />
+    }
+}
+
+#[component]
+fn Distributions(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<DistributionData>,
+    violins: bool,
+    tooltip: Option<TooltipRenderer>,
+    events: ChartEvents,
+    selected: Option<RwSignal<Option<Selection>>>,
+    export: Option<ChartExport>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+    let size = create_rw_signal(cx, None);
+    let options = resolve_options(cx, options, size.read_only());
+    let font_size = move || theme.with(|theme| theme.font_size);
+    let chart_height = move || options.with(|options| options.extent.height);
+
+    // Categories are labelled along the axis, there are no series to hide.
+    let hidden = create_rw_signal(cx, BTreeSet::new());
+    let legend = Signal::derive(cx, Vec::new);
+
+    let sorted = create_memo(cx, move |_| data.with(|data| data.sorted()));
+
+    let stats = create_memo(cx, move |_| {
+        let whiskers = options.with(|options| options.distribution.whiskers);
+        sorted.with(|sorted| sorted.iter().map(|sorted| BoxStats::new(sorted, whiskers)).collect::<Vec<_>>())
+    });
+
+    let value_labels = create_memo(cx, move |_| {
+        let length = chart_height() - axis::edge_margin(font_size()) - axis::bottom_margin(font_size());
+        let count = axis::tick_count(length);
+        let scale = data.with(|data| LinearScale::new(data.value_range(), (0.0, length)));
+        let step = scale.tick_step(count);
+        scale.ticks(count).into_iter()
+            .map(|value| (value, axis::format_tick(value, step)))
+            .collect::<Vec<_>>()
+    });
+
+    let margin = create_memo(cx, move |_| {
+        let left = value_labels.with(|labels| axis::label_width(labels.iter().map(|(_, label)| label), font_size()));
+        let (header, footer) = options.with(|options| {
+            theme.with(|theme| (options.annotations.header_height(theme), options.annotations.footer_height(theme)))
+        });
+        let edge = axis::edge_margin(font_size());
+        Margin::new(edge + header, edge, axis::bottom_margin(font_size()) + footer, label_margin(left))
+    });
+
+    let chart_extent = create_memo(cx, move |_| {
+        options.with(|options| margin.with(|margin| margin.shrink(&options.extent)))
+    });
+
+    let value_scale = create_memo(cx, move |_| {
+        let height = chart_extent.with(|extent| extent.height);
+        data.with(|data| LinearScale::new(data.value_range(), (0.0, height)))
+    });
+
+    let domain_scale = create_memo(cx, move |_| {
+        let width = chart_extent.with(|extent| extent.width);
+        data.with(|data| {
+            BandScale::new(Clone::clone(&data.domain), (0.0, width))
+                .with_padding_inner(BAND_PADDING)
+                .with_padding_outer(BAND_PADDING * 0.5)
+        })
+    });
+
+    let view_matrix = create_memo(cx, move |_| {
+        let mut view_matrix = Matrix3::<f32>::new(
+            1.0, 0.0, 0.0,
+            0.0, -1.0, 0.0,
+            0.0, 0.0, 1.0
+        );
+        let (left, top) = margin.with(|margin| (margin.left, margin.top));
+        let height = chart_extent.with(|extent| extent.height);
+        view_matrix.append_translation_mut(&Vector2::new(left, top + height));
+        view_matrix
+    });
+
+    let value_ticks = create_memo(cx, move |_| {
+        value_scale.with(|scale| {
+            value_labels.with(|labels| {
+                labels.iter()
+                    .map(|(value, label)| Tick { position: scale.map(*value), label: Clone::clone(label) })
+                    .collect::<Vec<_>>()
+            })
+        })
+    });
+
+    let domain_ticks = create_memo(cx, move |_| {
+        domain_scale.with(|scale| {
+            scale.ticks(0).into_iter()
+                .map(|index| Tick { position: scale.center(index), label: Clone::clone(&scale.domain()[index]) })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    // Violins share the scale of their widths, so equal widths stand for equal densities.
+    let densities = create_memo(cx, move |_| {
+        if !violins {
+            return Vec::new()
+        }
+        let bandwidth = options.with(|options| options.distribution.bandwidth);
+        sorted.with(|sorted| {
+            sorted.iter()
+                .map(|sorted| {
+                    let (Some(&min), Some(&max)) = (sorted.first(), sorted.last()) else {
+                        return Vec::new()
+                    };
+                    let bandwidth = bandwidth.unwrap_or_else(|| stats::silverman_bandwidth(sorted));
+                    (0..VIOLIN_RESOLUTION)
+                        .map(|step| {
+                            let value = min + (max - min) * step as f32 / (VIOLIN_RESOLUTION - 1) as f32;
+                            (value, stats::kernel_density(sorted, value, bandwidth))
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let distribution_geometry = create_memo(cx, move |_| {
+        let color_pallet = options.with(|options| theme.with(|theme| options.categorical_pallet(theme)));
+        let max_density = densities.with(|densities| {
+            densities.iter().flatten().map(|(_, density)| *density).fold(0.0, f32::max)
+        });
+        domain_scale.with(|domain_scale| {
+            value_scale.with(|value_scale| {
+                data.with(|data| {
+                    stats.with(|stats| {
+                        densities.with(|densities| {
+                            let category_keys = data.category_keys();
+                            let distributions = stats.iter()
+                                .enumerate()
+                                .filter_map(|(category, stats)| {
+                                    let stats = stats.as_ref()?;
+                                    let width = domain_scale.bandwidth();
+                                    let violin = densities.get(category)
+                                        .map(|density| {
+                                            density.iter()
+                                                .map(|(value, density)| {
+                                                    let half_width = if max_density > 0.0 { density / max_density * width * 0.5 } else { 0.0 };
+                                                    (value_scale.map(*value), half_width)
+                                                })
+                                                .collect()
+                                        })
+                                        .unwrap_or_default();
+                                    let label = &data.domain[category];
+                                    Some((Clone::clone(category_keys.get(category)?), DistributionGeometry {
+                                        category,
+                                        x: domain_scale.map(category),
+                                        width,
+                                        lower_whisker: value_scale.map(stats.lower_whisker),
+                                        first_quartile: value_scale.map(stats.first_quartile),
+                                        median: value_scale.map(stats.median),
+                                        third_quartile: value_scale.map(stats.third_quartile),
+                                        upper_whisker: value_scale.map(stats.upper_whisker),
+                                        outliers: stats.outliers.iter().map(|value| value_scale.map(*value)).collect(),
+                                        violin,
+                                        color: color_pallet.color_of(category, data.domain.len()),
+                                        description: describe_distribution(label, stats),
+                                    }))
+                                })
+                                .collect::<Vec<_>>();
+                            Keyed(distributions)
+                        })
+                    })
+                })
+            })
+        })
+    });
+
+    let targets = create_memo(cx, move |_| {
+        view_matrix.with(|view_matrix| {
+            data.with(|data| {
+                stats.with(|stats| {
+                    distribution_geometry.with(|Keyed(distributions)| {
+                        distributions.iter()
+                            .filter_map(|(_, geometry)| {
+                                let stats = stats.get(geometry.category)?.as_ref()?;
+                                // Outliers count as part of the distribution they lie beyond.
+                                let (low, high) = geometry.outliers.iter()
+                                    .fold((geometry.lower_whisker, geometry.upper_whisker), |(low, high), y| (low.min(*y), high.max(*y)));
+                                let start = view_matrix.transform_point(&Point2::new(geometry.x, low));
+                                let end = view_matrix.transform_point(&Point2::new(geometry.x + geometry.width, high));
+                                Some(Target {
+                                    anchor: view_matrix.transform_point(&Point2::new(geometry.x + geometry.width * 0.5, geometry.third_quartile)),
+                                    shape: HitShape::Rect {
+                                        min: Point2::new(start.x.min(end.x), start.y.min(end.y)),
+                                        max: Point2::new(start.x.max(end.x), start.y.max(end.y)),
+                                    },
+                                    datum: Datum {
+                                        series: 0,
+                                        index: geometry.category,
+                                        series_name: String::new(),
+                                        label: Clone::clone(&data.domain[geometry.category]),
+                                        value: stats.median,
+                                        color: Clone::clone(&geometry.color),
+                                    },
+                                })
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+            })
+        })
+    });
+
+    let interaction = Interaction::new(cx, targets, events, selected);
+
+    // Domain coordinates of annotations are category indices, spaced like the centres of the bands.
+    let annotations = create_memo(cx, move |_| {
+        let extent = chart_extent.with(|extent| (extent.width, extent.height));
+        domain_scale.with(|domain_scale| {
+            value_scale.with(|value_scale| {
+                let (first, step) = (domain_scale.center(0), domain_scale.center(1) - domain_scale.center(0));
+                options.with(|options| view_matrix.with(|view_matrix| {
+                    options.annotations.place(|x| first + x as f32 * step, |y| value_scale.map(y as f32), extent, view_matrix)
+                }))
+            })
+        })
+    });
+
+    let (description, table) = describe_chart(
+        cx,
+        options,
+        data,
+        move || (violins, options.with(|options| options.distribution.whiskers)),
+        move || if violins { "violins" } else { "boxes" },
+    );
+
+    let distribution_geometry = create_tween(cx, distribution_geometry, options);
+
+    let distribution_keys = create_memo(cx, move |_| {
+        distribution_geometry.with(|Keyed(distributions)| distributions.iter().map(|(key, _)| Clone::clone(key)).collect::<Vec<_>>())
+    });
+
+    let distribution_lookup = create_keyed_lookup(cx, distribution_geometry);
+
+    let distribution_view = move |cx, key: (String, usize)| {
+        let geometry = distribution_lookup(cx, key);
+        let highlight = Highlight::new(cx, interaction, move || {
+            geometry.with(|geometry| geometry.as_ref().map(|distribution| (0, distribution.category)))
+        });
+        let radius = Signal::derive(cx, move || options.with(|options| options.distribution.outlier_radius));
+        view! { cx,
+            <Distribution view_matrix=view_matrix geometry=geometry violin=violins outlier_radius=radius highlight=highlight/>
+        }
+    };
+
+    view! { cx,
+        <ChartFrame
+            options=options
+            legend=legend
+            hidden=hidden
+            size=size
+            export=export
+            interaction=interaction
+            description=description
+            table=table
+            tooltip=tooltip
+            hit_test=HitTest::nearest(HIT_DISTANCE, Metric::Euclidean)
+        >
+            <Axis
+                position=AxisPosition::Left
+                coordinate=Coordinate::Y
+                view_matrix=view_matrix
+                chart_extent=chart_extent
+                ticks=value_ticks
+                grid=true
+            />
+            <g>
+                <For each=move || distribution_keys.get() key=|key| Clone::clone(key) view=distribution_view/>
+            </g>
+            <Axis
+                position=AxisPosition::Bottom
+                coordinate=Coordinate::X
+                view_matrix=view_matrix
+                chart_extent=chart_extent
+                ticks=domain_ticks
+            />
+            <AnnotationLayer annotations=annotations/>
+            <Titles options=options/>
+        </ChartFrame>
+    }
+}
+
+/// A single box or violin with its outliers.
+#[component]
+fn Distribution(
+    cx: Scope,
+    view_matrix: Memo<Matrix3<f32>>,
+    geometry: Memo<Option<DistributionGeometry>>,
+    violin: bool,
+    outlier_radius: Signal<f32>,
+    highlight: Highlight,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+
+    // Maps a point in chart space given relative to the centre of the band onto the screen.
+    let point = move |geometry: &DistributionGeometry, offset: f32, y: f32| {
+        view_matrix.with(|view_matrix| view_matrix.transform_point(&Point2::new(geometry.x + geometry.width * 0.5 + offset, y)))
+    };
+    let line = move |geometry: &DistributionGeometry, (from_offset, from_y): (f32, f32), (to_offset, to_y): (f32, f32)| {
+        let (from, to) = (point(geometry, from_offset, from_y), point(geometry, to_offset, to_y));
+        format!("M {} {} L {} {} ", from.x, from.y, to.x, to.y)
+    };
+    let rect = move |geometry: &DistributionGeometry, half_width: f32| {
+        let (start, end) = (point(geometry, -half_width, geometry.first_quartile), point(geometry, half_width, geometry.third_quartile));
+        format!("M {} {} L {} {} L {} {} L {} {} Z", start.x, start.y, start.x, end.y, end.x, end.y, end.x, start.y)
+    };
+
+    let body = move || {
+        geometry.with(|geometry| {
+            geometry.as_ref().map_or_else(String::new, |geometry| {
+                if violin {
+                    let right = geometry.violin.iter().map(|(y, half_width)| point(geometry, *half_width, *y));
+                    let left = geometry.violin.iter().rev().map(|(y, half_width)| point(geometry, -half_width, *y));
+                    let mut path = right.chain(left)
+                        .enumerate()
+                        .map(|(index, point)| format!("{} {} {} ", if index == 0 { "M" } else { "L" }, point.x, point.y))
+                        .collect::<String>();
+                    path.push('Z');
+                    path
+                }
+                else {
+                    rect(geometry, geometry.width * 0.5)
+                }
+            })
+        })
+    };
+
+    // Violins show the whiskers and the box as a narrow bar inside of them.
+    let whiskers = move || {
+        geometry.with(|geometry| {
+            geometry.as_ref().map_or_else(String::new, |geometry| {
+                let mut path = line(geometry, (0.0, geometry.lower_whisker), (0.0, geometry.first_quartile));
+                path.push_str(&line(geometry, (0.0, geometry.third_quartile), (0.0, geometry.upper_whisker)));
+                if !violin {
+                    let cap = geometry.width * CAP_WIDTH * 0.5;
+                    path.push_str(&line(geometry, (-cap, geometry.lower_whisker), (cap, geometry.lower_whisker)));
+                    path.push_str(&line(geometry, (-cap, geometry.upper_whisker), (cap, geometry.upper_whisker)));
+                }
+                path
+            })
+        })
+    };
+
+    let inner_box = move || {
+        geometry.with(|geometry| {
+            geometry.as_ref().filter(|_| violin).map_or_else(String::new, |geometry| rect(geometry, geometry.width * INNER_BOX_WIDTH * 0.5))
+        })
+    };
+
+    let median = move || {
+        geometry.with(|geometry| {
+            geometry.as_ref().map_or_else(String::new, |geometry| {
+                let half_width = if violin { geometry.width * INNER_BOX_WIDTH * 0.5 } else { geometry.width * 0.5 };
+                line(geometry, (-half_width, geometry.median), (half_width, geometry.median))
+            })
+        })
+    };
+
+    let outliers = move || {
+        geometry.with(|geometry| {
+            geometry.as_ref().map_or_else(Vec::new, |geometry| {
+                geometry.outliers.iter()
+                    .map(|y| {
+                        let center = point(geometry, 0.0, *y);
+                        view! { cx,
+                            <circle cx=center.x cy=center.y r=move || outlier_radius.get() fill="none" stroke=Clone::clone(&geometry.color) stroke-width="1"/>
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+    };
+
+    let color = move || geometry.with(|geometry| geometry.as_ref().map(|geometry| Clone::clone(&geometry.color)));
+    let description = move || geometry.with(|geometry| geometry.as_ref().map(|geometry| Clone::clone(&geometry.description)));
+    let axis_color = move || theme.with(|theme| Clone::clone(&theme.axis));
+
+    view! { cx,
+        <g role="graphics-symbol" aria-label=description>
+            <path
+                d=body
+                stroke=move || if highlight.is_highlighted() { theme.with(|theme| Clone::clone(&theme.highlight)) } else { axis_color() }
+                stroke-width=move || if highlight.is_highlighted() { 2.0 } else { 1.0 }
+                stroke-dasharray=move || highlight.dash_array()
+                fill=color
+            />
+            <path d=whiskers stroke=axis_color stroke-width="1" fill="none"/>
+            <path d=inner_box fill=axis_color/>
+            <path d=median stroke=move || if violin { theme.with(|theme| Clone::clone(&theme.surface)) } else { axis_color() } stroke-width="2"/>
+            {outliers}
+        </g>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_categories_have_keys_of_their_own() {
+        let domain = vec![String::from("a"), String::from("a"), String::from("b")];
+        let data = DistributionData::new(domain, vec![vec![1.0], vec![2.0], vec![3.0]]);
+        assert_eq!(data.category_keys(), vec![(String::from("a"), 0), (String::from("a"), 1), (String::from("b"), 0)]);
+    }
+}
@@ -0,0 +1,629 @@
+use std::collections::BTreeSet;
+
+use leptos::{component, create_memo, create_rw_signal, For, IntoView, Memo, ReadSignal, RwSignal, Scope, Signal, SignalGet, SignalWith, view};
+use nalgebra::{Matrix3, Point2, Vector2};
+
+use crate::{ChartOptions, Margin};
+use crate::a11y::{describe_chart, describe_value, DataTable, Describe};
+use crate::annotation::Titles;
+use crate::axis::{self, label_margin, Axis, AxisPosition, Coordinate, Tick, LABEL_PADDING, TICK_SIZE};
+use crate::color::{contrasting_text, hex, ColorScale};
+use crate::scale::{BandScale, LinearScale, Scale};
+use crate::export::ChartExport;
+use crate::frame::ChartFrame;
+use crate::interaction::{ChartCallback, ChartEvents, Highlight, Interaction, SelectCallback, Selection};
+use crate::responsive::resolve_options;
+use crate::theme::use_theme;
+use crate::tooltip::{Datum, HitShape, HitTest, Metric, Target, TooltipRenderer};
+use crate::transition::{create_keyed_lookup, create_tween, unique_keys, Keyed, Tween};
+
+const DEFAULT_TICK_COUNT: usize = 5;
+/// Cells are only hovered while the pointer is inside of them.
+const HIT_DISTANCE: f32 = 0.0;
+const COLOR_BAR_WIDTH: f32 = 12.0;
+/// Space in pixels between the plot and the colour bar.
+const COLOR_BAR_OFFSET: f32 = 16.0;
+/// Number of slices the colour bar is drawn with.
+const COLOR_BAR_STEPS: usize = 64;
+/// Number of steps across the value range the precision of values written into cells is chosen for.
+const VALUE_PRECISION: usize = 100;
+
+/// Values by row and column, e.g. activity by weekday and hour or a correlation matrix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatmapData {
+    rows: Vec<String>,
+    columns: Vec<String>,
+    /// One vector of values per row, `NaN` for missing cells.
+    values: Vec<Vec<f32>>,
+    min_value: f32,
+    max_value: f32,
+}
+
+/// A cell in chart space, keyed by the labels of its row and column, numbered if repeated, for
+/// transitions.
+#[derive(Debug, Clone, PartialEq)]
+struct CellGeometry {
+    row: usize,
+    column: usize,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    value: f32,
+    color: String,
+    /// The value written into the cell, empty if values are not shown or do not fit.
+    text: String,
+    text_color: &'static str,
+    /// Value of the cell in words for screen readers.
+    description: String,
+}
+
+impl Tween for CellGeometry {
+
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        CellGeometry {
+            x: self.x.tween(&to.x, t),
+            y: self.y.tween(&to.y, t),
+            width: self.width.tween(&to.width, t),
+            height: self.height.tween(&to.height, t),
+            ..Clone::clone(to)
+        }
+    }
+
+    /// Cells grow from and shrink to their centre.
+    fn collapsed(&self) -> Self {
+        CellGeometry {
+            x: self.x + self.width * 0.5,
+            y: self.y + self.height * 0.5,
+            width: 0.0,
+            height: 0.0,
+            ..Clone::clone(self)
+        }
+    }
+}
+
+impl HeatmapData {
+
+    /// Creates the data from one vector of values per row, each with one value per column.
+    pub fn new(rows: Vec<String>, columns: Vec<String>, values: Vec<Vec<f32>>) -> Self {
+        let mut data = HeatmapData {
+            rows,
+            columns,
+            values,
+            min_value: 0.0,
+            max_value: 0.0,
+        };
+        data.update();
+        data
+    }
+
+    pub fn set_values(&mut self, values: Vec<Vec<f32>>) {
+        self.values = values;
+        self.update();
+    }
+
+    pub fn rows(&self) -> &[String] {
+        &self.rows
+    }
+
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    pub fn values(&self) -> &[Vec<f32>] {
+        &self.values
+    }
+
+    /// Returns the value of a cell, `None` if it is missing.
+    pub fn value(&self, row: usize, column: usize) -> Option<f32> {
+        self.values.get(row)
+            .and_then(|values| values.get(column))
+            .cloned()
+            .filter(|value| !value.is_nan())
+    }
+
+    /// Returns the lowest and the highest value, `(0.0, 1.0)` if there are none.
+    pub fn value_range(&self) -> (f32, f32) {
+        if self.min_value > self.max_value { (0.0, 1.0) } else { (self.min_value, self.max_value) }
+    }
+
+    /// Returns the row, column and value of every cell that has a value.
+    pub(crate) fn cells(&self) -> impl Iterator<Item = (usize, usize, f32)> + '_ {
+        (0..self.rows.len())
+            .flat_map(move |row| (0..self.columns.len()).map(move |column| (row, column)))
+            .filter_map(|(row, column)| self.value(row, column).map(|value| (row, column, value)))
+    }
+
+    /// Returns the labels of the rows and of the columns numbered by their repetitions, to key
+    /// their cells.
+    pub(crate) fn label_keys(&self) -> (Vec<(String, usize)>, Vec<(String, usize)>) {
+        (unique_keys(self.rows.iter().cloned()), unique_keys(self.columns.iter().cloned()))
+    }
+
+    fn update(&mut self) {
+        let (min_value, max_value) = self.cells()
+            .fold((f32::MAX, f32::MIN), |(min, max), (_, _, value)| (min.min(value), max.max(value)));
+        self.min_value = min_value;
+        self.max_value = max_value;
+    }
+}
+
+impl Describe for HeatmapData {
+
+    type Context = ();
+
+    fn summary(&self, _: &()) -> String {
+        let mut summary = format!("Heatmap of {} rows and {} columns", self.rows.len(), self.columns.len());
+        if self.cells().next().is_some() {
+            summary.push_str(&format!(", values from {} to {}", self.min_value, self.max_value));
+        }
+        summary.push('.');
+        summary
+    }
+
+    /// Returns the values as a table with the rows and columns of the heatmap.
+    fn table(&self, caption: String, _: &()) -> DataTable {
+        let header = std::iter::once(String::new())
+            .chain(self.columns.iter().cloned())
+            .collect();
+        let rows = self.rows.iter()
+            .enumerate()
+            .map(|(row, label)| {
+                std::iter::once(Clone::clone(label))
+                    .chain((0..self.columns.len()).map(|column| {
+                        self.value(row, column).map_or_else(String::new, |value| format!("{}", value))
+                    }))
+                    .collect()
+            })
+            .collect();
+        DataTable { caption, header, rows }
+    }
+}
+
+/// Formats a value with just enough decimals to tell apart values `step` apart.
+fn format_value(value: f32, step: Option<f32>) -> String {
+    match step {
+        Some(step) => axis::format_tick(value, step),
+        None => format!("{}", value),
+    }
+}
+
+/// Whether `text` set in `font_size` fits into a cell of the given size.
+fn text_fits(text: &str, width: f32, height: f32, font_size: f32) -> bool {
+    height >= font_size && width >= text.chars().count() as f32 * font_size * axis::GLYPH_ASPECT
+}
+
+#[component]
+pub fn Heatmap(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<HeatmapData>,
+    /// Renders the content of the tooltip shown for the cell under the pointer.
+    #[prop(optional, into)]
+    tooltip: Option<TooltipRenderer>,
+    /// Called when a cell is selected by click or keyboard, or with `None` when the selection is cleared.
+    #[prop(optional, into)]
+    on_select: Option<SelectCallback>,
+    /// Called when the pointer or the keyboard focus moves onto a cell.
+    #[prop(optional, into)]
+    on_hover: Option<ChartCallback>,
+    /// Called on every click on a cell.
+    #[prop(optional, into)]
+    on_click: Option<ChartCallback>,
+    /// Handle to export the chart as SVG or PNG.
+    #[prop(optional)]
+    export: Option<ChartExport>,
+    /// The selected cell with the row as series and the column as index, set by the chart and
+    /// highlighted when set elsewhere.
+    #[prop(optional)]
+    selected: Option<RwSignal<Option<Selection>>>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+    let size = create_rw_signal(cx, None);
+    let options = resolve_options(cx, options, size.read_only());
+    let font_size = move || theme.with(|theme| theme.font_size);
+
+    // The colour bar takes the place of the legend, there are no series to hide.
+    let hidden = create_rw_signal(cx, BTreeSet::new());
+    let legend = Signal::derive(cx, Vec::new);
+
+    let value_range = create_memo(cx, move |_| {
+        options.with(|options| options.heatmap.value_range)
+            .unwrap_or_else(|| data.with(|data| data.value_range()))
+    });
+
+    let color_scale = create_memo(cx, move |_| {
+        let (min, max) = value_range.get();
+        options.with(|options| {
+            theme.with(|theme| match options.heatmap.center {
+                Some(center) => {
+                    let ramp = Clone::clone(&options.heatmap.color_ramp).unwrap_or_else(|| Clone::clone(&theme.diverging));
+                    ColorScale::diverging((min, center, max), ramp)
+                },
+                None => {
+                    let ramp = Clone::clone(&options.heatmap.color_ramp).unwrap_or_else(|| Clone::clone(&theme.sequential));
+                    ColorScale::sequential((min, max), ramp)
+                },
+            })
+        })
+    });
+
+    let color_labels = create_memo(cx, move |_| {
+        let scale = LinearScale::new(value_range.get(), (0.0, 1.0));
+        let step = scale.tick_step(DEFAULT_TICK_COUNT);
+        scale.ticks(DEFAULT_TICK_COUNT).into_iter()
+            .map(|value| (value, axis::format_tick(value, step)))
+            .collect::<Vec<_>>()
+    });
+
+    let margin = create_memo(cx, move |_| {
+        let row_label_width = data.with(|data| axis::label_width(data.rows.iter(), font_size()));
+        let color_label_width = color_labels.with(|labels| {
+            axis::label_width(labels.iter().map(|(_, label)| label), font_size())
+        });
+        let (header, footer) = options.with(|options| {
+            theme.with(|theme| (options.annotations.header_height(theme), options.annotations.footer_height(theme)))
+        });
+        let edge = axis::edge_margin(font_size());
+        Margin::new(
+            edge + header,
+            COLOR_BAR_OFFSET + COLOR_BAR_WIDTH + label_margin(color_label_width),
+            axis::bottom_margin(font_size()) + footer,
+            label_margin(row_label_width),
+        )
+    });
+
+    let chart_extent = create_memo(cx, move |_| {
+        options.with(|options| margin.with(|margin| margin.shrink(&options.extent)))
+    });
+
+    let column_scale = create_memo(cx, move |_| {
+        let width = chart_extent.with(|extent| extent.width);
+        let padding = options.with(|options| options.heatmap.cell_padding);
+        data.with(|data| {
+            BandScale::new(Clone::clone(&data.columns), (0.0, width))
+                .with_padding_inner(padding)
+                .with_padding_outer(padding * 0.5)
+        })
+    });
+
+    let row_scale = create_memo(cx, move |_| {
+        let height = chart_extent.with(|extent| extent.height);
+        let padding = options.with(|options| options.heatmap.cell_padding);
+        data.with(|data| {
+            BandScale::new(Clone::clone(&data.rows), (0.0, height))
+                .with_padding_inner(padding)
+                .with_padding_outer(padding * 0.5)
+        })
+    });
+
+    // Rows are laid out from top to bottom like the rows of a table, so chart space only needs
+    // to be moved past the margin.
+    let view_matrix = create_memo(cx, move |_| {
+        let (left, top) = margin.with(|margin| (margin.left, margin.top));
+        Matrix3::new_translation(&Vector2::new(left, top))
+    });
+
+    let band_ticks = move |scale: Memo<BandScale>| create_memo(cx, move |_| {
+        scale.with(|scale| {
+            scale.ticks(0).into_iter()
+                .map(|index| Tick {
+                    position: scale.center(index),
+                    label: Clone::clone(&scale.domain()[index]),
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    let column_ticks = band_ticks(column_scale);
+    let row_ticks = band_ticks(row_scale);
+
+    let cells = create_memo(cx, move |_| {
+        let show_values = options.with(|options| options.heatmap.show_values);
+        let font_size = font_size();
+        let step = value_range.with(|(min, max)| (max > min).then(|| axis::nice_step(max - min, VALUE_PRECISION)));
+        color_scale.with(|color_scale| {
+            column_scale.with(|columns| {
+                row_scale.with(|rows| {
+                    data.with(|data| {
+                        let (width, height) = (columns.bandwidth(), rows.bandwidth());
+                        let (row_keys, column_keys) = data.label_keys();
+                        let cells = data.cells()
+                            .map(|(row, column, value)| {
+                                let color = color_scale.map(value);
+                                let text = format_value(value, step);
+                                let fits = text_fits(&text, width, height, font_size);
+                                let key = (Clone::clone(&row_keys[row]), Clone::clone(&column_keys[column]));
+                                (key, CellGeometry {
+                                    row,
+                                    column,
+                                    x: columns.map(column),
+                                    y: rows.map(row),
+                                    width,
+                                    height,
+                                    value,
+                                    color: hex(color),
+                                    text: if show_values && fits { text } else { String::new() },
+                                    text_color: contrasting_text(color),
+                                    description: describe_value(&data.rows[row], &data.columns[column], value),
+                                })
+                            })
+                            .collect::<Vec<_>>();
+                        Keyed(cells)
+                    })
+                })
+            })
+        })
+    });
+
+    let targets = create_memo(cx, move |_| {
+        view_matrix.with(|view_matrix| {
+            data.with(|data| {
+                cells.with(|Keyed(cells)| {
+                    cells.iter()
+                        .map(|(_, cell)| {
+                            let min = view_matrix.transform_point(&Point2::new(cell.x, cell.y));
+                            let max = view_matrix.transform_point(&Point2::new(cell.x + cell.width, cell.y + cell.height));
+                            Target {
+                                anchor: Point2::new((min.x + max.x) * 0.5, min.y),
+                                shape: HitShape::Rect { min, max },
+                                datum: Datum {
+                                    series: cell.row,
+                                    index: cell.column,
+                                    series_name: Clone::clone(&data.rows[cell.row]),
+                                    label: Clone::clone(&data.columns[cell.column]),
+                                    value: cell.value,
+                                    color: Clone::clone(&cell.color),
+                                },
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+        })
+    });
+
+    let interaction = Interaction::new(cx, targets, ChartEvents { on_select, on_hover, on_click }, selected);
+
+    let (description, table) = describe_chart(cx, options, data, || (), || "cells");
+
+    let cell_geometry = create_tween(cx, cells, options);
+
+    let cell_keys = create_memo(cx, move |_| {
+        cell_geometry.with(|Keyed(cells)| cells.iter().map(|(key, _)| Clone::clone(key)).collect::<Vec<_>>())
+    });
+
+    let cell_lookup = create_keyed_lookup(cx, cell_geometry);
+
+    let cell_view = move |cx, key: ((String, usize), (String, usize))| {
+        let geometry = cell_lookup(cx, key);
+        let highlight = Highlight::new(cx, interaction, move || {
+            geometry.with(|geometry| geometry.as_ref().map(|cell| (cell.row, cell.column)))
+        });
+        view! { cx,
+            <Cell view_matrix=view_matrix geometry=geometry highlight=highlight/>
+        }
+    };
+
+    let color_bar_bounds = create_memo(cx, move |_| {
+        let (width, height) = chart_extent.with(|extent| (extent.width, extent.height));
+        view_matrix.with(|view_matrix| {
+            let min = view_matrix.transform_point(&Point2::new(width + COLOR_BAR_OFFSET, 0.0));
+            (min, min + Vector2::new(COLOR_BAR_WIDTH, height))
+        })
+    });
+
+    view! { cx,
+        <ChartFrame
+            options=options
+            legend=legend
+            hidden=hidden
+            size=size
+            export=export
+            interaction=interaction
+            description=description
+            table=table
+            tooltip=tooltip
+            hit_test=HitTest::nearest(HIT_DISTANCE, Metric::Euclidean)
+        >
+            <g>
+                <For each=move || cell_keys.get() key=|key| Clone::clone(key) view=cell_view/>
+            </g>
+            <Axis
+                position=AxisPosition::Left
+                coordinate=Coordinate::Y
+                view_matrix=view_matrix
+                chart_extent=chart_extent
+                ticks=row_ticks
+            />
+            <Axis
+                position=AxisPosition::Bottom
+                coordinate=Coordinate::X
+                view_matrix=view_matrix
+                chart_extent=chart_extent
+                ticks=column_ticks
+            />
+            <ColorBar scale=color_scale range=value_range labels=color_labels bounds=color_bar_bounds/>
+            <Titles options=options/>
+        </ChartFrame>
+    }
+}
+
+#[component]
+fn Cell(
+    cx: Scope,
+    view_matrix: Memo<Matrix3<f32>>,
+    geometry: Memo<Option<CellGeometry>>,
+    highlight: Highlight,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+
+    // Top left and bottom right corner of the cell in screen space.
+    let bounds = move || {
+        geometry.with(|geometry| {
+            geometry.as_ref().map_or((Point2::origin(), Point2::origin()), |cell| {
+                view_matrix.with(|view_matrix| {
+                    let start = view_matrix.transform_point(&Point2::new(cell.x, cell.y));
+                    let end = view_matrix.transform_point(&Point2::new(cell.x + cell.width, cell.y + cell.height));
+                    (Point2::new(start.x.min(end.x), start.y.min(end.y)), Point2::new(start.x.max(end.x), start.y.max(end.y)))
+                })
+            })
+        })
+    };
+    let center = move || {
+        let (min, max) = bounds();
+        nalgebra::center(&min, &max)
+    };
+
+    let color = move || geometry.with(|geometry| geometry.as_ref().map(|cell| Clone::clone(&cell.color)));
+    let text = move || geometry.with(|geometry| geometry.as_ref().map(|cell| Clone::clone(&cell.text)));
+    let text_color = move || geometry.with(|geometry| geometry.as_ref().map_or("none", |cell| cell.text_color));
+    let description = move || geometry.with(|geometry| geometry.as_ref().map(|cell| Clone::clone(&cell.description)));
+
+    view! { cx,
+        <g>
+            <rect
+                x=move || bounds().0.x
+                y=move || bounds().0.y
+                width=move || { let (min, max) = bounds(); max.x - min.x }
+                height=move || { let (min, max) = bounds(); max.y - min.y }
+                stroke=move || if highlight.is_highlighted() { theme.with(|theme| Clone::clone(&theme.highlight)) } else { String::from("none") }
+                stroke-width="2"
+                stroke-dasharray=move || highlight.dash_array()
+                fill=color
+                role="graphics-symbol"
+                aria-label=description
+            />
+            <text
+                x=move || center().x
+                y=move || center().y
+                text-anchor="middle"
+                dominant-baseline="central"
+                fill=text_color
+                font-family=move || theme.with(|theme| Clone::clone(&theme.font_family))
+                font-size=move || theme.with(|theme| theme.font_size)
+                pointer-events="none"
+            >
+                {text}
+            </text>
+        </g>
+    }
+}
+
+/// A vertical bar showing the colours of the value range from the lowest value at the bottom to
+/// the highest at the top, labelled on its right.
+#[component]
+fn ColorBar(
+    cx: Scope,
+    scale: Memo<ColorScale>,
+    range: Memo<(f32, f32)>,
+    labels: Memo<Vec<(f32, String)>>,
+    /// Top left and bottom right corner of the bar in screen space.
+    bounds: Memo<(Point2<f32>, Point2<f32>)>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+
+    let slices = move || {
+        let (min, max) = bounds.get();
+        let slice_height = (max.y - min.y) / COLOR_BAR_STEPS as f32;
+        let (low, high) = range.get();
+        scale.with(|scale| {
+            (0..COLOR_BAR_STEPS)
+                .map(|step| {
+                    let t = (step as f32 + 0.5) / COLOR_BAR_STEPS as f32;
+                    // Slices overlap by half a pixel to hide the seams between them.
+                    view! { cx,
+                        <rect
+                            x=min.x
+                            y=max.y - (step + 1) as f32 * slice_height
+                            width=max.x - min.x
+                            height=slice_height + 0.5
+                            fill=scale.color(low + t * (high - low))
+                        />
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let ticks = move || {
+        let (min, max) = bounds.get();
+        let position = LinearScale::new(range.get(), (max.y, min.y));
+        let theme = theme.get();
+        labels.with(|labels| {
+            labels.iter()
+                .map(|(value, label)| {
+                    let y = position.map(*value);
+                    view! { cx,
+                        <g>
+                            <line
+                                x1=max.x y1=y
+                                x2=max.x + TICK_SIZE y2=y
+                                stroke=Clone::clone(&theme.axis)
+                                stroke-width=theme.axis_stroke_width
+                            />
+                            <text
+                                x=max.x + TICK_SIZE + LABEL_PADDING
+                                y=y
+                                dominant-baseline="middle"
+                                fill=Clone::clone(&theme.text)
+                                font-family=Clone::clone(&theme.font_family)
+                                font-size=theme.font_size
+                            >
+                                {Clone::clone(label)}
+                            </text>
+                        </g>
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    view! { cx,
+        <g aria-hidden="true" pointer-events="none">
+            {slices}
+            {ticks}
+        </g>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|label| String::from(*label)).collect()
+    }
+
+    #[test]
+    fn cells_of_repeated_labels_have_keys_of_their_own() {
+        let data = HeatmapData::new(labels(&["x", "x"]), labels(&["a", "b", "a"]), vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+        let (row_keys, column_keys) = data.label_keys();
+        let keys = data.cells()
+            .map(|(row, column, _)| (Clone::clone(&row_keys[row]), Clone::clone(&column_keys[column])))
+            .collect::<BTreeSet<_>>();
+        assert_eq!(keys.len(), 6);
+    }
+
+    #[test]
+    fn value_range_skips_missing_values() {
+        let mut data = HeatmapData::new(labels(&["x", "y"]), labels(&["a", "b"]), vec![vec![3.0, f32::NAN], vec![-2.0, 7.5]]);
+        assert_eq!(data.value_range(), (-2.0, 7.5));
+        data.set_values(vec![vec![1.0, 4.0], vec![f32::NAN]]);
+        assert_eq!(data.value_range(), (1.0, 4.0));
+        data.set_values(vec![vec![f32::NAN, f32::NAN]]);
+        assert_eq!(data.value_range(), (0.0, 1.0));
+        assert_eq!(HeatmapData::new(vec![], vec![], vec![]).value_range(), (0.0, 1.0));
+    }
+
+    #[test]
+    fn values_are_shown_only_in_cells_large_enough_for_them() {
+        // 4 glyphs of a 10px font take 24px.
+        assert!(text_fits("12.5", 24.0, 10.0, 10.0));
+        assert!(!text_fits("12.5", 23.0, 10.0, 10.0));
+        assert!(!text_fits("12.5", 100.0, 9.0, 10.0));
+        assert!(text_fits("", 0.0, 10.0, 10.0));
+    }
+}
@@ -0,0 +1,412 @@
+use std::collections::BTreeSet;
+
+use leptos::{component, create_memo, create_rw_signal, For, IntoView, Memo, ReadSignal, RwSignal, Scope, Signal, SignalGet, SignalWith, view};
+use nalgebra::{Matrix3, Point2, Vector2};
+
+use crate::{ChartOptions, HistogramOptions, Margin};
+use crate::a11y::{describe_chart, describe_value, DataTable, Describe};
+use crate::annotation::{AnnotationLayer, Titles};
+use crate::axis::{self, label_margin, Axis, AxisPosition, Coordinate, Tick};
+use crate::scale::{LinearScale, Scale};
+use crate::stats::{self, Bin};
+use crate::export::ChartExport;
+use crate::frame::ChartFrame;
+use crate::interaction::{ChartCallback, ChartEvents, Highlight, Interaction, SelectCallback, Selection};
+use crate::responsive::resolve_options;
+use crate::theme::use_theme;
+use crate::tooltip::{Datum, HitShape, HitTest, Metric, Target, TooltipRenderer};
+use crate::transition::{create_keyed_lookup, create_tween, Keyed, Tween};
+
+/// Bins are hovered anywhere above or below them, they can be too low to hit otherwise.
+const HIT_DISTANCE: f32 = 0.0;
+
+/// Raw samples of a single variable, binned by the chart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramData {
+    samples: Vec<f32>,
+}
+
+/// A bin in chart space, keyed by its index for transitions.
+#[derive(Debug, Clone, PartialEq)]
+struct BinGeometry {
+    index: usize,
+    start: f32,
+    end: f32,
+    height: f32,
+    /// Range and value of the bin in words for screen readers.
+    description: String,
+}
+
+impl Tween for BinGeometry {
+
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        BinGeometry {
+            start: self.start.tween(&to.start, t),
+            end: self.end.tween(&to.end, t),
+            height: self.height.tween(&to.height, t),
+            ..Clone::clone(to)
+        }
+    }
+
+    /// Bins grow from and shrink to the x-axis.
+    fn collapsed(&self) -> Self {
+        BinGeometry { height: 0.0, ..Clone::clone(self) }
+    }
+}
+
+impl HistogramData {
+
+    pub fn new(samples: Vec<f32>) -> Self {
+        HistogramData { samples }
+    }
+
+    pub fn set_samples(&mut self, samples: Vec<f32>) {
+        self.samples = samples
+    }
+
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// Returns the bins along with the height of their bars in the mode of the options.
+    pub(crate) fn bars(&self, options: &HistogramOptions) -> Vec<(Bin, f32)> {
+        let bins = options.binning.bins(&self.samples);
+        let total = bins.iter().map(|bin| bin.count).sum::<usize>().max(1) as f32;
+        let mut accumulated = 0.0;
+        bins.into_iter()
+            .map(|bin| {
+                // Densities of the bins integrate to one, accumulated they sum up to the share of samples.
+                let value = match (options.density, options.cumulative) {
+                    (false, false) => bin.count as f32,
+                    (true, false) => bin.count as f32 / (total * (bin.end - bin.start)),
+                    (false, true) => {
+                        accumulated += bin.count as f32;
+                        accumulated
+                    },
+                    (true, true) => {
+                        accumulated += bin.count as f32 / total;
+                        accumulated
+                    },
+                };
+                (bin, value)
+            })
+            .collect()
+    }
+}
+
+impl Describe for HistogramData {
+
+    type Context = HistogramOptions;
+
+    fn summary(&self, options: &HistogramOptions) -> String {
+        let sorted = stats::sorted(&self.samples);
+        let mut summary = format!("Histogram of {} samples in {} bins", sorted.len(), options.binning.bins(&sorted).len());
+        if let (Some(min), Some(max)) = (sorted.first(), sorted.last()) {
+            summary.push_str(&format!(", samples from {} to {}", min, max));
+        }
+        summary.push('.');
+        summary
+    }
+
+    /// Returns the bins as a table with one row per bin.
+    fn table(&self, caption: String, options: &HistogramOptions) -> DataTable {
+        let header = vec![String::from("Bin"), String::from(value_name(options))];
+        let rows = self.bars(options).into_iter()
+            .map(|(bin, value)| vec![bin_label(&bin), format!("{}", value)])
+            .collect();
+        DataTable { caption, header, rows }
+    }
+}
+
+/// Names what the height of the bars stands for.
+fn value_name(options: &HistogramOptions) -> &'static str {
+    match (options.density, options.cumulative) {
+        (false, false) => "Count",
+        (true, false) => "Density",
+        (false, true) => "Cumulative count",
+        (true, true) => "Cumulative share",
+    }
+}
+
+fn bin_label(bin: &Bin) -> String {
+    format!("{} to {}", bin.start, bin.end)
+}
+
+#[component]
+pub fn Histogram(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<HistogramData>,
+    /// Renders the content of the tooltip shown for the bin under the pointer.
+    #[prop(optional, into)]
+    tooltip: Option<TooltipRenderer>,
+    /// Called when a bin is selected by click or keyboard, or with `None` when the selection is cleared.
+    #[prop(optional, into)]
+    on_select: Option<SelectCallback>,
+    /// Called when the pointer or the keyboard focus moves onto a bin.
+    #[prop(optional, into)]
+    on_hover: Option<ChartCallback>,
+    /// Called on every click on a bin.
+    #[prop(optional, into)]
+    on_click: Option<ChartCallback>,
+    /// Handle to export the chart as SVG or PNG.
+    #[prop(optional)]
+    export: Option<ChartExport>,
+    /// The selected bin, set by the chart and highlighted when set elsewhere.
+    #[prop(optional)]
+    selected: Option<RwSignal<Option<Selection>>>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+    let size = create_rw_signal(cx, None);
+    let options = resolve_options(cx, options, size.read_only());
+    let font_size = move || theme.with(|theme| theme.font_size);
+    let chart_height = move || options.with(|options| options.extent.height);
+
+    // A histogram shows a single variable, there are no series to hide.
+    let hidden = create_rw_signal(cx, BTreeSet::new());
+    let legend = Signal::derive(cx, Vec::new);
+
+    let bars = create_memo(cx, move |_| {
+        options.with(|options| data.with(|data| data.bars(&options.histogram)))
+    });
+
+    let domain_range = create_memo(cx, move |_| {
+        bars.with(|bars| match (bars.first(), bars.last()) {
+            (Some((first, _)), Some((last, _))) => (first.start, last.end),
+            _ => (0.0, 1.0),
+        })
+    });
+
+    let value_labels = create_memo(cx, move |_| {
+        let length = chart_height() - axis::edge_margin(font_size()) - axis::bottom_margin(font_size());
+        let count = axis::tick_count(length);
+        let max = bars.with(|bars| bars.iter().map(|(_, value)| *value).fold(0.0, f32::max));
+        let scale = LinearScale::new((0.0, max), (0.0, length)).nice(count);
+        let step = scale.tick_step(count);
+        scale.ticks(count).into_iter()
+            .map(|value| (value, axis::format_tick(value, step)))
+            .collect::<Vec<_>>()
+    });
+
+    let margin = create_memo(cx, move |_| {
+        let left = value_labels.with(|labels| axis::label_width(labels.iter().map(|(_, label)| label), font_size()));
+        let (header, footer) = options.with(|options| {
+            theme.with(|theme| (options.annotations.header_height(theme), options.annotations.footer_height(theme)))
+        });
+        let edge = axis::edge_margin(font_size());
+        Margin::new(edge + header, edge, axis::bottom_margin(font_size()) + footer, label_margin(left))
+    });
+
+    let chart_extent = create_memo(cx, move |_| {
+        options.with(|options| margin.with(|margin| margin.shrink(&options.extent)))
+    });
+
+    let domain_scale = create_memo(cx, move |_| {
+        LinearScale::new(domain_range.get(), (0.0, chart_extent.with(|extent| extent.width)))
+    });
+
+    let value_scale = create_memo(cx, move |_| {
+        let max = value_labels.with(|labels| labels.last().map_or(1.0, |(value, _)| *value));
+        LinearScale::new((0.0, max), (0.0, chart_extent.with(|extent| extent.height)))
+    });
+
+    let view_matrix = create_memo(cx, move |_| {
+        let mut view_matrix = Matrix3::<f32>::new(
+            1.0, 0.0, 0.0,
+            0.0, -1.0, 0.0,
+            0.0, 0.0, 1.0
+        );
+        let (left, top) = margin.with(|margin| (margin.left, margin.top));
+        let height = chart_extent.with(|extent| extent.height);
+        view_matrix.append_translation_mut(&Vector2::new(left, top + height));
+        view_matrix
+    });
+
+    let value_ticks = create_memo(cx, move |_| {
+        value_scale.with(|scale| {
+            value_labels.with(|labels| {
+                labels.iter()
+                    .map(|(value, label)| Tick { position: scale.map(*value), label: Clone::clone(label) })
+                    .collect::<Vec<_>>()
+            })
+        })
+    });
+
+    let domain_ticks = create_memo(cx, move |_| {
+        let count = axis::tick_count(chart_extent.with(|extent| extent.width));
+        domain_scale.with(|scale| {
+            let step = scale.tick_step(count);
+            scale.ticks(count).into_iter()
+                .map(|value| Tick { position: scale.map(value), label: axis::format_tick(value, step) })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let color = move || options.with(|options| theme.with(|theme| options.categorical_pallet(theme).color(0)));
+
+    let bin_geometry = create_memo(cx, move |_| {
+        let value_name = options.with(|options| value_name(&options.histogram));
+        domain_scale.with(|domain_scale| {
+            value_scale.with(|value_scale| {
+                bars.with(|bars| {
+                    let bins = bars.iter()
+                        .enumerate()
+                        .map(|(index, (bin, value))| (index, BinGeometry {
+                            index,
+                            start: domain_scale.map(bin.start),
+                            end: domain_scale.map(bin.end),
+                            height: value_scale.map(*value),
+                            description: describe_value(value_name, &bin_label(bin), *value),
+                        }))
+                        .collect::<Vec<_>>();
+                    Keyed(bins)
+                })
+            })
+        })
+    });
+
+    let targets = create_memo(cx, move |_| {
+        let color = color();
+        view_matrix.with(|view_matrix| {
+            bars.with(|bars| {
+                bin_geometry.with(|Keyed(bins)| {
+                    bins.iter()
+                        .zip(bars.iter())
+                        .map(|((_, geometry), (bin, value))| {
+                            let start = view_matrix.transform_point(&Point2::new(geometry.start, 0.0));
+                            let end = view_matrix.transform_point(&Point2::new(geometry.end, geometry.height));
+                            Target {
+                                anchor: Point2::new((start.x + end.x) * 0.5, end.y),
+                                shape: HitShape::Rect {
+                                    min: Point2::new(start.x.min(end.x), start.y.min(end.y)),
+                                    max: Point2::new(start.x.max(end.x), start.y.max(end.y)),
+                                },
+                                datum: Datum {
+                                    series: 0,
+                                    index: geometry.index,
+                                    series_name: String::new(),
+                                    label: bin_label(bin),
+                                    value: *value,
+                                    color: Clone::clone(&color),
+                                },
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+        })
+    });
+
+    let interaction = Interaction::new(cx, targets, ChartEvents { on_select, on_hover, on_click }, selected);
+
+    let annotations = create_memo(cx, move |_| {
+        let extent = chart_extent.with(|extent| (extent.width, extent.height));
+        domain_scale.with(|domain_scale| {
+            value_scale.with(|value_scale| {
+                options.with(|options| view_matrix.with(|view_matrix| {
+                    options.annotations.place(|x| domain_scale.map(x as f32), |y| value_scale.map(y as f32), extent, view_matrix)
+                }))
+            })
+        })
+    });
+
+    let (description, table) = describe_chart(cx, options, data, move || options.with(|options| Clone::clone(&options.histogram)), || "bins");
+
+    let bin_geometry = create_tween(cx, bin_geometry, options);
+
+    let bin_keys = create_memo(cx, move |_| {
+        bin_geometry.with(|Keyed(bins)| bins.iter().map(|(key, _)| *key).collect::<Vec<_>>())
+    });
+
+    let bin_lookup = create_keyed_lookup(cx, bin_geometry);
+
+    let bin_view = move |cx, key: usize| {
+        let geometry = bin_lookup(cx, key);
+        let highlight = Highlight::new(cx, interaction, move || Some((0, key)));
+        view! { cx,
+            <BinBar view_matrix=view_matrix geometry=geometry color=Signal::derive(cx, color) highlight=highlight/>
+        }
+    };
+
+    view! { cx,
+        <ChartFrame
+            options=options
+            legend=legend
+            hidden=hidden
+            size=size
+            export=export
+            interaction=interaction
+            description=description
+            table=table
+            tooltip=tooltip
+            hit_test=HitTest::nearest(HIT_DISTANCE, Metric::Horizontal)
+        >
+            <Axis
+                position=AxisPosition::Left
+                coordinate=Coordinate::Y
+                view_matrix=view_matrix
+                chart_extent=chart_extent
+                ticks=value_ticks
+                grid=true
+            />
+            <g>
+                <For each=move || bin_keys.get() key=|key| *key view=bin_view/>
+            </g>
+            <Axis
+                position=AxisPosition::Bottom
+                coordinate=Coordinate::X
+                view_matrix=view_matrix
+                chart_extent=chart_extent
+                ticks=domain_ticks
+            />
+            <AnnotationLayer annotations=annotations/>
+            <Titles options=options/>
+        </ChartFrame>
+    }
+}
+
+#[component]
+fn BinBar(
+    cx: Scope,
+    view_matrix: Memo<Matrix3<f32>>,
+    geometry: Memo<Option<BinGeometry>>,
+    color: Signal<String>,
+    highlight: Highlight,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+
+    let d = move || {
+        let Some(BinGeometry { start, end, height, .. }) = geometry.get() else {
+            return String::new()
+        };
+        view_matrix.with(|view_matrix| {
+            let min = view_matrix.transform_point(&Point2::new(start, 0.0));
+            let max = view_matrix.transform_point(&Point2::new(end, height));
+            format!("M {} {} L {} {} L {} {} L {} {} Z", min.x, min.y, min.x, max.y, max.x, max.y, max.x, min.y)
+        })
+    };
+
+    let description = move || geometry.with(|geometry| geometry.as_ref().map(|bin| Clone::clone(&bin.description)));
+
+    // Neighbouring bins are separated by a thin line in the colour of the background.
+    view! { cx,
+        <path
+            d=d
+            stroke=move || {
+                if highlight.is_highlighted() {
+                    theme.with(|theme| Clone::clone(&theme.highlight))
+                }
+                else {
+                    theme.with(|theme| Clone::clone(&theme.surface))
+                }
+            }
+            stroke-width=move || if highlight.is_highlighted() { 2.0 } else { 0.5 }
+            stroke-dasharray=move || highlight.dash_array()
+            fill=color
+            role="graphics-symbol"
+            aria-label=description
+        />
+    }
+}
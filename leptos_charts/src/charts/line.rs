@@ -0,0 +1,630 @@
+use std::collections::BTreeSet;
+
+use leptos::{component, create_memo, create_rw_signal, For, IntoView, ReadSignal, RwSignal, Scope, SignalGet, SignalWith, view};
+use nalgebra::{Matrix3, Point2, Vector2};
+
+use crate::{ChartOptions, DomainScale, Margin, Series};
+use crate::a11y::{describe_chart, DataTable, Describe};
+use crate::annotation::{AnnotationLayer, Titles};
+use crate::axis::{self, label_margin, Axis, AxisPosition, Coordinate, Tick};
+use crate::scale::{LinearScale, Scale, TimeScale};
+use crate::export::ChartExport;
+use crate::frame::ChartFrame;
+use crate::interaction::{ChartCallback, ChartEvents, Interaction, SelectCallback, Selection, SelectionRing};
+use crate::legend::LegendItem;
+use crate::responsive::resolve_options;
+use crate::theme::use_theme;
+use crate::tooltip::{Crosshair, Datum, HitShape, HitTest, Metric, Target, TooltipRenderer};
+use crate::transition::{create_tween, Keyed, Tween};
+
+const DEFAULT_TICK_COUNT: usize = 5;
+const MARKER_RADIUS: f32 = 3.0;
+const AREA_OPACITY: f32 = 0.3;
+
+/// Values of one or more series over a numeric or time domain. Missing values are given as
+/// `NaN`, e.g. with [`Series::with_gaps`], and leave a gap in the line.
+///
+/// The domain does not need to be sorted, it is sorted together with the values of the series.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineChartData {
+    domain: Vec<f64>,
+    series: Vec<Series>,
+}
+
+impl LineChartData {
+
+    pub fn new(domain: Vec<f64>, series: Vec<Series>) -> Self {
+        let mut data = LineChartData { domain, series };
+        data.sort();
+        data
+    }
+
+    /// Replaces the domain and the series, whose values follow the order of the given domain.
+    pub fn set_data(&mut self, domain: Vec<f64>, series: Vec<Series>) {
+        self.domain = domain;
+        self.series = series;
+        self.sort();
+    }
+
+    pub fn domain(&self) -> &[f64] {
+        &self.domain
+    }
+
+    pub fn series(&self) -> &[Series] {
+        &self.series
+    }
+
+    /// Sorts the domain in ascending order and moves the values of every series along with it.
+    /// Series shorter than the domain are padded with missing values.
+    fn sort(&mut self) {
+        let mut order = (0..self.domain.len()).collect::<Vec<_>>();
+        order.sort_by(|a, b| self.domain[*a].total_cmp(&self.domain[*b]));
+        self.domain = order.iter().map(|index| self.domain[*index]).collect();
+        self.series.iter_mut().for_each(|series| {
+            series.values = order.iter()
+                .map(|index| series.values.get(*index).cloned().unwrap_or(f32::NAN))
+                .collect();
+        });
+    }
+
+    fn domain_range(&self) -> (f64, f64) {
+        let (min, max) = self.domain.iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::MAX, f64::MIN), |(min, max), value| (min.min(*value), max.max(*value)));
+        if min > max { (0.0, 1.0) } else { (min, max) }
+    }
+
+    /// Returns the lower and upper value of every point of every series, `NaN` where a value is missing.
+    /// Stacked series start at the upper values of the preceding series, others at zero.
+    pub(crate) fn bands(&self, stacked: bool) -> Vec<Vec<(f32, f32)>> {
+        let mut offsets = vec![0.0f32; self.domain.len()];
+        self.series.iter()
+            .map(|series| {
+                (0..self.domain.len())
+                    .map(|index| {
+                        let value = series.values.get(index).cloned().unwrap_or(f32::NAN);
+                        if !stacked {
+                            return (0.0, value)
+                        }
+                        let lower = offsets[index];
+                        if !value.is_nan() {
+                            offsets[index] += value;
+                        }
+                        (lower, lower + value)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns a copy in which the series at the `hidden` indices have no values.
+    pub(crate) fn without_series(&self, hidden: &BTreeSet<usize>) -> Self {
+        let mut data = Clone::clone(self);
+        data.series.iter_mut()
+            .enumerate()
+            .filter(|(index, _)| hidden.contains(index))
+            .for_each(|(_, series)| series.values.iter_mut().for_each(|value| *value = f32::NAN));
+        data
+    }
+
+    fn value_range(&self, stacked: bool, include_zero: bool) -> (f32, f32) {
+        let (min, max) = self.bands(stacked).iter()
+            .flatten()
+            .flat_map(|(lower, upper)| [*lower, *upper])
+            .filter(|value| !value.is_nan())
+            .fold((f32::MAX, f32::MIN), |(min, max), value| (min.min(value), max.max(value)));
+        let (min, max) = if min > max { (0.0, 1.0) } else { (min, max) };
+        let (min, max) = if include_zero { (min.min(0.0), max.max(0.0)) } else { (min, max) };
+        LinearScale::new((min, max), (0.0, 1.0))
+            .nice(DEFAULT_TICK_COUNT)
+            .domain()
+    }
+}
+
+impl Describe for LineChartData {
+
+    type Context = (bool, DomainAxis);
+
+    fn summary(&self, (area, domain_axis): &(bool, DomainAxis)) -> String {
+        let (start, end) = self.domain_range();
+        format!(
+            "{} chart of {} series over {} values from {} to {}.",
+            if *area { "Area" } else { "Line" }, self.series.len(), self.domain.len(), domain_axis.label(start), domain_axis.label(end)
+        )
+    }
+
+    /// Returns the values as a table with one row per domain value and one column per series.
+    fn table(&self, caption: String, (_, domain_axis): &(bool, DomainAxis)) -> DataTable {
+        let header = std::iter::once(String::new())
+            .chain(self.series.iter().map(|series| Clone::clone(&series.name)))
+            .collect();
+        let rows = self.domain.iter()
+            .enumerate()
+            .map(|(index, x)| {
+                std::iter::once(domain_axis.label(*x))
+                    .chain(self.series.iter().map(|series| {
+                        series.values.get(index)
+                            .filter(|value| !value.is_nan())
+                            .map_or_else(String::new, |value| format!("{}", value))
+                    }))
+                    .collect()
+            })
+            .collect();
+        DataTable { caption, header, rows }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DomainAxis {
+    Linear(LinearScale),
+    Time(TimeScale),
+}
+
+impl DomainAxis {
+
+    fn new(kind: DomainScale, domain: (f64, f64), range: (f32, f32)) -> Self {
+        match kind {
+            DomainScale::Linear => DomainAxis::Linear(LinearScale::new((domain.0 as f32, domain.1 as f32), range)),
+            DomainScale::Time => DomainAxis::Time(TimeScale::new(domain, range)),
+        }
+    }
+
+    fn map(&self, value: f64) -> f32 {
+        match self {
+            DomainAxis::Linear(scale) => scale.map(value as f32),
+            DomainAxis::Time(scale) => scale.map(value),
+        }
+    }
+
+    /// Formats a single domain value, e.g. for tooltips.
+    fn label(&self, value: f64) -> String {
+        match self {
+            DomainAxis::Linear(_) => format!("{}", value),
+            DomainAxis::Time(scale) => scale.tick_interval(DEFAULT_TICK_COUNT).format(value),
+        }
+    }
+
+    fn ticks(&self, count: usize) -> Vec<Tick> {
+        match self {
+            DomainAxis::Linear(scale) => {
+                let step = scale.tick_step(count);
+                scale.ticks(count).into_iter()
+                    .map(|value| Tick { position: scale.map(value), label: axis::format_tick(value, step) })
+                    .collect()
+            },
+            DomainAxis::Time(scale) => {
+                let interval = scale.tick_interval(count);
+                scale.ticks(count).into_iter()
+                    .map(|value| Tick { position: scale.map(value), label: interval.format(value) })
+                    .collect()
+            },
+        }
+    }
+}
+
+/// The bands of one series over the domain they were computed for.
+#[derive(Debug, Clone, PartialEq)]
+struct SeriesBands {
+    domain: Vec<f64>,
+    bands: Vec<(f32, f32)>,
+}
+
+impl Tween for SeriesBands {
+
+    /// Only values over the same domain are blended, a changed domain is shown at once.
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        if self.domain != to.domain {
+            return Clone::clone(to)
+        }
+        SeriesBands { domain: Clone::clone(&to.domain), bands: self.bands.tween(&to.bands, t) }
+    }
+
+    /// Series rise from and fall to their lower values.
+    fn collapsed(&self) -> Self {
+        let bands = self.bands.iter().map(|(lower, _)| (*lower, *lower)).collect();
+        SeriesBands { domain: Clone::clone(&self.domain), bands }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SeriesPaths {
+    index: usize,
+    line: String,
+    area: String,
+    points: Vec<Point2<f32>>,
+}
+
+#[component]
+pub fn LineChart(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<LineChartData>,
+    /// Renders the content of the tooltip shown for the point nearest to the pointer.
+    #[prop(optional, into)]
+    tooltip: Option<TooltipRenderer>,
+    /// Called when a point is selected by click or keyboard, or with `None` when the selection is cleared.
+    #[prop(optional, into)]
+    on_select: Option<SelectCallback>,
+    /// Called when the pointer or the keyboard focus moves onto a point.
+    #[prop(optional, into)]
+    on_hover: Option<ChartCallback>,
+    /// Called on every click on a point.
+    #[prop(optional, into)]
+    on_click: Option<ChartCallback>,
+    /// Handle to export the chart as SVG or PNG.
+    #[prop(optional)]
+    export: Option<ChartExport>,
+    /// The selected point, set by the chart and highlighted when set elsewhere.
+    #[prop(optional)]
+    selected: Option<RwSignal<Option<Selection>>>,
+) -> impl IntoView {
+    let events = ChartEvents { on_select, on_hover, on_click };
+    view! { cx,
+        <Lines options=options data=data area=false tooltip=tooltip events=events selected=selected export=export />
+    }
+}
+
+#[component]
+pub fn AreaChart(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<LineChartData>,
+    /// Renders the content of the tooltip shown for the point nearest to the pointer.
+    #[prop(optional, into)]
+    tooltip: Option<TooltipRenderer>,
+    /// Called when a point is selected by click or keyboard, or with `None` when the selection is cleared.
+    #[prop(optional, into)]
+    on_select: Option<SelectCallback>,
+    /// Called when the pointer or the keyboard focus moves onto a point.
+    #[prop(optional, into)]
+    on_hover: Option<ChartCallback>,
+    /// Called on every click on a point.
+    #[prop(optional, into)]
+    on_click: Option<ChartCallback>,
+    /// Handle to export the chart as SVG or PNG.
+    #[prop(optional)]
+    export: Option<ChartExport>,
+    /// The selected point, set by the chart and highlighted when set elsewhere.
+    #[prop(optional)]
+    selected: Option<RwSignal<Option<Selection>>>,
+) -> impl IntoView {
+    let events = ChartEvents { on_select, on_hover, on_click };
+    view! { cx,
+        <Lines options=options data=data area=true tooltip=tooltip events=events selected=selected export=export />
+    }
+}
+
+#[component]
+fn Lines(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<LineChartData>,
+    area: bool,
+    tooltip: Option<TooltipRenderer>,
+    events: ChartEvents,
+    selected: Option<RwSignal<Option<Selection>>>,
+    export: Option<ChartExport>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+    let size = create_rw_signal(cx, None);
+    let options = resolve_options(cx, options, size.read_only());
+    let font_size = move || theme.with(|theme| theme.font_size);
+    let chart_height = move || options.with(|options| options.extent.height);
+    let stacked = move || options.with(|options| options.line.stacked);
+
+    let hidden = create_rw_signal(cx, BTreeSet::new());
+
+    let legend = create_memo(cx, move |_| {
+        let color_pallet = options.with(|options| theme.with(|theme| options.categorical_pallet(theme)));
+        data.with(|data| {
+            data.series.iter()
+                .enumerate()
+                .map(|(index, series)| LegendItem::new(Clone::clone(&series.name), color_pallet.color_of(index, data.series.len())))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let data = create_memo(cx, move |_| hidden.with(|hidden| data.with(|data| data.without_series(hidden))));
+
+    let value_labels = create_memo(cx, move |_| {
+        let height = chart_height() - axis::edge_margin(font_size()) - axis::bottom_margin(font_size());
+        let count = axis::tick_count(height);
+        let scale = data.with(|data| LinearScale::new(data.value_range(stacked(), area), (0.0, height)));
+        let step = scale.tick_step(count);
+        scale.ticks(count).into_iter()
+            .map(|value| (value, axis::format_tick(value, step)))
+            .collect::<Vec<_>>()
+    });
+
+    let margin = create_memo(cx, move |_| {
+        let left = value_labels.with(|labels| axis::label_width(labels.iter().map(|(_, label)| label), font_size()));
+        let (header, footer) = options.with(|options| {
+            theme.with(|theme| (options.annotations.header_height(theme), options.annotations.footer_height(theme)))
+        });
+        let edge = axis::edge_margin(font_size());
+        Margin::new(edge + header, edge * 2.0, axis::bottom_margin(font_size()) + footer, label_margin(left))
+    });
+
+    let plot_extent = create_memo(cx, move |_| {
+        options.with(|options| margin.with(|margin| margin.shrink(&options.extent)))
+    });
+
+    let value_scale = create_memo(cx, move |_| {
+        let height = plot_extent.with(|extent| extent.height);
+        data.with(|data| LinearScale::new(data.value_range(stacked(), area), (0.0, height)))
+    });
+
+    let domain_axis = create_memo(cx, move |_| {
+        let width = plot_extent.with(|extent| extent.width);
+        let kind = options.with(|options| options.line.domain_scale);
+        data.with(|data| DomainAxis::new(kind, data.domain_range(), (0.0, width)))
+    });
+
+    let view_matrix = create_memo(cx, move |_| {
+        let mut view_matrix = Matrix3::<f32>::new(
+            1.0, 0.0, 0.0,
+            0.0, -1.0, 0.0,
+            0.0, 0.0, 1.0
+        );
+        let (left, top) = margin.with(|margin| (margin.left, margin.top));
+        let (_, y_end) = value_scale.with(|scale| scale.range());
+        view_matrix.append_translation_mut(&Vector2::new(left, top + y_end));
+        view_matrix
+    });
+
+    let value_ticks = create_memo(cx, move |_| {
+        value_scale.with(|scale| {
+            value_labels.with(|labels| {
+                labels.iter()
+                    .map(|(value, label)| Tick { position: scale.map(*value), label: Clone::clone(label) })
+                    .collect::<Vec<_>>()
+            })
+        })
+    });
+
+    let domain_ticks = create_memo(cx, move |_| {
+        let count = axis::tick_count(plot_extent.with(|extent| extent.width) * 0.5);
+        domain_axis.with(|domain_axis| domain_axis.ticks(count))
+    });
+
+    let series_bands = create_memo(cx, move |_| {
+        data.with(|data| {
+            let bands = data.bands(stacked()).into_iter()
+                .enumerate()
+                .map(|(index, bands)| (index, SeriesBands { domain: Clone::clone(&data.domain), bands }))
+                .collect::<Vec<_>>();
+            Keyed(bands)
+        })
+    });
+
+    let series_bands = create_tween(cx, series_bands, options);
+
+    let series_paths = create_memo(cx, move |_| {
+        let curve = options.with(|options| options.line.curve);
+        series_bands.with(|Keyed(series_bands)| {
+            domain_axis.with(|domain_axis| {
+                value_scale.with(|value_scale| {
+                    view_matrix.with(|view_matrix| {
+                        let to_screen = |x: f64, y: f32| {
+                            view_matrix.transform_point(&Point2::new(domain_axis.map(x), value_scale.map(y)))
+                        };
+                        series_bands.iter()
+                            .map(|(index, SeriesBands { domain, bands })| {
+                                let (mut line, mut area, mut points) = (String::new(), String::new(), Vec::new());
+                                // Every run of consecutive values becomes its own sub path, leaving gaps for missing values.
+                                let segments = domain.iter()
+                                    .zip(bands.iter())
+                                    .collect::<Vec<_>>();
+                                segments
+                                    .split(|(_, (_, upper))| upper.is_nan())
+                                    .filter(|run| !run.is_empty())
+                                    .for_each(|run| {
+                                        let upper = run.iter().map(|(x, (_, upper))| to_screen(**x, *upper)).collect::<Vec<_>>();
+                                        let lower = run.iter().rev().map(|(x, (lower, _))| to_screen(**x, *lower)).collect::<Vec<_>>();
+                                        line.push_str(&curve.path(&upper, false));
+                                        area.push_str(&curve.path(&upper, false));
+                                        area.push_str(&curve.path(&lower, true));
+                                        area.push_str("Z ");
+                                        points.extend(upper);
+                                    });
+                                SeriesPaths { index: *index, line, area, points }
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+            })
+        })
+    });
+
+    let targets = create_memo(cx, move |_| {
+        let color_pallet = options.with(|options| theme.with(|theme| options.categorical_pallet(theme)));
+        data.with(|data| {
+            domain_axis.with(|domain_axis| {
+                value_scale.with(|value_scale| {
+                    view_matrix.with(|view_matrix| {
+                        data.bands(stacked()).into_iter()
+                            .zip(data.series.iter())
+                            .enumerate()
+                            .flat_map(|(series_index, (bands, series))| {
+                                data.domain.iter()
+                                    .zip(bands)
+                                    .enumerate()
+                                    .filter(|(_, (_, (_, upper)))| !upper.is_nan())
+                                    .map(|(index, (x, (_, upper)))| {
+                                        let center = view_matrix.transform_point(&Point2::new(domain_axis.map(*x), value_scale.map(upper)));
+                                        Target {
+                                            anchor: center,
+                                            shape: HitShape::Point { center },
+                                            datum: Datum {
+                                                series: series_index,
+                                                index,
+                                                series_name: Clone::clone(&series.name),
+                                                label: domain_axis.label(*x),
+                                                value: series.values[index],
+                                                color: color_pallet.color_of(series_index, data.series.len()),
+                                            },
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+            })
+        })
+    });
+
+    let interaction = Interaction::new(cx, targets, events, selected);
+
+    let plot_bounds = create_memo(cx, move |_| {
+        let (left, top) = margin.with(|margin| (margin.left, margin.top));
+        plot_extent.with(|extent| (Point2::new(left, top), Point2::new(left + extent.width, top + extent.height)))
+    });
+
+    let series_keys = create_memo(cx, move |_| {
+        series_paths.with(|series_paths| series_paths.iter().map(|paths| paths.index).collect::<Vec<_>>())
+    });
+
+    // Every series is rendered once and updated in place, markers are keyed by their position in the series.
+    let series_view = move |cx, index: usize| {
+        let paths = create_memo(cx, move |_| {
+            series_paths.with(|series_paths| series_paths.iter().find(|paths| paths.index == index).cloned())
+        });
+        let color = move || {
+            let color_pallet = options.with(|options| theme.with(|theme| options.categorical_pallet(theme)));
+            color_pallet.color_of(index, data.with(|data| data.series.len()))
+        };
+        let line = move || paths.with(|paths| paths.as_ref().map(|paths| Clone::clone(&paths.line)));
+        let area_view = area.then(|| {
+            let area = move || paths.with(|paths| paths.as_ref().map(|paths| Clone::clone(&paths.area)));
+            view! { cx,
+                <path d=area fill=color fill-opacity=AREA_OPACITY stroke="none"/>
+            }
+        });
+        let marker_count = move || {
+            let markers = options.with(|options| options.line.markers);
+            if markers { paths.with(|paths| paths.as_ref().map_or(0, |paths| paths.points.len())) } else { 0 }
+        };
+        let marker_view = move |cx, marker: usize| {
+            let point = move || paths.with(|paths| paths.as_ref().and_then(|paths| paths.points.get(marker).cloned()));
+            view! { cx,
+                <circle cx=move || point().map(|point| point.x) cy=move || point().map(|point| point.y) r=MARKER_RADIUS fill=color/>
+            }
+        };
+        let name = move || data.with(|data| data.series.get(index).map(|series| Clone::clone(&series.name)));
+        view! { cx,
+            <g role="group" aria-label=name>
+                {area_view}
+                <path d=line fill="none" stroke=color stroke-width=move || theme.with(|theme| theme.line_width) stroke-linejoin="round"/>
+                <For each=move || (0..marker_count()).collect::<Vec<_>>() key=|marker| *marker view=marker_view/>
+            </g>
+        }
+    };
+
+    let annotations = create_memo(cx, move |_| {
+        let extent = plot_extent.with(|extent| (extent.width, extent.height));
+        domain_axis.with(|domain_axis| {
+            value_scale.with(|value_scale| {
+                options.with(|options| view_matrix.with(|view_matrix| {
+                    options.annotations.place(|x| domain_axis.map(x), |y| value_scale.map(y as f32), extent, view_matrix)
+                }))
+            })
+        })
+    });
+
+    let (description, table) = describe_chart(
+        cx,
+        options,
+        data,
+        move || (area, domain_axis.get()),
+        move || if options.with(|options| options.line.markers) { "points" } else { "values" },
+    );
+
+    view! { cx,
+        <ChartFrame
+            options=options
+            legend=legend
+            hidden=hidden
+            size=size
+            export=export
+            interaction=interaction
+            description=description
+            table=table
+            tooltip=tooltip
+            hit_test=HitTest::nearest(f32::INFINITY, Metric::Horizontal)
+        >
+            <Axis
+                position=AxisPosition::Left
+                coordinate=Coordinate::Y
+                view_matrix=view_matrix
+                chart_extent=plot_extent
+                ticks=value_ticks
+                grid=true
+            />
+            <g>
+                <For each=move || series_keys.get() key=|index| *index view=series_view/>
+            </g>
+            <Axis
+                position=AxisPosition::Bottom
+                coordinate=Coordinate::X
+                view_matrix=view_matrix
+                chart_extent=plot_extent
+                ticks=domain_ticks
+            />
+            <Crosshair hover=interaction.hover targets=interaction.targets bounds=plot_bounds vertical_only=true/>
+            <SelectionRing interaction=interaction/>
+            <AnnotationLayer annotations=annotations/>
+            <Titles options=options/>
+        </ChartFrame>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_domain_is_sorted_together_with_the_values() {
+        let data = LineChartData::new(vec![3.0, 1.0, 2.0], vec![
+            Series::new("a", vec![30.0, 10.0, 20.0]),
+            Series::new("b", vec![3.0, 1.0]),
+        ]);
+        assert_eq!(data.domain(), [1.0, 2.0, 3.0]);
+        assert_eq!(data.series()[0].values, [10.0, 20.0, 30.0]);
+        assert_eq!(data.series()[1].values[0], 1.0);
+        assert!(data.series()[1].values[1].is_nan());
+        assert_eq!(data.series()[1].values[2], 3.0);
+    }
+
+    #[test]
+    fn replaced_data_is_sorted_as_well() {
+        let mut data = LineChartData::new(vec![0.0], vec![Series::new("a", vec![1.0])]);
+        data.set_data(vec![2.0, 1.0], vec![Series::new("a", vec![4.0, 5.0])]);
+        assert_eq!(data.domain(), [1.0, 2.0]);
+        assert_eq!(data.series()[0].values, [5.0, 4.0]);
+    }
+
+    #[test]
+    fn gaps_are_missing_values() {
+        let data = LineChartData::new(vec![0.0, 1.0, 2.0], vec![Series::with_gaps("a", vec![Some(1.0), None, Some(2.0)])]);
+        let bands = data.bands(false);
+        assert_eq!(bands[0][0], (0.0, 1.0));
+        assert!(bands[0][1].1.is_nan());
+        assert_eq!(bands[0][2], (0.0, 2.0));
+    }
+
+    #[test]
+    fn stacked_bands_start_at_the_preceding_series() {
+        let data = LineChartData::new(vec![0.0, 1.0], vec![
+            Series::new("a", vec![1.0, 2.0]),
+            Series::with_gaps("b", vec![None, Some(3.0)]),
+            Series::new("c", vec![4.0, 1.0]),
+        ]);
+        let bands = data.bands(true);
+        assert_eq!(bands[0], [(0.0, 1.0), (0.0, 2.0)]);
+        assert_eq!(bands[1][1], (2.0, 5.0));
+        assert_eq!(bands[2], [(1.0, 5.0), (5.0, 6.0)]);
+        assert_eq!(data.value_range(true, true), (0.0, 6.0));
+    }
+}
@@ -0,0 +1,525 @@
+use core::f32::consts::PI;
+use std::collections::BTreeSet;
+
+use leptos::{component, create_memo, create_rw_signal, For, IntoView, ReadSignal, RwSignal, Scope, Signal, SignalGet, SignalWith, view};
+use nalgebra::{Point2, Vector2};
+
+use crate::ChartOptions;
+use crate::annotation::Titles;
+use crate::a11y::{describe_chart, DataTable, Describe};
+use crate::export::ChartExport;
+use crate::frame::ChartFrame;
+use crate::interaction::{ChartCallback, ChartEvents, Highlight, Interaction, SelectCallback, Selection};
+use crate::legend::LegendItem;
+use crate::responsive::resolve_options;
+use crate::theme::use_theme;
+use crate::tooltip::{Datum, HitShape, HitTest, Metric, Target, TooltipRenderer};
+use crate::transition::{create_keyed_lookup, create_tween, unique_keys, Keyed, Tween};
+
+/// Scale applied to the radius of the wedge under the pointer.
+const HOVER_SCALE: f32 = 1.075;
+/// Radius of the pie relative to the space available to it, leaving room for the hovered wedge.
+const PIE_RADIUS: f32 = 0.45;
+/// Height of the caption relative to its font size, including the space above it.
+const CAPTION_LINE_HEIGHT: f32 = 2.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PieChartData {
+    pub items: Vec<PieChartItem>,
+    pub caption: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PieChartItem {
+    pub name: String,
+    pub value: f32,
+}
+
+impl PieChartData {
+
+    /// Returns a copy in which the items at the `hidden` indices have a value of zero.
+    pub(crate) fn without_items(&self, hidden: &BTreeSet<usize>) -> Self {
+        let mut data = Clone::clone(self);
+        data.items.iter_mut()
+            .enumerate()
+            .filter(|(index, _)| hidden.contains(index))
+            .for_each(|(_, item)| item.value = 0.0);
+        data
+    }
+}
+
+impl Describe for PieChartData {
+
+    type Context = ();
+
+    fn summary(&self, _: &()) -> String {
+        let total = self.items.iter().map(|item| item.value.max(0.0)).sum::<f32>();
+        let title = if self.caption.is_empty() { String::from("Pie chart") } else { format!("Pie chart {}", self.caption) };
+        format!("{} of {} items totalling {}.", title, self.items.len(), total)
+    }
+
+    /// Returns the items as a table with their values and shares of the total.
+    fn table(&self, caption: String, _: &()) -> DataTable {
+        let total = self.items.iter().map(|item| item.value.max(0.0)).sum::<f32>();
+        let rows = self.items.iter()
+            .map(|item| {
+                let share = if total > 0.0 { item.value.max(0.0) / total * 100.0 } else { 0.0 };
+                vec![Clone::clone(&item.name), format!("{}", item.value), format!("{:.2}%", share)]
+            })
+            .collect();
+        DataTable {
+            caption,
+            header: vec![String::from("Item"), String::from("Value"), String::from("Share")],
+            rows,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct WedgeData {
+    index: usize,
+    value: f32,
+    percentage: f32,
+    start_angle: f32,
+    end_angle: f32,
+}
+
+impl Tween for WedgeData {
+
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        WedgeData {
+            start_angle: self.start_angle.tween(&to.start_angle, t),
+            end_angle: self.end_angle.tween(&to.end_angle, t),
+            ..Clone::clone(to)
+        }
+    }
+
+    /// Wedges open from and close to their start angle.
+    fn collapsed(&self) -> Self {
+        WedgeData { end_angle: self.start_angle, ..Clone::clone(self) }
+    }
+}
+
+#[component]
+pub fn PieChart(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<PieChartData>,
+    /// Renders the content of the tooltip shown for the wedge under the pointer.
+    #[prop(optional, into)]
+    tooltip: Option<TooltipRenderer>,
+    /// Called when a wedge is selected by click or keyboard, or with `None` when the selection is cleared.
+    #[prop(optional, into)]
+    on_select: Option<SelectCallback>,
+    /// Called when the pointer or the keyboard focus moves onto a wedge.
+    #[prop(optional, into)]
+    on_hover: Option<ChartCallback>,
+    /// Called on every click on a wedge.
+    #[prop(optional, into)]
+    on_click: Option<ChartCallback>,
+    /// Handle to export the chart as SVG or PNG.
+    #[prop(optional)]
+    export: Option<ChartExport>,
+    /// The selected wedge, set by the chart and highlighted when set elsewhere.
+    #[prop(optional)]
+    selected: Option<RwSignal<Option<Selection>>>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+    let size = create_rw_signal(cx, None);
+    let options = resolve_options(cx, options, size.read_only());
+    let width = move || options.with(|options| options.extent.width);
+    let height = move || options.with(|options| options.extent.height);
+
+    let hidden = create_rw_signal(cx, BTreeSet::new());
+
+    let legend = create_memo(cx, move |_| {
+        let color_pallet = options.with(|options| theme.with(|theme| options.categorical_pallet(theme)));
+        data.with(|data| {
+            data.items.iter()
+                .enumerate()
+                .map(|(index, item)| LegendItem::new(Clone::clone(&item.name), color_pallet.color_of(index, data.items.len())))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let data = create_memo(cx, move |_| hidden.with(|hidden| data.with(|data| data.without_items(hidden))));
+
+    let caption = move || data.with(|data| Clone::clone(&data.caption));
+
+    // The pie is centred in the space left between the titles above and the caption and
+    // footnote below it. Returns the centre from the top, the radius and the caption's offset.
+    let layout = create_memo(cx, move |_| {
+        let (header, footer, caption_height) = options.with(|options| theme.with(|theme| {
+            let caption_height = if caption().is_empty() { 0.0 } else { theme.title_font_size * CAPTION_LINE_HEIGHT };
+            (options.annotations.header_height(theme), options.annotations.footer_height(theme), caption_height)
+        }));
+        let plot_height = (height() - header - footer - caption_height).max(0.0);
+        let radius = width().min(plot_height) * PIE_RADIUS;
+        (header + plot_height * 0.5, radius, (plot_height + caption_height) * 0.5)
+    });
+    let center_y = move || layout.with(|(center_y, _, _)| *center_y);
+    let radius = move || layout.with(|(_, radius, _)| *radius);
+    let caption_y = move || layout.with(|(_, _, caption_y)| *caption_y);
+    let view_box = move || format!("{} {} {} {}", width() * -0.5, -center_y(), width(), height());
+
+    let wedge_data = create_memo(cx, move |_| {
+        let (start_angle, pad_angle) = options.with(|options| (options.pie.start_angle, options.pie.pad_angle));
+        data.with(|data| {
+            let sum = data.items.iter().map(|item| item.value.max(0.0)).sum::<f32>();
+            let mut angle = start_angle;
+            data.items.iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    let value = item.value.max(0.0);
+                    let ratio = if sum > 0.0 { value / sum } else { 0.0 };
+                    let sweep = ratio * 2.0 * PI;
+                    let pad = pad_angle.min(sweep) * 0.5;
+                    let wedge = WedgeData {
+                        index,
+                        value: item.value,
+                        percentage: ratio * 100.0,
+                        start_angle: angle + pad,
+                        end_angle: angle + sweep - pad,
+                    };
+                    angle += sweep;
+                    wedge
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let targets = create_memo(cx, move |_| {
+        let (inner_radius, color_pallet) = options.with(|options| (options.pie.inner_radius, theme.with(|theme| options.categorical_pallet(theme))));
+        // The view box is centred on the origin, pointer positions are relative to the top left corner.
+        let center = Point2::new(width() * 0.5, center_y());
+        let radius = radius();
+        data.with(|data| {
+            wedge_data.with(|wedge_data| {
+                wedge_data.iter()
+                    .map(|wedge| {
+                        let middle = (wedge.start_angle + wedge.end_angle) * 0.5;
+                        let anchor = compute_coordinates(middle, radius * (1.0 + inner_radius) * 0.5);
+                        Target {
+                            anchor: center + Vector2::new(anchor.x, anchor.y),
+                            shape: HitShape::Wedge {
+                                center,
+                                inner_radius: radius * inner_radius,
+                                outer_radius: radius * HOVER_SCALE,
+                                start_angle: wedge.start_angle,
+                                end_angle: wedge.end_angle,
+                            },
+                            datum: Datum {
+                                series: 0,
+                                index: wedge.index,
+                                series_name: String::new(),
+                                label: Clone::clone(&data.items[wedge.index].name),
+                                value: wedge.value,
+                                color: color_pallet.color_of(wedge.index, data.items.len()),
+                            },
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+    });
+
+    let interaction = Interaction::new(cx, targets, ChartEvents { on_select, on_hover, on_click }, selected);
+
+    let selected_wedge = move || {
+        interaction.hover.active.get().and_then(|index| wedge_data.with(|wedge_data| wedge_data.get(index).cloned()))
+    };
+    let selected_item_value = move || selected_wedge().map(|wedge| format!("{}", wedge.value));
+    let selected_item_percentage = move || selected_wedge().map(|wedge| format!("{:.2}%", wedge.percentage));
+
+    // Wedges are keyed by the name of their item, items sharing a name by their order.
+    let wedge_geometry = create_memo(cx, move |_| {
+        data.with(|data| {
+            wedge_data.with(|wedge_data| {
+                let item_keys = unique_keys(data.items.iter().map(|item| Clone::clone(&item.name)));
+                let wedges = wedge_data.iter()
+                    .map(|wedge| (Clone::clone(&item_keys[wedge.index]), Clone::clone(wedge)))
+                    .collect::<Vec<_>>();
+                Keyed(wedges)
+            })
+        })
+    });
+
+    let wedge_geometry = create_tween(cx, wedge_geometry, options);
+
+    let wedge_keys = create_memo(cx, move |_| {
+        wedge_geometry.with(|Keyed(wedges)| wedges.iter().map(|(key, _)| Clone::clone(key)).collect::<Vec<_>>())
+    });
+
+    let wedge_lookup = create_keyed_lookup(cx, wedge_geometry);
+
+    // Every wedge is rendered once per key, its outline and colour follow the data in place.
+    let wedge_view = move |cx, key: (String, usize)| {
+        let wedge = wedge_lookup(cx, key);
+        let highlight = Highlight::new(cx, interaction, move || wedge.with(|wedge| wedge.as_ref().map(|wedge| (0, wedge.index))));
+        let path = move || {
+            let Some(WedgeData { start_angle, end_angle, .. }) = wedge.get() else {
+                return String::new()
+            };
+            let (inner_radius, corner_radius) = options.with(|options| (options.pie.inner_radius, options.pie.corner_radius));
+            let outer_radius = if highlight.is_highlighted() { radius() * HOVER_SCALE } else { radius() };
+            wedge_path(start_angle, end_angle, radius() * inner_radius, outer_radius, corner_radius)
+        };
+        let fill = move || {
+            let color_pallet = options.with(|options| theme.with(|theme| options.categorical_pallet(theme)));
+            let count = data.with(|data| data.items.len());
+            wedge.with(|wedge| wedge.as_ref().map(|wedge| color_pallet.color_of(wedge.index, count)))
+        };
+        let stroke = move || if highlight.active.get() { theme.with(|theme| Clone::clone(&theme.highlight)) } else { String::from("none") };
+        let description = move || {
+            wedge.with(|wedge| wedge.as_ref().map(|wedge| {
+                let name = data.with(|data| data.items.get(wedge.index).map(|item| Clone::clone(&item.name)).unwrap_or_default());
+                format!("{}: {} ({:.2}%)", name, wedge.value, wedge.percentage)
+            }))
+        };
+        view! { cx,
+            <path fill=fill stroke=stroke stroke-width="2" stroke-dasharray="4 2" d=path role="graphics-symbol" aria-label=description></path>
+        }
+    };
+
+    let (description, table) = describe_chart(cx, options, data, || (), || "wedges");
+
+    view! { cx,
+        <ChartFrame
+            options=options
+            legend=legend
+            hidden=hidden
+            size=size
+            export=export
+            interaction=interaction
+            description=description
+            table=table
+            tooltip=tooltip
+            hit_test=HitTest::nearest(0.0, Metric::Euclidean)
+            view_box=Signal::derive(cx, view_box)
+        >
+            <For each=move || wedge_keys.get() key=|key| Clone::clone(key) view=wedge_view/>
+            <text
+                text-anchor="middle"
+                alignment-baseline="central"
+                fill=move || theme.with(|theme| Clone::clone(&theme.text))
+                font-family=move || theme.with(|theme| Clone::clone(&theme.font_family))
+                pointer-events="none"
+            >
+                <tspan x="0" dy="0em" font-size=move || theme.with(|theme| theme.title_font_size * 1.6) font-weight="bold">{selected_item_value}</tspan>
+                <tspan x="0" dy="1.75em" font-size=move || theme.with(|theme| theme.font_size)>{selected_item_percentage}</tspan>
+            </text>
+            <text
+                y=caption_y
+                text-anchor="middle"
+                alignment-baseline="central"
+                fill=move || theme.with(|theme| Clone::clone(&theme.text))
+                font-family=move || theme.with(|theme| Clone::clone(&theme.font_family))
+                font-size=move || theme.with(|theme| theme.title_font_size)
+                font-weight="bold"
+            >
+                {caption}
+            </text>
+            <g transform=move || format!("translate({} {})", width() * -0.5, -center_y())>
+                <Titles options=options/>
+            </g>
+        </ChartFrame>
+    }
+}
+
+/// Returns the point at `angle` radians clockwise from twelve o'clock at distance `radius` from the origin.
+pub(crate) fn compute_coordinates(angle: f32, radius: f32) -> Point2<f32> {
+    Point2::new(
+        (angle - PI * 0.5).cos() * radius,
+        (angle - PI * 0.5).sin() * radius,
+    )
+}
+
+/// Builds the outline of a wedge between two angles and two radii. An inner radius of zero
+/// yields a pie slice, the corners are rounded by `corner_radius` where the wedge is large enough.
+pub(crate) fn wedge_path(start_angle: f32, end_angle: f32, inner_radius: f32, outer_radius: f32, corner_radius: f32) -> String {
+    let sweep = end_angle - start_angle;
+    if sweep <= 0.0 || outer_radius <= 0.0 {
+        return String::new()
+    }
+    if sweep >= 2.0 * PI - f32::EPSILON {
+        return full_ring_path(inner_radius, outer_radius)
+    }
+
+    let large_arc = if sweep > PI { 1 } else { 0 };
+    let thickness = outer_radius - inner_radius;
+    // A corner must neither exceed half the ring thickness nor overlap the opposite corner.
+    let half_sweep = (sweep * 0.5).min(PI * 0.5).sin();
+    let outer_corner = corner_radius.min(thickness * 0.5).min(outer_radius * half_sweep / (1.0 + half_sweep)).max(0.0);
+    let inner_corner = if inner_radius > 0.0 {
+        corner_radius.min(thickness * 0.5).min(inner_radius * half_sweep).max(0.0)
+    }
+    else {
+        0.0
+    };
+
+    let mut path = String::new();
+
+    if outer_corner > 0.0 {
+        let delta = (outer_corner / (outer_radius - outer_corner)).asin();
+        let edge = ((outer_radius - outer_corner).powi(2) - outer_corner.powi(2)).sqrt();
+        let p0 = compute_coordinates(start_angle, edge);
+        let p1 = compute_coordinates(start_angle + delta, outer_radius);
+        let p2 = compute_coordinates(end_angle - delta, outer_radius);
+        let p3 = compute_coordinates(end_angle, edge);
+        let large_arc = if sweep - 2.0 * delta > PI { 1 } else { 0 };
+        path.push_str(&format!("M {} {} ", p0.x, p0.y));
+        path.push_str(&format!("A {r} {r} 0 0 1 {} {} ", p1.x, p1.y, r = outer_corner));
+        path.push_str(&format!("A {r} {r} 0 {} 1 {} {} ", large_arc, p2.x, p2.y, r = outer_radius));
+        path.push_str(&format!("A {r} {r} 0 0 1 {} {} ", p3.x, p3.y, r = outer_corner));
+    }
+    else {
+        let p0 = compute_coordinates(start_angle, outer_radius);
+        let p1 = compute_coordinates(end_angle, outer_radius);
+        path.push_str(&format!("M {} {} ", p0.x, p0.y));
+        path.push_str(&format!("A {r} {r} 0 {} 1 {} {} ", large_arc, p1.x, p1.y, r = outer_radius));
+    }
+
+    if inner_radius <= 0.0 {
+        path.push_str("L 0 0 ");
+    }
+    else if inner_corner > 0.0 {
+        let delta = (inner_corner / (inner_radius + inner_corner)).asin();
+        let edge = ((inner_radius + inner_corner).powi(2) - inner_corner.powi(2)).sqrt();
+        let p0 = compute_coordinates(end_angle, edge);
+        let p1 = compute_coordinates(end_angle - delta, inner_radius);
+        let p2 = compute_coordinates(start_angle + delta, inner_radius);
+        let p3 = compute_coordinates(start_angle, edge);
+        let large_arc = if sweep - 2.0 * delta > PI { 1 } else { 0 };
+        path.push_str(&format!("L {} {} ", p0.x, p0.y));
+        path.push_str(&format!("A {r} {r} 0 0 1 {} {} ", p1.x, p1.y, r = inner_corner));
+        path.push_str(&format!("A {r} {r} 0 {} 0 {} {} ", large_arc, p2.x, p2.y, r = inner_radius));
+        path.push_str(&format!("A {r} {r} 0 0 1 {} {} ", p3.x, p3.y, r = inner_corner));
+    }
+    else {
+        let p0 = compute_coordinates(end_angle, inner_radius);
+        let p1 = compute_coordinates(start_angle, inner_radius);
+        path.push_str(&format!("L {} {} ", p0.x, p0.y));
+        path.push_str(&format!("A {r} {r} 0 {} 0 {} {} ", large_arc, p1.x, p1.y, r = inner_radius));
+    }
+
+    path.push('Z');
+    path
+}
+
+/// A single wedge covering the whole circle can not be drawn with one arc, hence two half circles.
+fn full_ring_path(inner_radius: f32, outer_radius: f32) -> String {
+    let mut path = format!(
+        "M 0 {top} A {r} {r} 0 1 1 0 {bottom} A {r} {r} 0 1 1 0 {top} Z ",
+        top = -outer_radius, bottom = outer_radius, r = outer_radius
+    );
+    if inner_radius > 0.0 {
+        path.push_str(&format!(
+            "M 0 {top} A {r} {r} 0 1 0 0 {bottom} A {r} {r} 0 1 0 0 {top} Z",
+            top = -inner_radius, bottom = inner_radius, r = inner_radius
+        ));
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-3, "{} is not close to {}", actual, expected);
+    }
+
+    /// Returns the commands of a path with their numeric arguments.
+    fn commands(path: &str) -> Vec<(String, Vec<f32>)> {
+        let mut commands: Vec<(String, Vec<f32>)> = Vec::new();
+        for token in path.split_whitespace() {
+            match token.parse::<f32>() {
+                Ok(number) => commands.last_mut().unwrap().1.push(number),
+                Err(_) => commands.push((String::from(token), Vec::new())),
+            }
+        }
+        commands
+    }
+
+    /// Returns the points the path moves, draws lines or arcs to.
+    fn points(path: &str) -> Vec<Point2<f32>> {
+        commands(path).into_iter()
+            .filter(|(command, _)| command != "Z")
+            .map(|(_, arguments)| Point2::new(arguments[arguments.len() - 2], arguments[arguments.len() - 1]))
+            .collect()
+    }
+
+    #[test]
+    fn coordinates_start_at_twelve_o_clock_and_run_clockwise() {
+        let top = compute_coordinates(0.0, 2.0);
+        let right = compute_coordinates(PI * 0.5, 2.0);
+        let bottom = compute_coordinates(PI, 2.0);
+        assert_close(top.x, 0.0);
+        assert_close(top.y, -2.0);
+        assert_close(right.x, 2.0);
+        assert_close(right.y, 0.0);
+        assert_close(bottom.x, 0.0);
+        assert_close(bottom.y, 2.0);
+    }
+
+    #[test]
+    fn empty_wedges_have_no_path() {
+        assert_eq!(wedge_path(1.0, 1.0, 0.0, 10.0, 0.0), "");
+        assert_eq!(wedge_path(1.0, 0.5, 0.0, 10.0, 0.0), "");
+        assert_eq!(wedge_path(0.0, 1.0, 0.0, 0.0, 0.0), "");
+    }
+
+    #[test]
+    fn pie_slices_close_at_the_center() {
+        let path = wedge_path(0.0, PI * 0.5, 0.0, 10.0, 0.0);
+        let commands = commands(&path);
+        assert_eq!(commands.iter().map(|(command, _)| command.as_str()).collect::<Vec<_>>(), vec!["M", "A", "L", "Z"]);
+        assert_eq!(commands[1].1[..5], [10.0, 10.0, 0.0, 0.0, 1.0]);
+        let points = points(&path);
+        assert_close(points[0].x, 0.0);
+        assert_close(points[0].y, -10.0);
+        assert_close(points[1].x, 10.0);
+        assert_close(points[1].y, 0.0);
+        assert_eq!(points[2], Point2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn wedges_beyond_half_a_circle_use_the_large_arc() {
+        let path = wedge_path(0.0, PI * 1.5, 5.0, 10.0, 0.0);
+        let arcs = commands(&path).into_iter().filter(|(command, _)| command == "A").collect::<Vec<_>>();
+        assert_eq!(arcs.len(), 2);
+        assert_eq!(arcs[0].1[..5], [10.0, 10.0, 0.0, 1.0, 1.0]);
+        assert_eq!(arcs[1].1[..5], [5.0, 5.0, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn donut_wedges_stay_within_the_ring() {
+        for corner_radius in [0.0, 2.0, 100.0] {
+            for (start, end) in [(0.0, 0.1), (0.3, 2.0), (1.0, 5.5)] {
+                let path = wedge_path(start, end, 5.0, 10.0, corner_radius);
+                for point in points(&path) {
+                    let radius = point.coords.norm();
+                    assert!(radius > 5.0 - 1e-3 && radius < 10.0 + 1e-3, "{} lies outside the ring in {}", radius, path);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rounded_corners_are_limited_by_the_ring_thickness() {
+        let path = wedge_path(0.0, PI, 8.0, 10.0, 100.0);
+        let corner_arcs = commands(&path).into_iter()
+            .filter(|(command, arguments)| command == "A" && arguments[0] != 10.0 && arguments[0] != 8.0)
+            .collect::<Vec<_>>();
+        assert_eq!(corner_arcs.len(), 4);
+        assert!(corner_arcs.iter().all(|(_, arguments)| arguments[0] <= 1.0));
+    }
+
+    #[test]
+    fn full_circles_are_drawn_as_two_half_circles() {
+        assert_eq!(wedge_path(0.0, 2.0 * PI, 0.0, 10.0, 3.0), "M 0 -10 A 10 10 0 1 1 0 10 A 10 10 0 1 1 0 -10 Z ");
+        let ring = wedge_path(0.5, 0.5 + 2.0 * PI, 5.0, 10.0, 0.0);
+        assert_eq!(commands(&ring).iter().filter(|(command, _)| command == "M").count(), 2);
+    }
+}
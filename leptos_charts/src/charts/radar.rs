@@ -0,0 +1,568 @@
+use core::f32::consts::PI;
+use std::collections::BTreeSet;
+
+use leptos::{component, create_memo, create_rw_signal, For, IntoView, Memo, ReadSignal, RwSignal, Scope, Signal, SignalGet, SignalWith, view};
+use nalgebra::{Point2, Vector2};
+
+use crate::{ChartOptions, Series};
+use crate::a11y::{describe_chart, DataTable, Describe};
+use crate::annotation::Titles;
+use crate::axis;
+use crate::scale::{LinearScale, Scale};
+use crate::export::ChartExport;
+use crate::frame::ChartFrame;
+use crate::interaction::{ChartCallback, ChartEvents, Interaction, SelectCallback, Selection, SelectionRing};
+use crate::legend::LegendItem;
+use crate::responsive::resolve_options;
+use crate::theme::use_theme;
+use crate::tooltip::{Datum, HitShape, HitTest, Metric, Target, TooltipRenderer};
+use crate::transition::{create_keyed_lookup, create_tween, Keyed, Tween};
+
+use super::pie::compute_coordinates;
+
+const MARKER_RADIUS: f32 = 3.0;
+/// Maximum distance in pixels between the pointer and a vertex to count as hovering it.
+const HIT_DISTANCE: f32 = 12.0;
+/// Space in pixels between the outermost ring and the labels of the axes.
+const LABEL_OFFSET: f32 = 8.0;
+/// Factor applied to the opacity of the highlighted series.
+const HIGHLIGHT_OPACITY: f32 = 2.0;
+/// Factor applied to the opacity of all other series while one is highlighted.
+const DIMMED_OPACITY: f32 = 0.3;
+
+/// Values of one or more series, one per axis, e.g. scores of products in several categories.
+/// Missing values are given as `NaN` and drawn at the centre without a marker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadarChartData {
+    axes: Vec<String>,
+    series: Vec<Series>,
+}
+
+/// The outline of a series in polar coordinates, one radius in pixels per axis.
+#[derive(Debug, Clone, PartialEq)]
+struct SeriesShape {
+    index: usize,
+    radii: Vec<f32>,
+    /// Whether the value on each axis is missing, its vertex is at the centre without a marker.
+    missing: Vec<bool>,
+}
+
+impl Tween for SeriesShape {
+
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        SeriesShape { radii: self.radii.tween(&to.radii, t), ..Clone::clone(to) }
+    }
+
+    /// Series grow from and shrink to the centre.
+    fn collapsed(&self) -> Self {
+        SeriesShape { radii: vec![0.0; self.radii.len()], ..Clone::clone(self) }
+    }
+}
+
+impl RadarChartData {
+
+    pub fn new(axes: Vec<String>, series: Vec<Series>) -> Self {
+        RadarChartData { axes, series }
+    }
+
+    pub fn set_axes(&mut self, axes: Vec<String>) {
+        self.axes = axes
+    }
+
+    pub fn set_series(&mut self, series: Vec<Series>) {
+        self.series = series
+    }
+
+    pub fn axes(&self) -> &[String] {
+        &self.axes
+    }
+
+    pub fn series(&self) -> &[Series] {
+        &self.series
+    }
+
+    /// Returns the value of `series` on the axis at `index`, `NaN` if it is missing.
+    fn value(&self, series: &Series, index: usize) -> f32 {
+        series.values.get(index).cloned().unwrap_or(f32::NAN)
+    }
+
+    /// Returns the largest value of all series, at least zero.
+    fn max_value(&self) -> f32 {
+        self.series.iter()
+            .flat_map(|series| series.values.iter().take(self.axes.len()))
+            .filter(|value| value.is_finite())
+            .fold(0.0, |max, value| max.max(*value))
+    }
+
+    /// Returns the outline of the series at `index` with its values mapped through `scale`.
+    /// Negative values are drawn at the centre like missing values, but keep their marker.
+    fn shape(&self, index: usize, scale: &LinearScale) -> Option<SeriesShape> {
+        let series = self.series.get(index)?;
+        let values = (0..self.axes.len()).map(|axis| self.value(series, axis)).collect::<Vec<_>>();
+        Some(SeriesShape {
+            index,
+            radii: values.iter().map(|value| if value.is_nan() { 0.0 } else { scale.map(value.max(0.0)) }).collect(),
+            missing: values.iter().map(|value| value.is_nan()).collect(),
+        })
+    }
+
+    /// Returns a copy in which the series at the `hidden` indices have no values.
+    pub(crate) fn without_series(&self, hidden: &BTreeSet<usize>) -> Self {
+        let mut data = Clone::clone(self);
+        data.series.iter_mut()
+            .enumerate()
+            .filter(|(index, _)| hidden.contains(index))
+            .for_each(|(_, series)| series.values.clear());
+        data
+    }
+}
+
+impl Describe for RadarChartData {
+
+    type Context = ();
+
+    fn summary(&self, _: &()) -> String {
+        format!("Radar chart of {} series over {} axes.", self.series.len(), self.axes.len())
+    }
+
+    /// Returns the values as a table with one row per axis and one column per series.
+    fn table(&self, caption: String, _: &()) -> DataTable {
+        let header = std::iter::once(String::new())
+            .chain(self.series.iter().map(|series| Clone::clone(&series.name)))
+            .collect();
+        let rows = self.axes.iter()
+            .enumerate()
+            .map(|(index, axis)| {
+                std::iter::once(Clone::clone(axis))
+                    .chain(self.series.iter().map(|series| {
+                        Some(self.value(series, index))
+                            .filter(|value| !value.is_nan())
+                            .map_or_else(String::new, |value| format!("{}", value))
+                    }))
+                    .collect()
+            })
+            .collect();
+        DataTable { caption, header, rows }
+    }
+}
+
+/// Returns the angle of the axis at `index` out of `count`, clockwise from twelve o'clock.
+fn axis_angle(index: usize, count: usize) -> f32 {
+    index as f32 * 2.0 * PI / count.max(1) as f32
+}
+
+/// Builds a closed polygon through one point per axis, at the given distances from the centre.
+fn polygon_path(radii: impl Iterator<Item = f32>, count: usize) -> String {
+    let mut path = radii
+        .enumerate()
+        .map(|(index, radius)| {
+            let point = compute_coordinates(axis_angle(index, count), radius);
+            format!("{} {} {} ", if index == 0 { "M" } else { "L" }, point.x, point.y)
+        })
+        .collect::<String>();
+    if !path.is_empty() {
+        path.push('Z');
+    }
+    path
+}
+
+#[component]
+pub fn RadarChart(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<RadarChartData>,
+    /// Renders the content of the tooltip shown for the vertex nearest to the pointer.
+    #[prop(optional, into)]
+    tooltip: Option<TooltipRenderer>,
+    /// Called when a vertex is selected by click or keyboard, or with `None` when the selection is cleared.
+    #[prop(optional, into)]
+    on_select: Option<SelectCallback>,
+    /// Called when the pointer or the keyboard focus moves onto a vertex.
+    #[prop(optional, into)]
+    on_hover: Option<ChartCallback>,
+    /// Called on every click on a vertex.
+    #[prop(optional, into)]
+    on_click: Option<ChartCallback>,
+    /// Handle to export the chart as SVG or PNG.
+    #[prop(optional)]
+    export: Option<ChartExport>,
+    /// The selected vertex, set by the chart and highlighted along with its series when set elsewhere.
+    #[prop(optional)]
+    selected: Option<RwSignal<Option<Selection>>>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+    let size = create_rw_signal(cx, None);
+    let options = resolve_options(cx, options, size.read_only());
+    let font_size = move || theme.with(|theme| theme.font_size);
+    let width = move || options.with(|options| options.extent.width);
+    let height = move || options.with(|options| options.extent.height);
+
+    let hidden = create_rw_signal(cx, BTreeSet::new());
+
+    let legend = create_memo(cx, move |_| {
+        let color_pallet = options.with(|options| theme.with(|theme| options.categorical_pallet(theme)));
+        data.with(|data| {
+            data.series.iter()
+                .enumerate()
+                .map(|(index, series)| LegendItem::new(Clone::clone(&series.name), color_pallet.color_of(index, data.series.len())))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let data = create_memo(cx, move |_| hidden.with(|hidden| data.with(|data| data.without_series(hidden))));
+
+    let axis_count = move || data.with(|data| data.axes.len());
+
+    // The radar is centred in the space left between the titles and the footnote, with room for
+    // the labels of the axes around it. Returns the centre from the top and the radius.
+    let layout = create_memo(cx, move |_| {
+        let (header, footer) = options.with(|options| {
+            theme.with(|theme| (options.annotations.header_height(theme), options.annotations.footer_height(theme)))
+        });
+        let label_width = data.with(|data| axis::label_width(data.axes.iter(), font_size()));
+        let plot_height = (height() - header - footer).max(0.0);
+        let radius = (width() - (label_width + LABEL_OFFSET) * 2.0)
+            .min(plot_height - (font_size() + LABEL_OFFSET) * 2.0)
+            .max(0.0) * 0.5;
+        (header + plot_height * 0.5, radius)
+    });
+    let center_y = move || layout.with(|(center_y, _)| *center_y);
+    let radius = move || layout.with(|(_, radius)| *radius);
+    let view_box = move || format!("{} {} {} {}", width() * -0.5, -center_y(), width(), height());
+
+    let value_scale = create_memo(cx, move |_| {
+        let (rings, max_value) = options.with(|options| (options.radar.rings, options.radar.max_value));
+        let max = max_value.unwrap_or_else(|| data.with(|data| data.max_value()));
+        let max = if max > 0.0 { max } else { 1.0 };
+        let scale = LinearScale::new((0.0, max), (0.0, radius()));
+        if max_value.is_some() { scale } else { scale.nice(rings) }
+    });
+
+    // One ring per tick of the value scale, leaving out the centre.
+    let rings = create_memo(cx, move |_| {
+        let count = options.with(|options| options.radar.rings);
+        value_scale.with(|scale| {
+            let step = scale.tick_step(count);
+            scale.ticks(count).into_iter()
+                .filter(|value| *value > 0.0)
+                .map(|value| (scale.map(value), axis::format_tick(value, step)))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    // Rings and spokes as two paths, returned as a pair.
+    let grid = create_memo(cx, move |_| {
+        let count = axis_count();
+        let rings = rings.with(|rings| {
+            rings.iter()
+                .map(|(radius, _)| polygon_path(std::iter::repeat(*radius).take(count), count))
+                .collect::<String>()
+        });
+        let spokes = (0..count)
+            .map(|index| {
+                let end = compute_coordinates(axis_angle(index, count), radius());
+                format!("M 0 0 L {} {} ", end.x, end.y)
+            })
+            .collect::<String>();
+        (rings, spokes)
+    });
+
+    let ring_labels = move || {
+        let theme = theme.get();
+        rings.with(|rings| {
+            rings.iter()
+                .map(|(radius, label)| view! { cx,
+                    <text
+                        x=LABEL_OFFSET * 0.5
+                        y=-radius
+                        dominant-baseline="hanging"
+                        fill=Clone::clone(&theme.text)
+                        font-family=Clone::clone(&theme.font_family)
+                        font-size=theme.font_size
+                        pointer-events="none"
+                    >
+                        {Clone::clone(label)}
+                    </text>
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // Labels are anchored at the side facing the centre and shifted outwards along their axis.
+    let axis_labels = move || {
+        let theme = theme.get();
+        let distance = radius() + LABEL_OFFSET;
+        data.with(|data| {
+            data.axes.iter()
+                .enumerate()
+                .map(|(index, label)| {
+                    let direction = compute_coordinates(axis_angle(index, data.axes.len()), 1.0);
+                    let text_anchor = if direction.x > 0.1 { "start" } else if direction.x < -0.1 { "end" } else { "middle" };
+                    view! { cx,
+                        <text
+                            x=direction.x * distance
+                            y=direction.y * (distance + theme.font_size * 0.5)
+                            text-anchor=text_anchor
+                            dominant-baseline="middle"
+                            fill=Clone::clone(&theme.text)
+                            font-family=Clone::clone(&theme.font_family)
+                            font-size=theme.font_size
+                        >
+                            {Clone::clone(label)}
+                        </text>
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // Hidden series have no values and leave the chart, missing values are drawn at the centre.
+    let series_shapes = create_memo(cx, move |_| {
+        value_scale.with(|scale| {
+            data.with(|data| {
+                let shapes = data.series.iter()
+                    .enumerate()
+                    .filter(|(_, series)| !series.values.is_empty())
+                    .filter_map(|(index, _)| Some((index, data.shape(index, scale)?)))
+                    .collect::<Vec<_>>();
+                Keyed(shapes)
+            })
+        })
+    });
+
+    let targets = create_memo(cx, move |_| {
+        let color_pallet = options.with(|options| theme.with(|theme| options.categorical_pallet(theme)));
+        // The view box is centred on the origin, pointer positions are relative to the top left corner.
+        let center = Point2::new(width() * 0.5, center_y());
+        value_scale.with(|scale| {
+            data.with(|data| {
+                data.series.iter()
+                    .enumerate()
+                    .flat_map(|(series_index, series)| {
+                        (0..data.axes.len())
+                            .map(move |index| (series_index, series, index, data.value(series, index)))
+                            .filter(|(_, _, _, value)| !value.is_nan())
+                    })
+                    .map(|(series_index, series, index, value)| {
+                        let point = compute_coordinates(axis_angle(index, data.axes.len()), scale.map(value.max(0.0)));
+                        let anchor = center + Vector2::new(point.x, point.y);
+                        Target {
+                            anchor,
+                            shape: HitShape::Point { center: anchor },
+                            datum: Datum {
+                                series: series_index,
+                                index,
+                                series_name: Clone::clone(&series.name),
+                                label: Clone::clone(&data.axes[index]),
+                                value,
+                                color: color_pallet.color_of(series_index, data.series.len()),
+                            },
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+    });
+
+    let interaction = Interaction::new(cx, targets, ChartEvents { on_select, on_hover, on_click }, selected);
+
+    // The series under the pointer or the keyboard focus, otherwise the series of the selected vertex.
+    let highlighted = create_memo(cx, move |_| {
+        interaction.active_datum()
+            .map(|datum| datum.series)
+            .or_else(|| interaction.selected.get().map(|selection| selection.series))
+    });
+
+    let (description, table) = describe_chart(cx, options, data, || (), || "vertices");
+
+    let series_shapes = create_tween(cx, series_shapes, options);
+
+    let series_keys = create_memo(cx, move |_| {
+        series_shapes.with(|Keyed(shapes)| shapes.iter().map(|(key, _)| *key).collect::<Vec<_>>())
+    });
+
+    let series_lookup = create_keyed_lookup(cx, series_shapes);
+
+    let series_view = move |cx, index: usize| {
+        let shape = series_lookup(cx, index);
+        let color = Signal::derive(cx, move || {
+            let color_pallet = options.with(|options| theme.with(|theme| options.categorical_pallet(theme)));
+            color_pallet.color_of(index, data.with(|data| data.series.len()))
+        });
+        let name = Signal::derive(cx, move || data.with(|data| data.series.get(index).map(|series| Clone::clone(&series.name))));
+        let opacity = Signal::derive(cx, move || options.with(|options| options.radar.opacity));
+        view! { cx,
+            <SeriesPolygon shape=shape color=color name=name opacity=opacity highlighted=highlighted/>
+        }
+    };
+
+    view! { cx,
+        <ChartFrame
+            options=options
+            legend=legend
+            hidden=hidden
+            size=size
+            export=export
+            interaction=interaction
+            description=description
+            table=table
+            tooltip=tooltip
+            hit_test=HitTest::nearest(HIT_DISTANCE, Metric::Euclidean)
+            view_box=Signal::derive(cx, view_box)
+        >
+            <path
+                d=move || grid.with(|(rings, _)| Clone::clone(rings))
+                fill="none"
+                stroke=move || theme.with(|theme| Clone::clone(&theme.grid))
+                stroke-width=move || theme.with(|theme| theme.grid_stroke_width)
+            />
+            <path
+                d=move || grid.with(|(_, spokes)| Clone::clone(spokes))
+                stroke=move || theme.with(|theme| Clone::clone(&theme.axis))
+                stroke-width=move || theme.with(|theme| theme.axis_stroke_width)
+            />
+            <g>
+                <For each=move || series_keys.get() key=|index| *index view=series_view/>
+            </g>
+            {ring_labels}
+            {axis_labels}
+            <g transform=move || format!("translate({} {})", width() * -0.5, -center_y())>
+                <SelectionRing interaction=interaction/>
+                <Titles options=options/>
+            </g>
+        </ChartFrame>
+    }
+}
+
+/// The filled outline of a series with a marker at every vertex.
+#[component]
+fn SeriesPolygon(
+    cx: Scope,
+    shape: Memo<Option<SeriesShape>>,
+    color: Signal<String>,
+    name: Signal<Option<String>>,
+    /// Opacity of the fill while no series is highlighted.
+    opacity: Signal<f32>,
+    /// The series currently highlighted, all others are dimmed.
+    highlighted: Memo<Option<usize>>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+
+    let emphasis = move || {
+        let index = shape.with(|shape| shape.as_ref().map(|shape| shape.index));
+        match highlighted.get() {
+            None => 1.0,
+            Some(highlighted) if Some(highlighted) == index => HIGHLIGHT_OPACITY,
+            Some(_) => DIMMED_OPACITY,
+        }
+    };
+
+    let path = move || {
+        shape.with(|shape| {
+            shape.as_ref().map_or_else(String::new, |shape| polygon_path(shape.radii.iter().cloned(), shape.radii.len()))
+        })
+    };
+
+    let vertices = move || {
+        shape.with(|shape| {
+            shape.as_ref().map_or_else(Vec::new, |shape| {
+                shape.radii.iter()
+                    .enumerate()
+                    .map(|(index, radius)| compute_coordinates(axis_angle(index, shape.radii.len()), *radius))
+                    .collect::<Vec<_>>()
+            })
+        })
+    };
+
+    let vertex_view = move |cx, vertex: usize| {
+        let point = move || vertices().get(vertex).cloned();
+        view! { cx,
+            <circle
+                cx=move || point().map(|point| point.x)
+                cy=move || point().map(|point| point.y)
+                r=MARKER_RADIUS
+                fill=color
+                fill-opacity=move || emphasis().min(1.0)
+            />
+        }
+    };
+
+    // Missing values have no marker, it would look like a value of zero.
+    let present_vertices = move || {
+        shape.with(|shape| {
+            shape.as_ref().map_or_else(Vec::new, |shape| {
+                (0..shape.radii.len()).filter(|vertex| !shape.missing.get(*vertex).copied().unwrap_or(false)).collect::<Vec<_>>()
+            })
+        })
+    };
+
+    view! { cx,
+        <g role="group" aria-label=name>
+            <path
+                d=path
+                fill=color
+                fill-opacity=move || (opacity.get() * emphasis()).min(1.0)
+                stroke=color
+                stroke-opacity=move || emphasis().min(1.0)
+                stroke-width=move || theme.with(|theme| if emphasis() > 1.0 { theme.line_width + 1.0 } else { theme.line_width })
+                stroke-linejoin="round"
+            />
+            <For each=present_vertices key=|vertex| *vertex view=vertex_view/>
+        </g>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(series: Vec<Vec<f32>>) -> RadarChartData {
+        let axes = (0..4).map(|axis| format!("{}", axis)).collect();
+        RadarChartData::new(axes, series.into_iter().map(|values| Series::new("", values)).collect())
+    }
+
+    #[test]
+    fn axes_start_at_twelve_o_clock_and_turn_clockwise() {
+        assert_eq!(axis_angle(0, 4), 0.0);
+        assert_eq!(axis_angle(1, 4), PI * 0.5);
+        assert_eq!(axis_angle(2, 4), PI);
+        assert_eq!(axis_angle(1, 0), 2.0 * PI);
+    }
+
+    #[test]
+    fn polygons_pass_through_one_point_per_axis() {
+        let path = polygon_path([1.0, 2.0, 3.0, 4.0].into_iter(), 4);
+        let points = path.trim_end_matches('Z')
+            .split(|c| c == 'M' || c == 'L')
+            .filter(|point| !point.trim().is_empty())
+            .map(|point| {
+                let coordinates = point.split_whitespace().map(|value| value.parse::<f32>().unwrap()).collect::<Vec<_>>();
+                Point2::new(coordinates[0], coordinates[1])
+            })
+            .collect::<Vec<_>>();
+        let expected = [Point2::new(0.0, -1.0), Point2::new(2.0, 0.0), Point2::new(0.0, 3.0), Point2::new(-4.0, 0.0)];
+        assert_eq!(points.len(), expected.len());
+        points.iter().zip(&expected).for_each(|(point, expected)| assert!(nalgebra::distance(point, expected) < 1e-5, "{} != {}", point, expected));
+        assert!(path.starts_with("M ") && path.ends_with('Z'));
+        assert_eq!(polygon_path(std::iter::empty(), 0), "");
+    }
+
+    #[test]
+    fn the_largest_value_counts_only_finite_values_on_the_axes() {
+        assert_eq!(data(vec![vec![1.0, f32::NAN, 3.0, 2.0, 9.0], vec![f32::INFINITY, 2.5]]).max_value(), 3.0);
+        assert_eq!(data(vec![vec![-1.0, -2.0]]).max_value(), 0.0);
+        assert_eq!(data(Vec::new()).max_value(), 0.0);
+    }
+
+    #[test]
+    fn missing_and_negative_values_are_drawn_at_the_centre() {
+        let data = data(vec![vec![2.0, f32::NAN, -1.0]]);
+        let shape = data.shape(0, &LinearScale::new((0.0, 4.0), (0.0, 100.0))).unwrap();
+        assert_eq!(shape.radii, vec![50.0, 0.0, 0.0, 0.0]);
+        assert_eq!(shape.missing, vec![false, true, false, true]);
+        assert_eq!(shape.collapsed().missing, shape.missing);
+        assert!(data.shape(1, &LinearScale::new((0.0, 4.0), (0.0, 100.0))).is_none());
+    }
+}
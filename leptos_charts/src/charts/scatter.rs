@@ -0,0 +1,582 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
+
+use leptos::{component, create_memo, create_rw_signal, For, IntoView, ReadSignal, RwSignal, Scope, Signal, SignalGet, SignalWith, view};
+use nalgebra::{Matrix3, Point2, Vector2};
+
+use crate::{ChartOptions, Margin, ScatterOptions};
+use crate::a11y::{describe_chart, DataTable, Describe};
+use crate::annotation::{AnnotationLayer, Titles};
+use crate::axis::{self, label_margin, Axis, AxisPosition, Coordinate, Tick};
+use crate::color::ColorScale;
+use crate::scale::{LinearScale, Scale};
+use crate::spatial::SpatialIndex;
+use crate::export::ChartExport;
+use crate::frame::ChartFrame;
+use crate::interaction::{ChartCallback, ChartEvents, Interaction, SelectCallback, Selection, SelectionRing};
+use crate::legend::LegendItem;
+use crate::responsive::resolve_options;
+use crate::theme::use_theme;
+use crate::tooltip::{Datum, HitTest, Targets, TooltipRenderer};
+
+const DEFAULT_TICK_COUNT: usize = 5;
+/// Number of distinct colours a colour encoding is quantized to, each of them is rendered as one path.
+const COLOR_STEPS: usize = 16;
+/// Maximum distance in pixels between the pointer and a point to count as hovering it.
+const HIT_DISTANCE: f32 = 12.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScatterPoint {
+    pub x: f32,
+    pub y: f32,
+    /// Value encoded by the area of the point in bubble charts.
+    pub size: Option<f32>,
+    /// Value encoded by the colour of the point, taking precedence over the series colour.
+    pub color: Option<f32>,
+}
+
+impl ScatterPoint {
+
+    pub fn new(x: f32, y: f32) -> Self {
+        ScatterPoint { x, y, size: None, color: None }
+    }
+
+    pub fn with_size(self, size: f32) -> Self {
+        ScatterPoint { size: Some(size), ..self }
+    }
+
+    pub fn with_color(self, color: f32) -> Self {
+        ScatterPoint { color: Some(color), ..self }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScatterSeries {
+    pub name: String,
+    pub points: Vec<ScatterPoint>,
+}
+
+impl ScatterSeries {
+    pub fn new(name: impl Into<String>, points: Vec<ScatterPoint>) -> Self {
+        ScatterSeries { name: name.into(), points }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScatterChartData {
+    series: Vec<ScatterSeries>,
+}
+
+impl ScatterChartData {
+
+    pub fn new(series: Vec<ScatterSeries>) -> Self {
+        ScatterChartData { series }
+    }
+
+    pub fn set_series(&mut self, series: Vec<ScatterSeries>) {
+        self.series = series
+    }
+
+    pub fn series(&self) -> &[ScatterSeries] {
+        &self.series
+    }
+
+    /// Returns a copy in which the series at the `hidden` indices have no points.
+    pub(crate) fn without_series(&self, hidden: &BTreeSet<usize>) -> Self {
+        let mut data = Clone::clone(self);
+        data.series.iter_mut()
+            .enumerate()
+            .filter(|(index, _)| hidden.contains(index))
+            .for_each(|(_, series)| series.points.clear());
+        data
+    }
+
+    fn points(&self) -> impl Iterator<Item = &ScatterPoint> + '_ {
+        self.series.iter().flat_map(|series| series.points.iter())
+    }
+
+    fn range(values: impl Iterator<Item = f32>) -> (f32, f32) {
+        let (min, max) = values
+            .filter(|value| value.is_finite())
+            .fold((f32::MAX, f32::MIN), |(min, max), value| (min.min(value), max.max(value)));
+        if min > max { (0.0, 1.0) } else { (min, max) }
+    }
+
+    fn x_range(&self) -> (f32, f32) {
+        let (min, max) = Self::range(self.points().map(|point| point.x));
+        LinearScale::new((min, max), (0.0, 1.0)).nice(DEFAULT_TICK_COUNT).domain()
+    }
+
+    fn y_range(&self) -> (f32, f32) {
+        let (min, max) = Self::range(self.points().map(|point| point.y));
+        LinearScale::new((min, max), (0.0, 1.0)).nice(DEFAULT_TICK_COUNT).domain()
+    }
+
+    fn size_range(&self) -> (f32, f32) {
+        Self::range(self.points().filter_map(|point| point.size))
+    }
+
+    fn color_range(&self) -> (f32, f32) {
+        Self::range(self.points().filter_map(|point| point.color))
+    }
+
+    /// Places the points in screen space and batches their outlines into one path per series and
+    /// colour step. Points without finite coordinates are left out, points without a finite
+    /// colour value take the colour of their series.
+    fn place(
+        &self,
+        options: &ScatterOptions,
+        bubbles: bool,
+        x_scale: &LinearScale,
+        y_scale: &LinearScale,
+        view_matrix: &Matrix3<f32>,
+    ) -> (BTreeMap<(usize, Option<usize>), String>, Vec<PlacedPoint>) {
+        let size_scale = {
+            let (min, max) = self.size_range();
+            // Sizes are encoded by area, hence radii grow with the square root of the value.
+            LinearScale::new((min.max(0.0).sqrt(), max.max(0.0).sqrt()), options.radius_range)
+        };
+        let color_scale = LinearScale::new(self.color_range(), (0.0, 1.0));
+
+        let mut groups: BTreeMap<(usize, Option<usize>), String> = BTreeMap::new();
+        let mut points = Vec::new();
+        self.series.iter().enumerate().for_each(|(series_index, series)| {
+            series.points.iter().enumerate()
+                .filter(|(_, point)| point.x.is_finite() && point.y.is_finite())
+                .for_each(|(index, point)| {
+                    let position = view_matrix.transform_point(&Point2::new(x_scale.map(point.x), y_scale.map(point.y)));
+                    let radius = match (bubbles, point.size.filter(|size| size.is_finite())) {
+                        (true, Some(size)) => size_scale.map(size.max(0.0).sqrt()),
+                        _ => options.radius,
+                    };
+                    let color_step = point.color
+                        .filter(|value| value.is_finite())
+                        .map(|value| (color_scale.map(value) * (COLOR_STEPS - 1) as f32).round() as usize);
+                    groups.entry((series_index, color_step)).or_default()
+                        .push_str(&circle_path(&position, radius));
+                    points.push(PlacedPoint { series: series_index, index, position, radius });
+                });
+        });
+        (groups, points)
+    }
+}
+
+impl Describe for ScatterChartData {
+
+    type Context = bool;
+
+    fn summary(&self, bubbles: &bool) -> String {
+        let (x_min, x_max) = Self::range(self.points().map(|point| point.x));
+        let (y_min, y_max) = Self::range(self.points().map(|point| point.y));
+        format!(
+            "{} chart of {} points in {} series, x from {} to {}, y from {} to {}.",
+            if *bubbles { "Bubble" } else { "Scatter" }, self.points().count(), self.series.len(), x_min, x_max, y_min, y_max
+        )
+    }
+
+    /// Returns the points as a table with one row per point.
+    fn table(&self, caption: String, bubbles: &bool) -> DataTable {
+        let mut header = vec![String::from("Series"), String::from("x"), String::from("y")];
+        if *bubbles {
+            header.push(String::from("Size"));
+        }
+        let rows = self.series.iter()
+            .flat_map(|series| {
+                series.points.iter().map(move |point| {
+                    let mut row = vec![Clone::clone(&series.name), format!("{}", point.x), format!("{}", point.y)];
+                    if *bubbles {
+                        row.push(point.size.map_or_else(String::new, |size| format!("{}", size)));
+                    }
+                    row
+                })
+            })
+            .collect();
+        DataTable { caption, header, rows }
+    }
+}
+
+/// A point in screen space along with the series and index it originates from.
+#[derive(Debug, Clone, PartialEq)]
+struct PlacedPoint {
+    series: usize,
+    index: usize,
+    position: Point2<f32>,
+    radius: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct PointLayout {
+    /// Fill and outline of the points of every series and colour step, the step being `None`
+    /// unless points are coloured by value.
+    paths: BTreeMap<(usize, Option<usize>), (String, String)>,
+    points: Vec<PlacedPoint>,
+    index: SpatialIndex,
+}
+
+#[component]
+pub fn ScatterChart(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<ScatterChartData>,
+    /// Renders the content of the tooltip shown for the point nearest to the pointer.
+    #[prop(optional, into)]
+    tooltip: Option<TooltipRenderer>,
+    /// Called when a point is selected by click or keyboard, or with `None` when the selection is cleared.
+    #[prop(optional, into)]
+    on_select: Option<SelectCallback>,
+    /// Called when the pointer or the keyboard focus moves onto a point.
+    #[prop(optional, into)]
+    on_hover: Option<ChartCallback>,
+    /// Called on every click on a point.
+    #[prop(optional, into)]
+    on_click: Option<ChartCallback>,
+    /// Handle to export the chart as SVG or PNG.
+    #[prop(optional)]
+    export: Option<ChartExport>,
+    /// The selected point, set by the chart and highlighted when set elsewhere.
+    #[prop(optional)]
+    selected: Option<RwSignal<Option<Selection>>>,
+) -> impl IntoView {
+    let events = ChartEvents { on_select, on_hover, on_click };
+    view! { cx,
+        <Points options=options data=data bubbles=false tooltip=tooltip events=events selected=selected export=export />
+    }
+}
+
+#[component]
+pub fn BubbleChart(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<ScatterChartData>,
+    /// Renders the content of the tooltip shown for the point nearest to the pointer.
+    #[prop(optional, into)]
+    tooltip: Option<TooltipRenderer>,
+    /// Called when a point is selected by click or keyboard, or with `None` when the selection is cleared.
+    #[prop(optional, into)]
+    on_select: Option<SelectCallback>,
+    /// Called when the pointer or the keyboard focus moves onto a point.
+    #[prop(optional, into)]
+    on_hover: Option<ChartCallback>,
+    /// Called on every click on a point.
+    #[prop(optional, into)]
+    on_click: Option<ChartCallback>,
+    /// Handle to export the chart as SVG or PNG.
+    #[prop(optional)]
+    export: Option<ChartExport>,
+    /// The selected point, set by the chart and highlighted when set elsewhere.
+    #[prop(optional)]
+    selected: Option<RwSignal<Option<Selection>>>,
+) -> impl IntoView {
+    let events = ChartEvents { on_select, on_hover, on_click };
+    view! { cx,
+        <Points options=options data=data bubbles=true tooltip=tooltip events=events selected=selected export=export />
+    }
+}
+
+#[component]
+fn Points(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    data: ReadSignal<ScatterChartData>,
+    bubbles: bool,
+    tooltip: Option<TooltipRenderer>,
+    events: ChartEvents,
+    selected: Option<RwSignal<Option<Selection>>>,
+    export: Option<ChartExport>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+    let size = create_rw_signal(cx, None);
+    let options = resolve_options(cx, options, size.read_only());
+    let font_size = move || theme.with(|theme| theme.font_size);
+    let chart_height = move || options.with(|options| options.extent.height);
+
+    let hidden = create_rw_signal(cx, BTreeSet::new());
+
+    let legend = create_memo(cx, move |_| {
+        let color_pallet = options.with(|options| theme.with(|theme| options.categorical_pallet(theme)));
+        data.with(|data| {
+            data.series.iter()
+                .enumerate()
+                .map(|(index, series)| LegendItem::new(Clone::clone(&series.name), color_pallet.color_of(index, data.series.len())))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let data = create_memo(cx, move |_| hidden.with(|hidden| data.with(|data| data.without_series(hidden))));
+
+    let y_labels = create_memo(cx, move |_| {
+        let height = chart_height() - axis::edge_margin(font_size()) - axis::bottom_margin(font_size());
+        let count = axis::tick_count(height);
+        let scale = data.with(|data| LinearScale::new(data.y_range(), (0.0, height)));
+        let step = scale.tick_step(count);
+        scale.ticks(count).into_iter()
+            .map(|value| (value, axis::format_tick(value, step)))
+            .collect::<Vec<_>>()
+    });
+
+    let margin = create_memo(cx, move |_| {
+        let left = y_labels.with(|labels| axis::label_width(labels.iter().map(|(_, label)| label), font_size()));
+        let (header, footer) = options.with(|options| {
+            theme.with(|theme| (options.annotations.header_height(theme), options.annotations.footer_height(theme)))
+        });
+        let edge = axis::edge_margin(font_size());
+        Margin::new(edge + header, edge * 2.0, axis::bottom_margin(font_size()) + footer, label_margin(left))
+    });
+
+    let plot_extent = create_memo(cx, move |_| {
+        options.with(|options| margin.with(|margin| margin.shrink(&options.extent)))
+    });
+
+    let x_scale = create_memo(cx, move |_| {
+        let width = plot_extent.with(|extent| extent.width);
+        data.with(|data| LinearScale::new(data.x_range(), (0.0, width)))
+    });
+
+    let y_scale = create_memo(cx, move |_| {
+        let height = plot_extent.with(|extent| extent.height);
+        data.with(|data| LinearScale::new(data.y_range(), (0.0, height)))
+    });
+
+    let view_matrix = create_memo(cx, move |_| {
+        let mut view_matrix = Matrix3::<f32>::new(
+            1.0, 0.0, 0.0,
+            0.0, -1.0, 0.0,
+            0.0, 0.0, 1.0
+        );
+        let (left, top) = margin.with(|margin| (margin.left, margin.top));
+        let (_, y_end) = y_scale.with(|scale| scale.range());
+        view_matrix.append_translation_mut(&Vector2::new(left, top + y_end));
+        view_matrix
+    });
+
+    let x_ticks = create_memo(cx, move |_| {
+        let count = axis::tick_count(plot_extent.with(|extent| extent.width));
+        x_scale.with(|scale| {
+            let step = scale.tick_step(count);
+            scale.ticks(count).into_iter()
+                .map(|value| Tick { position: scale.map(value), label: axis::format_tick(value, step) })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let y_ticks = create_memo(cx, move |_| {
+        y_scale.with(|scale| {
+            y_labels.with(|labels| {
+                labels.iter()
+                    .map(|(value, label)| Tick { position: scale.map(*value), label: Clone::clone(label) })
+                    .collect::<Vec<_>>()
+            })
+        })
+    });
+
+    let layout = create_memo(cx, move |_| {
+        let scatter = options.with(|options| options.scatter.clone());
+        let color_ramp = Clone::clone(&scatter.color_ramp).unwrap_or_else(|| theme.with(|theme| Clone::clone(&theme.sequential)));
+        let step_colors = ColorScale::sequential((0.0, (COLOR_STEPS - 1) as f32), color_ramp);
+        let color_pallet = options.with(|options| theme.with(|theme| options.categorical_pallet(theme)));
+        data.with(|data| {
+            let (groups, points) = data.place(&scatter, bubbles, &x_scale.get(), &y_scale.get(), &view_matrix.get());
+
+            let paths = groups.into_iter()
+                .map(|((series, color_step), path)| {
+                    let fill = match color_step {
+                        Some(step) => step_colors.color(step as f32),
+                        None => color_pallet.color_of(series, data.series.len()),
+                    };
+                    ((series, color_step), (fill, path))
+                })
+                .collect::<BTreeMap<_, _>>();
+
+            let max_radius = points.iter().map(|point| point.radius).fold(0.0f32, f32::max);
+            let index = SpatialIndex::new(points.iter().map(|point| point.position).collect(), (max_radius * 2.0).max(HIT_DISTANCE));
+            PointLayout { paths, points, index }
+        })
+    });
+
+    // Charts may hold many thousand points, the datum is only built for the point shown in the tooltip.
+    let marks = create_memo(cx, move |_| {
+        layout.with(|layout| {
+            layout.points.iter()
+                .map(|placed| (placed.position, Selection::new(placed.series, placed.index)))
+                .collect::<Vec<_>>()
+        })
+    });
+    let targets = Targets::lazy(cx, marks, move |index| {
+        let color_pallet = options.with(|options| theme.with(|theme| options.categorical_pallet(theme)));
+        let placed = layout.with(|layout| layout.points.get(index).cloned())?;
+        data.with(|data| {
+            let series = data.series.get(placed.series)?;
+            let point = series.points.get(placed.index)?;
+            Some(Datum {
+                series: placed.series,
+                index: placed.index,
+                series_name: Clone::clone(&series.name),
+                label: format!("{}", point.x),
+                value: point.y,
+                color: color_pallet.color_of(placed.series, data.series.len()),
+            })
+        })
+    });
+
+    let interaction = Interaction::new(cx, targets, events, selected);
+
+    // Points are looked up in the spatial index instead of testing every target.
+    let hit_test = HitTest::Lookup(Rc::new(move |position| {
+        layout.with(|layout| {
+            let max_radius = layout.points.iter().map(|point| point.radius).fold(0.0f32, f32::max);
+            layout.index.nearest(position, HIT_DISTANCE.max(max_radius))
+        })
+    }));
+
+    let path_keys = create_memo(cx, move |_| {
+        layout.with(|layout| layout.paths.keys().copied().collect::<Vec<_>>())
+    });
+
+    // One path per series and colour step, kept in the DOM while it has points and updated in
+    // place, also when the theme or the colour scale changes its fill.
+    let path_view = move |cx, key: (usize, Option<usize>)| {
+        let path = create_memo(cx, move |_| layout.with(|layout| layout.paths.get(&key).cloned()));
+        let fill = move || path.with(|path| path.as_ref().map(|(fill, _)| Clone::clone(fill)));
+        let d = move || path.with(|path| path.as_ref().map(|(_, d)| Clone::clone(d)));
+        view! { cx,
+            <path d=d fill=fill fill-opacity=move || options.with(|options| options.scatter.opacity) stroke="none"/>
+        }
+    };
+
+    let highlight = move || {
+        interaction.hover.active.get().and_then(|hovered| {
+            layout.with(|layout| layout.points.get(hovered).cloned())
+        })
+        .map(|point| view! { cx,
+            <circle
+                cx=point.position.x
+                cy=point.position.y
+                r=point.radius + 2.0
+                fill="none"
+                stroke=theme.with(|theme| Clone::clone(&theme.highlight))
+                stroke-width="1.5"
+                pointer-events="none"
+            />
+        })
+    };
+
+    let annotations = create_memo(cx, move |_| {
+        let extent = plot_extent.with(|extent| (extent.width, extent.height));
+        x_scale.with(|x_scale| {
+            y_scale.with(|y_scale| {
+                options.with(|options| view_matrix.with(|view_matrix| {
+                    options.annotations.place(|x| x_scale.map(x as f32), |y| y_scale.map(y as f32), extent, view_matrix)
+                }))
+            })
+        })
+    });
+
+    let (description, table) = describe_chart(cx, options, data, move || bubbles, || "points");
+
+    view! { cx,
+        <ChartFrame
+            options=options
+            legend=legend
+            hidden=hidden
+            size=size
+            export=export
+            interaction=interaction
+            description=description
+            table=table
+            tooltip=tooltip
+            hit_test=hit_test
+        >
+            <Axis
+                position=AxisPosition::Left
+                coordinate=Coordinate::Y
+                view_matrix=view_matrix
+                chart_extent=plot_extent
+                ticks=y_ticks
+                grid=true
+            />
+            <Axis
+                position=AxisPosition::Bottom
+                coordinate=Coordinate::X
+                view_matrix=view_matrix
+                chart_extent=plot_extent
+                ticks=x_ticks
+            />
+            <g>
+                <For each=move || path_keys.get() key=|key| *key view=path_view/>
+            </g>
+            {highlight}
+            <SelectionRing interaction=interaction/>
+            <AnnotationLayer annotations=annotations/>
+            <Titles options=options/>
+        </ChartFrame>
+    }
+}
+
+/// Returns a closed circle as two arcs, so many circles can be batched into a single path.
+pub(crate) fn circle_path(center: &Point2<f32>, radius: f32) -> String {
+    format!(
+        "M {} {} a {r} {r} 0 1 0 {d} 0 a {r} {r} 0 1 0 -{d} 0 ",
+        center.x - radius, center.y, r = radius, d = radius * 2.0
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place(series: Vec<ScatterSeries>, bubbles: bool) -> (BTreeMap<(usize, Option<usize>), String>, Vec<PlacedPoint>) {
+        let scale = LinearScale::new((0.0, 8.0), (0.0, 80.0));
+        ScatterChartData::new(series).place(&ScatterOptions::default(), bubbles, &scale, &scale, &Matrix3::identity())
+    }
+
+    fn colored(colors: &[f32]) -> Vec<ScatterPoint> {
+        colors.iter().map(|color| ScatterPoint::new(1.0, 1.0).with_color(*color)).collect()
+    }
+
+    #[test]
+    fn colour_values_are_quantized_into_steps() {
+        let (groups, _) = place(vec![ScatterSeries::new("a", colored(&[0.0, 5.0, 10.0, 9.9]))], false);
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), vec![(0, Some(0)), (0, Some(8)), (0, Some(15))]);
+        assert_eq!(groups[&(0, Some(15))].matches('M').count(), 2);
+    }
+
+    #[test]
+    fn points_without_a_finite_colour_take_the_series_colour() {
+        let mut points = colored(&[2.0, f32::NAN, 4.0, f32::INFINITY]);
+        points.push(ScatterPoint::new(1.0, 1.0));
+        let (groups, _) = place(vec![ScatterSeries::new("a", points)], false);
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), vec![(0, None), (0, Some(0)), (0, Some(15))]);
+        assert_eq!(groups[&(0, None)].matches('M').count(), 3);
+    }
+
+    #[test]
+    fn bubble_radii_grow_with_the_square_root_of_the_size() {
+        let points = [1.0, 4.0, 2.25, f32::NAN].iter()
+            .map(|size| ScatterPoint::new(1.0, 1.0).with_size(*size))
+            .collect::<Vec<_>>();
+        let radii = |bubbles| {
+            let (_, placed) = place(vec![ScatterSeries::new("a", Clone::clone(&points))], bubbles);
+            placed.iter().map(|point| point.radius).collect::<Vec<_>>()
+        };
+        let radius = ScatterOptions::default().radius;
+        assert_eq!(radii(true), vec![2.0, 20.0, 11.0, radius]);
+        assert_eq!(radii(false), vec![radius; 4]);
+    }
+
+    #[test]
+    fn points_are_batched_per_series_and_keep_their_index() {
+        let series = vec![
+            ScatterSeries::new("a", vec![ScatterPoint::new(1.0, 1.0), ScatterPoint::new(f32::NAN, 2.0), ScatterPoint::new(3.0, 3.0)]),
+            ScatterSeries::new("b", vec![ScatterPoint::new(5.0, 5.0)]),
+        ];
+        let (groups, placed) = place(series, false);
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), vec![(0, None), (1, None)]);
+        assert_eq!(groups[&(0, None)].matches('M').count(), 2);
+        assert_eq!(groups[&(1, None)].matches('M').count(), 1);
+        assert_eq!(
+            placed.iter().map(|point| (point.series, point.index, point.position)).collect::<Vec<_>>(),
+            vec![(0, 0, Point2::new(10.0, 10.0)), (0, 2, Point2::new(30.0, 30.0)), (1, 0, Point2::new(50.0, 50.0))]
+        );
+    }
+}
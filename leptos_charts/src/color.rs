@@ -0,0 +1,437 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::f32::consts::PI;
+
+use color_art::Color;
+
+use crate::scale::normalize;
+
+const DEFAULT_COLOR_PALLET: [u32; 16] = [0xf94144, 0xf3722c, 0xf8961e, 0xf9844a, 0xf9c74f, 0x90be6d, 0x43aa8b, 0x4d908e, 0x577590, 0x277da1, 0x2d3057, 0x2b2d42, 0x333a56, 0x4e6e58, 0xbcbd8b, 0xaa5042];
+const MATERIAL_COLOR_PALLET: [u32; 16] = [0xf44336, 0xe81e63, 0x9c27b0, 0x673ab7, 0x3f51b5, 0x2196f3, 0x03a9f4, 0x00bcd4, 0x009688, 0x4caf50, 0x8bc34a, 0xcddc39, 0xffeb3b, 0xffc107, 0xff9800, 0xff5722];
+const DARK_BLUE_COLOR_PALLET: [u32; 16] = [0x001233, 0x001845, 0x012a4a, 0x013a63, 0x01497c, 0x014f86, 0x2a6f97, 0x2c7da0, 0x468faf, 0x61a5c2, 0x89c2d9, 0xa9d6e5, 0x90e0ef, 0xade8f4, 0xcaf0f8, 0x0466c8];
+const VIRIDIS_RAMP: [u32; 10] = [0x440154, 0x482878, 0x3e4989, 0x31688e, 0x26828e, 0x1f9e89, 0x35b779, 0x6ece58, 0xb5de2b, 0xfde725];
+const BLUES_RAMP: [u32; 9] = [0xf7fbff, 0xdeebf7, 0xc6dbef, 0x9ecae1, 0x6baed6, 0x4292c6, 0x2171b5, 0x08519c, 0x08306b];
+const GREENS_RAMP: [u32; 9] = [0xf7fcf5, 0xe5f5e0, 0xc7e9c0, 0xa1d99b, 0x74c476, 0x41ab5d, 0x238b45, 0x006d2c, 0x00441b];
+const RED_BLUE_RAMP: [u32; 11] = [0x67001f, 0xb2182b, 0xd6604d, 0xf4a582, 0xfddbc7, 0xf7f7f7, 0xd1e5f0, 0x92c5de, 0x4393c3, 0x2166ac, 0x053061];
+const ORANGE_FIRE_COLOR_PALLET: [u32; 16] = [0xfc2f00, 0xff4800, 0xff5400, 0xff6000, 0xff6d00, 0xff7900, 0xff7b00, 0xff8800, 0xff9500, 0xffa200, 0xffaa00, 0xffb700, 0xffc300, 0xffd000, 0xffdd00, 0xffea00];
+
+/// Levels per channel of the sRGB grid colours beyond the pallet are picked from.
+const GRID_LEVELS: u32 = 17;
+/// Range of Lab lightness generated colours are picked from.
+const MIN_LIGHTNESS: f32 = 25.0;
+const MAX_LIGHTNESS: f32 = 85.0;
+
+thread_local! {
+    /// Colours generated last, charts ask for the colour of every item with the same count.
+    static GENERATED: RefCell<Option<(ColorPallet, Vec<u32>)>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ColorPallet {
+    #[default]
+    Default,
+    OrangeFire,
+    DarkBlue,
+    Material,
+}
+
+impl ColorPallet {
+
+    pub fn colors(&self) -> &'static [u32; 16] {
+        match self {
+            ColorPallet::Default => &DEFAULT_COLOR_PALLET,
+            ColorPallet::OrangeFire => &ORANGE_FIRE_COLOR_PALLET,
+            ColorPallet::DarkBlue => &DARK_BLUE_COLOR_PALLET,
+            ColorPallet::Material => &MATERIAL_COLOR_PALLET,
+        }
+    }
+
+    /// Returns the colour for the item at `index` as hex string, cycling through the pallet.
+    pub fn color(&self, index: usize) -> String {
+        let colors = self.colors();
+        hex(colors[index % colors.len()])
+    }
+
+    /// Returns `count` colours without repeats, starting with the colours of the pallet. Every
+    /// further colour is the one farthest in Lab from all colours before it, so a colour keeps
+    /// its place when more colours are generated.
+    pub fn generate(&self, count: usize) -> Vec<u32> {
+        let mut colors = self.colors().iter().copied().take(count).collect::<Vec<_>>();
+        // Colours close to black or white are left out, they would vanish on dark or light backgrounds.
+        let mut candidates = grid()
+            .map(|color| (color, to_lab(color), f32::MAX))
+            .filter(|(_, [lightness, _, _], _)| (MIN_LIGHTNESS..=MAX_LIGHTNESS).contains(lightness))
+            .collect::<Vec<_>>();
+        let update = |candidates: &mut Vec<(u32, [f32; 3], f32)>, lab: [f32; 3]| {
+            candidates.iter_mut().for_each(|(_, other, distance)| *distance = distance.min(delta_e(&lab, other)));
+        };
+        colors.iter().for_each(|color| update(&mut candidates, to_lab(*color)));
+        while colors.len() < count {
+            let farthest = candidates.iter()
+                .enumerate()
+                .filter(|(_, (_, _, distance))| *distance > 0.0)
+                .max_by(|(_, (_, _, a)), (_, (_, _, b))| a.total_cmp(b))
+                .map(|(position, _)| position);
+            let Some(position) = farthest else {
+                break
+            };
+            let (color, lab, _) = candidates.swap_remove(position);
+            colors.push(color);
+            update(&mut candidates, lab);
+        }
+        // Once the grid is used up, the remaining colours follow in order.
+        if colors.len() < count {
+            let used = colors.iter().copied().collect::<HashSet<_>>();
+            let rest = (0..=0xffffff).filter(|color| !used.contains(color)).take(count - colors.len());
+            colors.extend(rest);
+        }
+        colors
+    }
+
+    /// Returns the colour for the item at `index` of `count` items as hex string, without
+    /// repeating colours when there are more items than colours in the pallet.
+    pub fn color_of(&self, index: usize, count: usize) -> String {
+        if count <= self.colors().len() {
+            return self.color(index)
+        }
+        GENERATED.with(|generated| {
+            let mut generated = generated.borrow_mut();
+            let cached = matches!(&*generated, Some((pallet, colors)) if pallet == self && colors.len() >= count);
+            if !cached {
+                *generated = Some((*self, self.generate(count)));
+            }
+            generated.as_ref().map_or_else(|| self.color(index), |(_, colors)| hex(colors[index % count]))
+        })
+    }
+}
+
+/// Colours of an evenly spaced grid over sRGB.
+fn grid() -> impl Iterator<Item = u32> {
+    let level = |step: u32| (step * 255 + (GRID_LEVELS - 1) / 2) / (GRID_LEVELS - 1);
+    (0..GRID_LEVELS).flat_map(move |r| {
+        (0..GRID_LEVELS).flat_map(move |g| (0..GRID_LEVELS).map(move |b| (level(r) << 16) | (level(g) << 8) | level(b)))
+    })
+}
+
+/// Perceptual distance of two Lab colours (CIE76).
+fn delta_e(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+/// Colour space colours are interpolated in.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Interpolation {
+    Rgb,
+    /// Perceptually uniform CIE Lab, avoiding the muddy midpoints of RGB.
+    #[default]
+    Lab,
+    /// Polar form of Lab, interpolating the hue along the shorter arc.
+    Hcl,
+}
+
+/// A sequence of colours a continuous value is mapped along.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorRamp {
+    Viridis,
+    Blues,
+    Greens,
+    /// Diverging from red over white to blue.
+    RedBlue,
+    /// Colours `0xRRGGBB` from the lowest to the highest value, at least one.
+    Custom(Vec<u32>),
+}
+
+impl ColorRamp {
+
+    pub fn stops(&self) -> &[u32] {
+        match self {
+            ColorRamp::Viridis => &VIRIDIS_RAMP,
+            ColorRamp::Blues => &BLUES_RAMP,
+            ColorRamp::Greens => &GREENS_RAMP,
+            ColorRamp::RedBlue => &RED_BLUE_RAMP,
+            ColorRamp::Custom(stops) => stops,
+        }
+    }
+}
+
+/// Maps numeric values onto colours of a ramp. Sequential scales span the ramp from the lowest to
+/// the highest value of the domain, diverging scales put the middle of the ramp at a given value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorScale {
+    domain: Vec<f32>,
+    ramp: ColorRamp,
+    interpolation: Interpolation,
+}
+
+impl ColorScale {
+
+    pub fn sequential(domain: (f32, f32), ramp: ColorRamp) -> Self {
+        ColorScale { domain: vec![domain.0, domain.1], ramp, interpolation: Interpolation::default() }
+    }
+
+    /// Creates a scale mapping `domain.1` onto the middle of the ramp.
+    pub fn diverging(domain: (f32, f32, f32), ramp: ColorRamp) -> Self {
+        ColorScale { domain: vec![domain.0, domain.1, domain.2], ramp, interpolation: Interpolation::default() }
+    }
+
+    pub fn with_interpolation(self, interpolation: Interpolation) -> Self {
+        ColorScale { interpolation, ..self }
+    }
+
+    pub fn domain(&self) -> &[f32] {
+        &self.domain
+    }
+
+    pub fn ramp(&self) -> &ColorRamp {
+        &self.ramp
+    }
+
+    /// Returns the position of `value` along the ramp between `0.0` and `1.0`.
+    pub fn position(&self, value: f32) -> f32 {
+        let t = match self.domain[..] {
+            [min, max] => normalize((min, max), value),
+            [min, middle, _] if value < middle => normalize((min, middle), value) * 0.5,
+            [_, middle, max] => 0.5 + normalize((middle, max), value) * 0.5,
+            _ => 0.5,
+        };
+        if t.is_finite() { t.clamp(0.0, 1.0) } else { 0.0 }
+    }
+
+    /// Maps `value` onto a colour `0xRRGGBB`, values outside the domain are clamped.
+    pub fn map(&self, value: f32) -> u32 {
+        sample(self.ramp.stops(), self.position(value), self.interpolation)
+    }
+
+    /// Maps `value` onto a colour as hex string.
+    pub fn color(&self, value: f32) -> String {
+        hex(self.map(value))
+    }
+}
+
+/// Returns the colour at `t` between `0.0` and `1.0` along evenly spaced `stops`.
+pub(crate) fn sample(stops: &[u32], t: f32, interpolation: Interpolation) -> u32 {
+    match stops.len() {
+        0 => 0,
+        1 => stops[0],
+        len => {
+            let position = t.clamp(0.0, 1.0) * (len - 1) as f32;
+            let index = (position.floor() as usize).min(len - 2);
+            interpolate(stops[index], stops[index + 1], position - index as f32, interpolation)
+        },
+    }
+}
+
+/// Interpolates between two `0xRRGGBB` colours.
+pub(crate) fn interpolate(from: u32, to: u32, t: f32, interpolation: Interpolation) -> u32 {
+    let t = t.clamp(0.0, 1.0);
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    match interpolation {
+        Interpolation::Rgb => {
+            let (a, b) = (channels(from), channels(to));
+            from_channels([lerp(a[0], b[0]), lerp(a[1], b[1]), lerp(a[2], b[2])])
+        },
+        Interpolation::Lab => {
+            let (a, b) = (to_lab(from), to_lab(to));
+            from_lab([lerp(a[0], b[0]), lerp(a[1], b[1]), lerp(a[2], b[2])])
+        },
+        Interpolation::Hcl => {
+            let (a, b) = (to_lab(from), to_lab(to));
+            let (chroma_a, chroma_b) = (a[1].hypot(a[2]), b[1].hypot(b[2]));
+            let (hue_a, mut hue_b) = (a[2].atan2(a[1]), b[2].atan2(b[1]));
+            if hue_b - hue_a > PI {
+                hue_b -= 2.0 * PI;
+            }
+            else if hue_a - hue_b > PI {
+                hue_b += 2.0 * PI;
+            }
+            // Greys have no hue, they take the hue of the other colour instead of turning to red.
+            let (hue_a, hue_b) = match (chroma_a < 1e-3, chroma_b < 1e-3) {
+                (true, false) => (hue_b, hue_b),
+                (false, true) => (hue_a, hue_a),
+                _ => (hue_a, hue_b),
+            };
+            let (chroma, hue) = (lerp(chroma_a, chroma_b), lerp(hue_a, hue_b));
+            from_lab([lerp(a[0], b[0]), chroma * hue.cos(), chroma * hue.sin()])
+        },
+    }
+}
+
+fn channels(color: u32) -> [f32; 3] {
+    [((color >> 16) & 0xff) as f32, ((color >> 8) & 0xff) as f32, (color & 0xff) as f32]
+}
+
+fn from_channels(channels: [f32; 3]) -> u32 {
+    channels.iter().fold(0, |color, channel| (color << 8) | channel.round().clamp(0.0, 255.0) as u32)
+}
+
+// The conversions between sRGB and CIE Lab are done here on `0xRRGGBB` numbers because
+// color_art only formats Lab as CSS strings, which would be formatted and parsed again for
+// every sampled colour of a heatmap. They follow the sRGB specification with a D65 white point.
+
+/// Reference white D65.
+const WHITE: [f32; 3] = [0.950_47, 1.0, 1.088_83];
+const EPSILON: f32 = 6.0 / 29.0;
+
+fn to_lab(color: u32) -> [f32; 3] {
+    let [r, g, b] = channels(color).map(|channel| {
+        let channel = channel / 255.0;
+        if channel <= 0.040_45 { channel / 12.92 } else { ((channel + 0.055) / 1.055).powf(2.4) }
+    });
+    let xyz = [
+        0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b,
+        0.212_672_9 * r + 0.715_152_2 * g + 0.072_175_0 * b,
+        0.019_333_9 * r + 0.119_192_0 * g + 0.950_304_1 * b,
+    ];
+    let [x, y, z] = [0, 1, 2].map(|index| {
+        let t = xyz[index] / WHITE[index];
+        if t > EPSILON.powi(3) { t.cbrt() } else { t / (3.0 * EPSILON * EPSILON) + 4.0 / 29.0 }
+    });
+    [116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z)]
+}
+
+fn from_lab(lab: [f32; 3]) -> u32 {
+    let y = (lab[0] + 16.0) / 116.0;
+    let [x, y, z] = [y + lab[1] / 500.0, y, y - lab[2] / 200.0];
+    let [x, y, z] = [(x, 0), (y, 1), (z, 2)].map(|(t, index)| {
+        let t = if t > EPSILON { t.powi(3) } else { 3.0 * EPSILON * EPSILON * (t - 4.0 / 29.0) };
+        t * WHITE[index]
+    });
+    let linear = [
+        3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+        -0.969_266_0 * x + 1.876_010_8 * y + 0.041_556_0 * z,
+        0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z,
+    ];
+    from_channels(linear.map(|channel| {
+        let channel = channel.clamp(0.0, 1.0);
+        let channel = if channel <= 0.003_130_8 { channel * 12.92 } else { 1.055 * channel.powf(1.0 / 2.4) - 0.055 };
+        channel * 255.0
+    }))
+}
+
+/// Returns black or white text, whichever contrasts more with the `0xRRGGBB` background.
+pub(crate) fn contrasting_text(background: u32) -> &'static str {
+    // Black and white contrast equally with a relative luminance of about 0.18.
+    let luminance = Color::from_num(background).map_or(1.0, |color| color.luminance());
+    if luminance > 0.179 { "#000000" } else { "#ffffff" }
+}
+
+/// Formats a `0xRRGGBB` colour as hex string.
+pub(crate) fn hex(color: u32) -> String {
+    Color::from_num(color).unwrap().hex()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_lab(color: u32, expected: [f32; 3]) {
+        let lab = to_lab(color);
+        for (actual, expected) in lab.iter().zip(expected) {
+            assert!((actual - expected).abs() < 0.05, "Lab of {:06x} is {:?}, expected {:?}", color, lab, expected);
+        }
+    }
+
+    #[test]
+    fn lab_matches_known_values() {
+        assert_lab(0xff0000, [53.24, 80.09, 67.20]);
+        assert_lab(0x00ff00, [87.73, -86.18, 83.18]);
+        assert_lab(0x0000ff, [32.30, 79.19, -107.86]);
+        assert_lab(0xffffff, [100.0, 0.0, 0.0]);
+        assert_lab(0x000000, [0.0, 0.0, 0.0]);
+        assert_lab(0x808080, [53.59, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn lab_round_trips() {
+        let colors = [0xff0000, 0x00ff00, 0x0000ff, 0xffffff, 0x000000, 0x010203, 0x7f7f80];
+        colors.iter()
+            .chain(&VIRIDIS_RAMP)
+            .chain(&RED_BLUE_RAMP)
+            .chain(&DEFAULT_COLOR_PALLET)
+            .for_each(|color| assert_eq!(from_lab(to_lab(*color)), *color, "{:06x}", color));
+    }
+
+    #[test]
+    fn interpolation_starts_and_ends_at_the_given_colours() {
+        for interpolation in [Interpolation::Rgb, Interpolation::Lab, Interpolation::Hcl] {
+            assert_eq!(interpolate(0x440154, 0xfde725, 0.0, interpolation), 0x440154);
+            assert_eq!(interpolate(0x440154, 0xfde725, 1.0, interpolation), 0xfde725);
+        }
+    }
+
+    #[test]
+    fn lab_interpolates_lightness_evenly() {
+        assert_eq!(interpolate(0x000000, 0xffffff, 0.5, Interpolation::Rgb), 0x808080);
+        assert_eq!(interpolate(0x000000, 0xffffff, 0.5, Interpolation::Lab), 0x777777);
+    }
+
+    #[test]
+    fn hcl_keeps_the_hue_of_the_coloured_end() {
+        let [_, a, b] = to_lab(interpolate(0xffffff, 0x0000ff, 0.5, Interpolation::Hcl));
+        let [_, blue_a, blue_b] = to_lab(0x0000ff);
+        assert!((b.atan2(a) - blue_b.atan2(blue_a)).abs() < 0.05);
+    }
+
+    #[test]
+    fn sample_spreads_the_stops_evenly() {
+        let stops = [0x000000, 0x646464, 0xc8c8c8];
+        assert_eq!(sample(&stops, 0.5, Interpolation::Rgb), 0x646464);
+        assert_eq!(sample(&stops, 0.75, Interpolation::Rgb), 0x969696);
+        assert_eq!(sample(&stops, 2.0, Interpolation::Rgb), 0xc8c8c8);
+        assert_eq!(sample(&stops[..1], 0.3, Interpolation::Lab), 0x000000);
+    }
+
+    #[test]
+    fn text_contrasts_with_the_background() {
+        assert_eq!(contrasting_text(0x000000), "#ffffff");
+        assert_eq!(contrasting_text(0x440154), "#ffffff");
+        assert_eq!(contrasting_text(0x606060), "#ffffff");
+        assert_eq!(contrasting_text(0x808080), "#000000");
+        assert_eq!(contrasting_text(0xfde725), "#000000");
+        assert_eq!(contrasting_text(0xffffff), "#000000");
+    }
+
+    const PALLETS: [ColorPallet; 4] = [ColorPallet::Default, ColorPallet::OrangeFire, ColorPallet::DarkBlue, ColorPallet::Material];
+
+    #[test]
+    fn generated_colours_start_with_the_pallet_and_never_repeat() {
+        for pallet in PALLETS {
+            let colors = pallet.generate(300);
+            assert_eq!(colors[..16], pallet.colors()[..]);
+            assert_eq!(colors.iter().collect::<HashSet<_>>().len(), 300, "{:?}", pallet);
+        }
+    }
+
+    #[test]
+    fn generated_colours_keep_their_place_when_more_are_generated() {
+        for pallet in PALLETS {
+            assert_eq!(pallet.generate(40), pallet.generate(100)[..40]);
+            assert_eq!(pallet.generate(10), pallet.colors()[..10]);
+            for index in 0..17 {
+                assert_eq!(pallet.color_of(index, 17), pallet.color_of(index, 100));
+            }
+            for index in 0..16 {
+                assert_eq!(pallet.color_of(index, 17), pallet.color(index));
+            }
+        }
+    }
+
+    #[test]
+    fn generated_colours_are_perceptually_distinct() {
+        for pallet in PALLETS {
+            let colors = pallet.generate(100).into_iter().map(to_lab).collect::<Vec<_>>();
+            for index in 16..colors.len() {
+                let closest = colors[..index].iter().map(|other| delta_e(&colors[index], other)).fold(f32::MAX, f32::min);
+                assert!(closest > 15.0, "colour {} of {:?} is {} from another colour", index, pallet, closest);
+                assert!((MIN_LIGHTNESS..=MAX_LIGHTNESS).contains(&colors[index][0]));
+            }
+        }
+    }
+
+    #[test]
+    fn colours_beyond_the_grid_never_repeat() {
+        let count = GRID_LEVELS.pow(3) as usize + 100;
+        let colors = ColorPallet::Default.generate(count);
+        assert_eq!(colors.iter().collect::<HashSet<_>>().len(), count);
+    }
+}
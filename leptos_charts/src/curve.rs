@@ -0,0 +1,201 @@
+use nalgebra::Point2;
+
+/// Interpolation used to connect the points of a line or the edge of an area.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Curve {
+    /// Straight lines between the points.
+    #[default]
+    Linear,
+    /// Horizontal steps changing value halfway between two points.
+    Step,
+    /// Cubic splines that preserve monotonicity in y, no overshoot between points.
+    MonotoneCubic,
+    /// Uniform Catmull-Rom splines passing through every point.
+    CatmullRom,
+}
+
+impl Curve {
+
+    /// Builds path commands through `points`. The path starts with a move when `connect`
+    /// is false, otherwise with a line continuing the current path.
+    pub fn path(&self, points: &[Point2<f32>], connect: bool) -> String {
+        let Some(first) = points.first() else {
+            return String::new()
+        };
+        let mut path = format!("{} {} {} ", if connect { "L" } else { "M" }, first.x, first.y);
+        if points.len() < 2 {
+            return path
+        }
+        match self {
+            Curve::Linear => {
+                points[1..].iter().for_each(|point| {
+                    path.push_str(&format!("L {} {} ", point.x, point.y));
+                });
+            },
+            Curve::Step => {
+                points.windows(2).for_each(|window| {
+                    let middle = (window[0].x + window[1].x) * 0.5;
+                    path.push_str(&format!("L {} {} L {} {} L {} {} ", middle, window[0].y, middle, window[1].y, window[1].x, window[1].y));
+                });
+            },
+            Curve::MonotoneCubic => {
+                let tangents = monotone_tangents(points);
+                points.windows(2).zip(tangents.windows(2)).for_each(|(window, tangent)| {
+                    let dx = (window[1].x - window[0].x) / 3.0;
+                    path.push_str(&format!(
+                        "C {} {} {} {} {} {} ",
+                        window[0].x + dx, window[0].y + dx * tangent[0],
+                        window[1].x - dx, window[1].y - dx * tangent[1],
+                        window[1].x, window[1].y,
+                    ));
+                });
+            },
+            Curve::CatmullRom => {
+                (0..points.len() - 1).for_each(|index| {
+                    let p0 = points[index.saturating_sub(1)];
+                    let p1 = points[index];
+                    let p2 = points[index + 1];
+                    let p3 = points[(index + 2).min(points.len() - 1)];
+                    let c1 = p1 + (p2 - p0) / 6.0;
+                    let c2 = p2 - (p3 - p1) / 6.0;
+                    path.push_str(&format!("C {} {} {} {} {} {} ", c1.x, c1.y, c2.x, c2.y, p2.x, p2.y));
+                });
+            },
+        }
+        path
+    }
+}
+
+/// Computes the tangents of a monotone cubic interpolation after Fritsch and Carlson.
+fn monotone_tangents(points: &[Point2<f32>]) -> Vec<f32> {
+    let secants = points.windows(2)
+        .map(|window| {
+            let dx = window[1].x - window[0].x;
+            if dx == 0.0 { 0.0 } else { (window[1].y - window[0].y) / dx }
+        })
+        .collect::<Vec<_>>();
+
+    let mut tangents = Vec::with_capacity(points.len());
+    tangents.push(secants[0]);
+    secants.windows(2).for_each(|window| {
+        if window[0] * window[1] <= 0.0 {
+            tangents.push(0.0);
+        }
+        else {
+            tangents.push((window[0] + window[1]) * 0.5);
+        }
+    });
+    tangents.push(secants[secants.len() - 1]);
+
+    secants.iter().enumerate().for_each(|(index, secant)| {
+        if *secant == 0.0 {
+            tangents[index] = 0.0;
+            tangents[index + 1] = 0.0;
+        }
+        else {
+            let alpha = tangents[index] / secant;
+            let beta = tangents[index + 1] / secant;
+            let length = alpha.hypot(beta);
+            if length > 3.0 {
+                let tau = 3.0 / length;
+                tangents[index] = tau * alpha * secant;
+                tangents[index + 1] = tau * beta * secant;
+            }
+        }
+    });
+
+    tangents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-4, "{} is not close to {}", actual, expected);
+    }
+
+    fn points(coordinates: &[(f32, f32)]) -> Vec<Point2<f32>> {
+        coordinates.iter().map(|(x, y)| Point2::new(*x, *y)).collect()
+    }
+
+    /// Returns the control and end points of the cubic segments of a path.
+    fn cubic_segments(path: &str) -> Vec<[Point2<f32>; 3]> {
+        path.split('C')
+            .skip(1)
+            .map(|segment| {
+                let numbers = segment.split_whitespace().map(|number| number.parse::<f32>().unwrap()).collect::<Vec<_>>();
+                [
+                    Point2::new(numbers[0], numbers[1]),
+                    Point2::new(numbers[2], numbers[3]),
+                    Point2::new(numbers[4], numbers[5]),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn paths_start_with_a_move_or_continue_with_a_line() {
+        let points = points(&[(0.0, 1.0), (2.0, 3.0)]);
+        assert_eq!(Curve::Linear.path(&points, false), "M 0 1 L 2 3 ");
+        assert_eq!(Curve::Linear.path(&points, true), "L 0 1 L 2 3 ");
+        assert_eq!(Curve::CatmullRom.path(&points[..1], false), "M 0 1 ");
+        assert_eq!(Curve::MonotoneCubic.path(&[], false), "");
+    }
+
+    #[test]
+    fn steps_change_value_halfway() {
+        let points = points(&[(0.0, 1.0), (2.0, 3.0)]);
+        assert_eq!(Curve::Step.path(&points, false), "M 0 1 L 1 1 L 1 3 L 2 3 ");
+    }
+
+    #[test]
+    fn monotone_cubic_does_not_overshoot() {
+        let points = points(&[(0.0, 0.0), (1.0, 0.0), (2.0, 10.0), (3.0, 10.5), (4.0, 0.0)]);
+        let segments = cubic_segments(&Curve::MonotoneCubic.path(&points, false));
+        assert_eq!(segments.len(), 4);
+        points.windows(2).zip(segments).for_each(|(window, [c1, c2, end])| {
+            assert_eq!(end, window[1]);
+            let (min, max) = (window[0].y.min(window[1].y), window[0].y.max(window[1].y));
+            for control in [c1, c2] {
+                assert!(control.y >= min - 1e-4 && control.y <= max + 1e-4, "{:?} overshoots {:?}", control, window);
+            }
+        });
+    }
+
+    #[test]
+    fn monotone_cubic_is_flat_at_extrema() {
+        let points = points(&[(0.0, 0.0), (1.0, 2.0), (2.0, 0.0)]);
+        let segments = cubic_segments(&Curve::MonotoneCubic.path(&points, false));
+        assert_close(segments[0][1].y, 2.0);
+        assert_close(segments[1][0].y, 2.0);
+    }
+
+    #[test]
+    fn monotone_tangents_follow_straight_lines() {
+        let tangents = monotone_tangents(&points(&[(0.0, 1.0), (1.0, 3.0), (3.0, 7.0)]));
+        tangents.iter().for_each(|tangent| assert_close(*tangent, 2.0));
+    }
+
+    #[test]
+    fn catmull_rom_passes_through_every_point() {
+        let points = points(&[(0.0, 0.0), (1.0, 2.0), (2.0, -1.0), (4.0, 3.0)]);
+        let segments = cubic_segments(&Curve::CatmullRom.path(&points, false));
+        assert_eq!(segments.iter().map(|segment| segment[2]).collect::<Vec<_>>(), points[1..]);
+        // The tangent at an inner point is parallel to the chord between its neighbours.
+        let c1 = segments[1][0];
+        assert_close(c1.x, 1.0 + 2.0 / 6.0);
+        assert_close(c1.y, 2.0 - 1.0 / 6.0);
+    }
+
+    #[test]
+    fn catmull_rom_keeps_evenly_spaced_lines_straight() {
+        let points = points(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)]);
+        let segments = cubic_segments(&Curve::CatmullRom.path(&points, false));
+        let [c1, c2, _] = segments[1];
+        assert_close(c1.x, 1.0 + 1.0 / 3.0);
+        assert_close(c1.y, 1.0 + 1.0 / 3.0);
+        assert_close(c2.x, 2.0 - 1.0 / 3.0);
+        assert_close(c2.y, 2.0 - 1.0 / 3.0);
+    }
+}
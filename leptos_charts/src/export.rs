@@ -0,0 +1,445 @@
+use std::time::Duration;
+
+use leptos::{document, set_timeout, spawn_local, store_value, window, Scope, Signal, SignalWithUntracked, StoredValue};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, BlobPropertyBag, CanvasRenderingContext2d, CssRule, CssStyleSheet, Element, HtmlAnchorElement, HtmlCanvasElement, HtmlImageElement, Node, Response, Url, XmlSerializer};
+
+use crate::theme::Theme;
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// Time an object URL is kept after its download started, browsers read it asynchronously.
+const REVOKE_DELAY: Duration = Duration::from_secs(10);
+
+/// Properties copied from the computed style of every element of the chart, so that the export
+/// looks as shown without the style sheets of the page.
+const STYLE_PROPERTIES: &[&str] = &[
+    "display", "visibility", "opacity",
+    "fill", "fill-opacity", "stroke", "stroke-width", "stroke-opacity", "stroke-dasharray",
+    "font-family", "font-size", "font-style", "font-weight", "text-anchor", "dominant-baseline",
+];
+
+/// The element a chart is rendered into and the theme it is styled with.
+#[derive(Clone)]
+struct ExportTarget {
+    chart_box: Element,
+    theme: Signal<Theme>,
+}
+
+/// A handle to export a chart as shown, given to a chart with its `export` prop.
+///
+/// ```ignore
+/// let export = ChartExport::new(cx);
+/// view! { cx,
+///     <BarChart options=options data=data export=export/>
+///     <button on:click=move |_| export.download_png("revenue.png", 2.0)>"Download"</button>
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct ChartExport {
+    target: StoredValue<Option<ExportTarget>>,
+}
+
+impl ChartExport {
+
+    pub fn new(cx: Scope) -> Self {
+        ChartExport { target: store_value(cx, None) }
+    }
+
+    pub(crate) fn register(&self, chart_box: Element, theme: Signal<Theme>) {
+        self.target.update_value(|target| *target = Some(ExportTarget { chart_box, theme }));
+    }
+
+    /// Returns standalone SVG markup of the chart and its legend, `None` before it is rendered.
+    /// Styles are inlined and fonts declared by the page with `@font-face` are referenced by
+    /// their URLs, the downloads embed the fonts themselves.
+    pub fn to_svg_string(&self) -> Option<String> {
+        let font_faces = self.font_faces()?;
+        self.render_svg(&font_faces)
+    }
+
+    /// Downloads the chart as SVG file.
+    pub fn download_svg(&self, file_name: &str) {
+        let file_name = String::from(file_name);
+        self.with_embedded_fonts(move |svg| {
+            if let Some(url) = svg_url(&svg) {
+                download(&url, &file_name);
+                revoke_later(url);
+            }
+        });
+    }
+
+    /// Downloads the chart as PNG file, `scale` times the size it is shown in.
+    pub fn download_png(&self, file_name: &str, scale: f32) {
+        let file_name = String::from(file_name);
+        self.with_embedded_fonts(move |svg| {
+            let Some(url) = svg_url(&svg) else {
+                return
+            };
+            let Ok(image) = HtmlImageElement::new() else {
+                return
+            };
+            let onload = {
+                let image = Clone::clone(&image);
+                let url = Clone::clone(&url);
+                Closure::once_into_js(move || {
+                    let _ = Url::revoke_object_url(&url);
+                    rasterize(&image, scale, file_name);
+                })
+            };
+            image.set_onload(Some(onload.unchecked_ref()));
+            image.set_src(&url);
+        });
+    }
+
+    /// Calls `export` with the SVG markup of the chart once the fonts it uses are fetched and
+    /// embedded as data URLs.
+    fn with_embedded_fonts(&self, export: impl FnOnce(String) + 'static) {
+        let Some(font_faces) = self.font_faces() else {
+            return
+        };
+        let this = *self;
+        spawn_local(async move {
+            let font_faces = embed_urls(font_faces).await;
+            if let Some(svg) = this.render_svg(&font_faces) {
+                export(svg);
+            }
+        });
+    }
+
+    /// Returns the `@font-face` rules of the page declaring fonts of the theme, with their URLs
+    /// resolved against their style sheets.
+    fn font_faces(&self) -> Option<String> {
+        let font_family = self.target.with_value(|target| {
+            target.as_ref().map(|target| target.theme.with_untracked(|theme| Clone::clone(&theme.font_family)))
+        })?;
+        let families = font_families(&font_family);
+        let sheets = document().style_sheets();
+        let mut rules = Vec::new();
+        for index in 0..sheets.length() {
+            let Some(sheet) = sheets.item(index).and_then(|sheet| sheet.dyn_into::<CssStyleSheet>().ok()) else {
+                continue
+            };
+            // Rules of style sheets from other origins cannot be read.
+            let Ok(sheet_rules) = sheet.css_rules() else {
+                continue
+            };
+            let base = sheet.href().ok()
+                .flatten()
+                .or_else(|| document().base_uri().ok().flatten())
+                .unwrap_or_default();
+            for rule_index in 0..sheet_rules.length() {
+                let Some(rule) = sheet_rules.item(rule_index).filter(|rule| rule.type_() == CssRule::FONT_FACE_RULE) else {
+                    continue
+                };
+                let css = rule.css_text();
+                let declared = font_face_family(&css)
+                    .map_or(false, |family| families.iter().any(|other| other.eq_ignore_ascii_case(family)));
+                if declared {
+                    rules.push(replace_urls(&css, |url| {
+                        Url::new_with_base(url, &base).map_or_else(|_| String::from(url), |url| url.href())
+                    }));
+                }
+            }
+        }
+        Some(rules.join("\n"))
+    }
+
+    /// Copies the chart and its legend into a standalone svg element, placed as they are shown.
+    fn render_svg(&self, font_faces: &str) -> Option<String> {
+        self.target.with_value(|target| {
+            let target = target.as_ref()?;
+            let chart = target.chart_box.query_selector("svg").ok()??;
+            let legend = target.chart_box.parent_element()
+                .and_then(|container| container.query_selector(":scope > ul").ok().flatten());
+
+            let chart_rect = chart.get_bounding_client_rect();
+            let (left, top, right, bottom) = legend.iter()
+                .map(|legend| legend.get_bounding_client_rect())
+                .fold(
+                    (chart_rect.left(), chart_rect.top(), chart_rect.right(), chart_rect.bottom()),
+                    |(left, top, right, bottom), rect| {
+                        (left.min(rect.left()), top.min(rect.top()), right.max(rect.right()), bottom.max(rect.bottom()))
+                    }
+                );
+
+            let svg = svg_element("svg")?;
+            svg.set_attribute("xmlns", SVG_NAMESPACE).ok()?;
+            svg.set_attribute("width", &(right - left).round().to_string()).ok()?;
+            svg.set_attribute("height", &(bottom - top).round().to_string()).ok()?;
+            if !font_faces.is_empty() {
+                let style = svg_element("style")?;
+                style.set_text_content(Some(font_faces));
+                svg.append_child(&style).ok()?;
+            }
+            let background = svg_element("rect")?;
+            background.set_attribute("width", "100%").ok()?;
+            background.set_attribute("height", "100%").ok()?;
+            target.theme.with_untracked(|theme| background.set_attribute("fill", &theme.background)).ok()?;
+            svg.append_child(&background).ok()?;
+
+            let copy = chart.clone_node_with_deep(true).ok()?.dyn_into::<Element>().ok()?;
+            inline_styles(&chart, &copy);
+            copy.set_attribute("x", &(chart_rect.left() - left).to_string()).ok()?;
+            copy.set_attribute("y", &(chart_rect.top() - top).to_string()).ok()?;
+            copy.set_attribute("width", &chart.client_width().to_string()).ok()?;
+            copy.set_attribute("height", &chart.client_height().to_string()).ok()?;
+            copy.remove_attribute("tabindex").ok()?;
+            svg.append_child(&copy).ok()?;
+
+            if let Some(legend) = legend {
+                svg.append_child(&legend_to_svg(&legend, (left, top))?).ok()?;
+            }
+            XmlSerializer::new().ok()?.serialize_to_string(&svg).ok()
+        })
+    }
+}
+
+fn svg_element(name: &str) -> Option<Element> {
+    document().create_element_ns(Some(SVG_NAMESPACE), name).ok()
+}
+
+/// Copies the computed style of `source` and its descendants onto `copy`, a deep clone of it.
+fn inline_styles(source: &Element, copy: &Element) {
+    inline_style(source, copy);
+    if let (Ok(sources), Ok(copies)) = (source.query_selector_all("*"), copy.query_selector_all("*")) {
+        for index in 0..sources.length().min(copies.length()) {
+            if let (Some(source), Some(copy)) = (sources.item(index), copies.item(index)) {
+                inline_style(source.unchecked_ref(), copy.unchecked_ref());
+            }
+        }
+    }
+}
+
+fn inline_style(source: &Element, copy: &Element) {
+    let Some(style) = window().get_computed_style(source).ok().flatten() else {
+        return
+    };
+    let declarations = STYLE_PROPERTIES.iter()
+        .filter_map(|property| {
+            style.get_property_value(property).ok()
+                .filter(|value| !value.is_empty())
+                .map(|value| format!("{}: {};", property, value))
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let _ = copy.set_attribute("style", &declarations);
+}
+
+/// Draws the entries of the HTML legend as swatches and labels at the positions they are shown
+/// at, relative to `origin`.
+fn legend_to_svg(legend: &Element, origin: (f64, f64)) -> Option<Element> {
+    let group = svg_element("g")?;
+    let entries = legend.query_selector_all("li").ok()?;
+    for index in 0..entries.length() {
+        let Some(entry) = entries.item(index).map(|entry| entry.unchecked_into::<Element>()) else {
+            continue
+        };
+        let Some(style) = window().get_computed_style(&entry).ok().flatten() else {
+            continue
+        };
+        let item = svg_element("g")?;
+        item.set_attribute("opacity", &style.get_property_value("opacity").ok()?).ok()?;
+
+        if let Some(swatch) = entry.query_selector("span").ok().flatten() {
+            let rect = swatch.get_bounding_client_rect();
+            let color = window().get_computed_style(&swatch).ok()
+                .flatten()
+                .and_then(|style| style.get_property_value("background-color").ok())
+                .unwrap_or_default();
+            let shape = svg_element("rect")?;
+            shape.set_attribute("x", &(rect.left() - origin.0).to_string()).ok()?;
+            shape.set_attribute("y", &(rect.top() - origin.1).to_string()).ok()?;
+            shape.set_attribute("width", &rect.width().to_string()).ok()?;
+            shape.set_attribute("height", &rect.height().to_string()).ok()?;
+            shape.set_attribute("fill", &color).ok()?;
+            item.append_child(&shape).ok()?;
+        }
+
+        // The label is the text node of the entry or of its button, measured with a range as it
+        // has no element.
+        let nodes = entry.query_selector("button").ok()
+            .flatten()
+            .map_or_else(|| entry.child_nodes(), |button| button.child_nodes());
+        let label = (0..nodes.length())
+            .filter_map(|index| nodes.item(index))
+            .find(|node| node.node_type() == Node::TEXT_NODE && node.text_content().map_or(false, |text| !text.trim().is_empty()));
+        if let Some(label) = label {
+            let range = document().create_range().ok()?;
+            range.select_node_contents(&label).ok()?;
+            let rect = range.get_bounding_client_rect();
+            let text = svg_element("text")?;
+            text.set_attribute("x", &(rect.left() - origin.0).to_string()).ok()?;
+            text.set_attribute("y", &(rect.top() + rect.height() / 2.0 - origin.1).to_string()).ok()?;
+            text.set_attribute("dominant-baseline", "central").ok()?;
+            text.set_attribute("style", &format!(
+                "fill: {}; font-family: {}; font-size: {};",
+                style.get_property_value("color").ok()?,
+                style.get_property_value("font-family").ok()?,
+                style.get_property_value("font-size").ok()?
+            )).ok()?;
+            text.set_text_content(label.text_content().as_deref());
+            item.append_child(&text).ok()?;
+        }
+        group.append_child(&item).ok()?;
+    }
+    Some(group)
+}
+
+/// Replaces the URLs in `css` by data URLs of their content, keeping those that cannot be fetched.
+async fn embed_urls(css: String) -> String {
+    let urls = css_urls(&css).into_iter()
+        .filter(|url| !url.starts_with("data:"))
+        .map(String::from)
+        .collect::<Vec<_>>();
+    let mut embedded: Vec<(String, String)> = Vec::new();
+    for url in urls {
+        if embedded.iter().all(|(other, _)| other != &url) {
+            if let Some(data) = data_url(&url).await {
+                embedded.push((url, data));
+            }
+        }
+    }
+    replace_urls(&css, |url| {
+        embedded.iter()
+            .find(|(other, _)| other == url)
+            .map_or_else(|| String::from(url), |(_, data)| Clone::clone(data))
+    })
+}
+
+async fn data_url(url: &str) -> Option<String> {
+    let response = JsFuture::from(window().fetch_with_str(url)).await.ok()?.dyn_into::<Response>().ok()?;
+    if !response.ok() {
+        return None
+    }
+    let blob = JsFuture::from(response.blob().ok()?).await.ok()?.dyn_into::<Blob>().ok()?;
+    let buffer = JsFuture::from(blob.array_buffer()).await.ok()?;
+    let binary = js_sys::Uint8Array::new(&buffer).to_vec().into_iter().map(char::from).collect::<String>();
+    let base64 = window().btoa(&binary).ok()?;
+    Some(format!("data:{};base64,{}", blob.type_(), base64))
+}
+
+/// Names of the fonts in a `font-family` list, without quotes.
+fn font_families(font_family: &str) -> Vec<&str> {
+    font_family.split(',')
+        .map(|family| family.trim().trim_matches(|c| c == '"' || c == '\''))
+        .filter(|family| !family.is_empty())
+        .collect()
+}
+
+/// Name of the font declared by a `@font-face` rule.
+fn font_face_family(css: &str) -> Option<&str> {
+    let start = css.find("font-family:")? + "font-family:".len();
+    let value = &css[start..];
+    let end = value.find(|c| c == ';' || c == '}').unwrap_or(value.len());
+    Some(value[..end].trim().trim_matches(|c| c == '"' || c == '\''))
+}
+
+/// Finds the first `url(...)` in `css` and returns where it starts, the URL without quotes and
+/// where it ends.
+fn next_url(css: &str) -> Option<(usize, &str, usize)> {
+    let start = css.find("url(")?;
+    let end = start + css[start..].find(')')? + 1;
+    let url = css[start + 4..end - 1].trim().trim_matches(|c| c == '"' || c == '\'');
+    Some((start, url, end))
+}
+
+fn css_urls(css: &str) -> Vec<&str> {
+    let mut urls = Vec::new();
+    let mut rest = css;
+    while let Some((_, url, end)) = next_url(rest) {
+        urls.push(url);
+        rest = &rest[end..];
+    }
+    urls
+}
+
+/// Replaces every `url(...)` in `css` by one of the URL returned by `replace`.
+fn replace_urls(css: &str, replace: impl Fn(&str) -> String) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some((start, url, end)) = next_url(rest) {
+        result.push_str(&rest[..start]);
+        result.push_str(&format!("url(\"{}\")", replace(url)));
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Releases an object URL once the download using it has had time to read it.
+fn revoke_later(url: String) {
+    set_timeout(move || {
+        let _ = Url::revoke_object_url(&url);
+    }, REVOKE_DELAY);
+}
+
+fn svg_url(svg: &str) -> Option<String> {
+    let parts = js_sys::Array::of1(&JsValue::from_str(svg));
+    let blob = Blob::new_with_str_sequence_and_options(&parts, BlobPropertyBag::new().type_("image/svg+xml")).ok()?;
+    Url::create_object_url_with_blob(&blob).ok()
+}
+
+fn download(url: &str, file_name: &str) {
+    if let Some(anchor) = document().create_element("a").ok().and_then(|anchor| anchor.dyn_into::<HtmlAnchorElement>().ok()) {
+        anchor.set_href(url);
+        anchor.set_download(file_name);
+        anchor.click();
+    }
+}
+
+/// Draws the loaded SVG image onto a canvas and downloads the canvas as PNG.
+fn rasterize(image: &HtmlImageElement, scale: f32, file_name: String) {
+    let Some(canvas) = document().create_element("canvas").ok().and_then(|canvas| canvas.dyn_into::<HtmlCanvasElement>().ok()) else {
+        return
+    };
+    canvas.set_width((image.natural_width() as f32 * scale).round() as u32);
+    canvas.set_height((image.natural_height() as f32 * scale).round() as u32);
+    let context = canvas.get_context("2d").ok()
+        .flatten()
+        .and_then(|context| context.dyn_into::<CanvasRenderingContext2d>().ok());
+    let Some(context) = context else {
+        return
+    };
+    let _ = context.scale(scale as f64, scale as f64);
+    if context.draw_image_with_html_image_element(image, 0.0, 0.0).is_err() {
+        return
+    }
+    let callback = Closure::once_into_js(move |blob: Option<Blob>| {
+        if let Some(url) = blob.and_then(|blob| Url::create_object_url_with_blob(&blob).ok()) {
+            download(&url, &file_name);
+            revoke_later(url);
+        }
+    });
+    let _ = canvas.to_blob_with_type(callback.unchecked_ref(), "image/png");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls_are_found_with_and_without_quotes() {
+        let css = "@font-face { src: url(\"/a.woff2\") format(\"woff2\"), url('b.woff'), url( c.ttf ); }";
+        assert_eq!(css_urls(css), vec!["/a.woff2", "b.woff", "c.ttf"]);
+        assert_eq!(css_urls("@font-face { src: local(Inter); }"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn urls_are_replaced_and_the_rest_is_kept() {
+        let css = "@font-face { font-family: Inter; src: url(/a.woff2) format(\"woff2\"), url('b.woff'); }";
+        assert_eq!(
+            replace_urls(css, |url| format!("https://example.com/{}", url.trim_start_matches('/'))),
+            "@font-face { font-family: Inter; src: url(\"https://example.com/a.woff2\") format(\"woff2\"), url(\"https://example.com/b.woff\"); }"
+        );
+    }
+
+    #[test]
+    fn font_families_are_read_without_quotes() {
+        assert_eq!(font_families("\"Open Sans\", 'Inter', sans-serif"), vec!["Open Sans", "Inter", "sans-serif"]);
+        assert_eq!(font_face_family("@font-face { font-family: \"Open Sans\"; src: url(a.woff2); }"), Some("Open Sans"));
+        assert_eq!(font_face_family("@font-face { src: url(a.woff2); }"), None);
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::BTreeSet;
+
+use leptos::{Children, component, create_node_ref, IntoView, Memo, ReadSignal, RwSignal, Scope, Signal, SignalGet, SignalWith, view};
+use leptos::ev::{KeyboardEvent, MouseEvent};
+use leptos::html::Div;
+
+use crate::{ChartOptions, Extent2};
+use crate::a11y::{Announcer, DataTable, HiddenTable};
+use crate::export::ChartExport;
+use crate::interaction::Interaction;
+use crate::legend::{Legend, LegendItem, LegendPosition};
+use crate::responsive::observe_size;
+use crate::theme::use_theme;
+use crate::tooltip::{HitTest, Tooltip, TooltipRenderer};
+
+/// Wraps the marks of a chart in its svg element and places the legend if the options ask for
+/// one. The svg carries the accessible name and description and handles pointer and keyboard
+/// through `interaction`, the tooltip, the live region and the data table are positioned relative
+/// to it. Responsive charts fill their container and receive the space left next to the legend
+/// through `size`.
+#[component]
+pub(crate) fn ChartFrame(
+    cx: Scope,
+    options: ReadSignal<ChartOptions>,
+    #[prop(into)]
+    legend: Signal<Vec<LegendItem>>,
+    hidden: RwSignal<BTreeSet<usize>>,
+    size: RwSignal<Option<Extent2<f32>>>,
+    export: Option<ChartExport>,
+    interaction: Interaction,
+    /// Label and description of the chart as returned by [`describe_chart`](crate::a11y::describe_chart).
+    description: Memo<(String, String)>,
+    table: Memo<DataTable>,
+    tooltip: Option<TooltipRenderer>,
+    hit_test: HitTest,
+    /// Coordinates of the svg if the marks are not placed in pixels from its top left corner.
+    #[prop(optional)]
+    view_box: Option<Signal<String>>,
+    children: Children,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+    let position = move || options.with(|options| options.legend.position);
+    let responsive = move || options.with(|options| options.responsive.is_some());
+
+    let chart_box = create_node_ref::<Div>(cx);
+    observe_size(cx, chart_box, size);
+    if let Some(export) = export {
+        chart_box.on_load(cx, move |element| export.register(Clone::clone(&*element).into(), theme));
+    }
+
+    let style = move || {
+        let direction = match position() {
+            Some(LegendPosition::Top) => "column-reverse",
+            Some(LegendPosition::Bottom) | None => "column",
+            Some(LegendPosition::Left) => "row-reverse",
+            Some(LegendPosition::Right) => "row",
+        };
+        if responsive() {
+            format!("display: flex; flex-direction: {}; align-items: stretch; width: 100%; height: 100%;", direction)
+        }
+        else {
+            format!("display: inline-flex; flex-direction: {}; align-items: stretch;", direction)
+        }
+    };
+
+    let box_style = move || {
+        if responsive() {
+            "position: relative; flex: 1 1 auto; min-width: 0; min-height: 0;"
+        }
+        else {
+            "position: relative; display: inline-block;"
+        }
+    };
+
+    let legend_view = move || {
+        position().map(|position| view! { cx,
+            <Legend items=legend position=position hidden=hidden/>
+        })
+    };
+
+    let label = move || description.with(|(label, _)| Clone::clone(label));
+
+    view! { cx,
+        <div style=style>
+            <div style=box_style node_ref=chart_box>
+                <svg
+                    width=move || options.with(|options| options.extent.width)
+                    height=move || options.with(|options| options.extent.height)
+                    viewBox=move || view_box.map(|view_box| view_box.get())
+                    style=move || theme.with(|theme| format!("display: block; background: {};", theme.background))
+                    tabindex="0"
+                    role="graphics-document"
+                    aria-label=label
+                    on:mousemove=move |event: MouseEvent| interaction.hover.on_pointer_move(&event, interaction.targets, &hit_test)
+                    on:mouseleave=move |_| interaction.hover.on_pointer_leave()
+                    on:click=move |_| interaction.click()
+                    on:keydown=move |event: KeyboardEvent| interaction.on_key_down(&event)
+                    on:blur=move |_| interaction.hover.on_pointer_leave()
+                >
+                    <title>{label}</title>
+                    <desc>{move || description.with(|(_, description)| Clone::clone(description))}</desc>
+                    {children(cx)}
+                </svg>
+                <Tooltip hover=interaction.hover targets=interaction.targets content=tooltip/>
+                <Announcer interaction=interaction/>
+                <HiddenTable table=table visible=Signal::derive(cx, move || options.with(|options| options.accessibility.data_table))/>
+            </div>
+            {legend_view}
+        </div>
+    }
+}
@@ -0,0 +1,211 @@
+use std::rc::Rc;
+
+use leptos::{component, create_effect, create_rw_signal, IntoView, RwSignal, Scope, Signal, SignalGet, SignalGetUntracked, SignalSet, SignalWith, StoredValue, store_value, view};
+use leptos::ev::KeyboardEvent;
+
+use crate::theme::use_theme;
+use crate::tooltip::{Datum, Hover, Targets};
+
+const SELECTION_RING_RADIUS: f32 = 6.0;
+
+/// Identifies a mark of a chart by the series and the index of its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Selection {
+    pub series: usize,
+    pub index: usize,
+}
+
+impl Selection {
+    pub fn new(series: usize, index: usize) -> Self {
+        Selection { series, index }
+    }
+}
+
+impl From<&Datum> for Selection {
+    fn from(datum: &Datum) -> Self {
+        Selection::new(datum.series, datum.index)
+    }
+}
+
+/// A function called with the datum of the mark a user interacted with.
+#[derive(Clone)]
+pub struct ChartCallback(Rc<dyn Fn(Datum)>);
+
+impl ChartCallback {
+    pub fn call(&self, datum: Datum) {
+        (self.0)(datum)
+    }
+}
+
+impl<F> From<F> for ChartCallback
+where F: Fn(Datum) + 'static {
+    fn from(function: F) -> Self {
+        ChartCallback(Rc::new(function))
+    }
+}
+
+/// A function called with the datum of the newly selected mark, `None` when the selection is cleared.
+#[derive(Clone)]
+pub struct SelectCallback(Rc<dyn Fn(Option<Datum>)>);
+
+impl SelectCallback {
+    pub fn call(&self, datum: Option<Datum>) {
+        (self.0)(datum)
+    }
+}
+
+impl<F> From<F> for SelectCallback
+where F: Fn(Option<Datum>) + 'static {
+    fn from(function: F) -> Self {
+        SelectCallback(Rc::new(function))
+    }
+}
+
+/// The callbacks a chart was given by the application.
+#[derive(Clone, Default)]
+pub(crate) struct ChartEvents {
+    /// Called when a mark becomes selected or the selection is cleared, by click or by `Enter` on
+    /// the focused mark.
+    pub on_select: Option<SelectCallback>,
+    /// Called when the pointer or the keyboard focus moves onto a mark.
+    pub on_hover: Option<ChartCallback>,
+    /// Called on every click on a mark.
+    pub on_click: Option<ChartCallback>,
+}
+
+/// Hover, selection and callbacks of a chart, shared by its event listeners and marks.
+#[derive(Clone, Copy)]
+pub(crate) struct Interaction {
+    pub hover: Hover,
+    pub selected: RwSignal<Option<Selection>>,
+    pub targets: Targets,
+    events: StoredValue<ChartEvents>,
+}
+
+impl Interaction {
+
+    /// Uses `selected` as the selection if given, so the application can drive and observe it.
+    pub fn new(cx: Scope, targets: impl Into<Targets>, events: ChartEvents, selected: Option<RwSignal<Option<Selection>>>) -> Self {
+        let targets = targets.into();
+        let hover = Hover::new(cx);
+        let selected = selected.unwrap_or_else(|| create_rw_signal(cx, None));
+        let events = store_value(cx, events);
+
+        create_effect(cx, move |_| {
+            let datum = hover.active.get().and_then(|index| cx.untrack(|| targets.datum(index)));
+            if let Some(datum) = datum {
+                events.with_value(|events| {
+                    if let Some(on_hover) = &events.on_hover {
+                        on_hover.call(datum);
+                    }
+                });
+            }
+        });
+
+        Interaction { hover, selected, targets, events }
+    }
+
+    /// Handles a click on the chart, toggling the selection of the mark under the pointer.
+    pub fn click(&self) {
+        let Some(datum) = self.active_datum_untracked() else {
+            return
+        };
+        self.events.with_value(|events| {
+            if let Some(on_click) = &events.on_click {
+                on_click.call(Clone::clone(&datum));
+            }
+        });
+        self.toggle_selection(datum);
+    }
+
+    /// Toggles the selection of the focused mark with `Enter` or `Space`, other keys move the
+    /// focus. Keyboard activation is not a click, `on_click` is left to the pointer.
+    pub fn on_key_down(&self, event: &KeyboardEvent) {
+        match event.key().as_str() {
+            "Enter" | " " => {
+                event.prevent_default();
+                if let Some(datum) = self.active_datum_untracked() {
+                    self.toggle_selection(datum);
+                }
+            },
+            _ => self.hover.on_key_down(event, self.targets.len()),
+        }
+    }
+
+    fn active_datum_untracked(&self) -> Option<Datum> {
+        self.hover.active.get_untracked().and_then(|index| self.targets.datum(index))
+    }
+
+    /// Selects the mark of `datum` or clears the selection if it is already selected.
+    fn toggle_selection(&self, datum: Datum) {
+        let selection = Selection::from(&datum);
+        let selected = if self.selected.get_untracked() == Some(selection) { None } else { Some(datum) };
+        self.selected.set(selected.as_ref().map(Selection::from));
+        self.events.with_value(|events| {
+            if let Some(on_select) = &events.on_select {
+                on_select.call(selected);
+            }
+        });
+    }
+
+    pub fn is_selected(&self, series: usize, index: usize) -> bool {
+        self.selected.get() == Some(Selection::new(series, index))
+    }
+
+    /// Returns the datum of the mark under the pointer or the keyboard focus.
+    pub fn active_datum(&self) -> Option<Datum> {
+        self.hover.active.get().and_then(|active| self.targets.datum(active))
+    }
+
+    /// Whether the mark is under the pointer or the keyboard focus.
+    pub fn is_active(&self, series: usize, index: usize) -> bool {
+        self.active_datum().map_or(false, |datum| Selection::from(&datum) == Selection::new(series, index))
+    }
+}
+
+/// Whether a mark is selected or active, i.e. has the keyboard focus or is under the pointer.
+/// Marks are outlined while highlighted, with a dashed line while they are only active.
+#[derive(Clone, Copy)]
+pub(crate) struct Highlight {
+    pub selected: Signal<bool>,
+    pub active: Signal<bool>,
+}
+
+impl Highlight {
+
+    /// Follows the series and index of the mark returned by `mark`, `None` while it has no data.
+    pub fn new(cx: Scope, interaction: Interaction, mark: impl Fn() -> Option<(usize, usize)> + Copy + 'static) -> Self {
+        Highlight {
+            selected: Signal::derive(cx, move || mark().map_or(false, |(series, index)| interaction.is_selected(series, index))),
+            active: Signal::derive(cx, move || mark().map_or(false, |(series, index)| interaction.is_active(series, index))),
+        }
+    }
+
+    pub fn is_highlighted(&self) -> bool {
+        self.selected.get() || self.active.get()
+    }
+
+    /// Returns the dash pattern of the outline.
+    pub fn dash_array(&self) -> &'static str {
+        if self.active.get() && !self.selected.get() { "4 2" } else { "none" }
+    }
+}
+
+/// A ring around the selected mark, for charts whose marks are too small to highlight themselves.
+#[component]
+pub(crate) fn SelectionRing(
+    cx: Scope,
+    interaction: Interaction,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+
+    move || {
+        interaction.selected.get()
+            .and_then(|selection| interaction.targets.position_of(selection))
+            .and_then(|index| interaction.targets.anchor(index))
+            .map(|anchor| view! { cx,
+                <circle cx=anchor.x cy=anchor.y r=SELECTION_RING_RADIUS fill="none" stroke=theme.with(|theme| Clone::clone(&theme.highlight)) stroke-width="2" pointer-events="none"/>
+            })
+    }
+}
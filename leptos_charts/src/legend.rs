@@ -0,0 +1,118 @@
+use std::collections::BTreeSet;
+
+use leptos::{component, IntoView, RwSignal, Scope, Signal, SignalUpdate, SignalWith, view};
+
+use crate::theme::use_theme;
+
+/// Resets the look of the buttons of toggling entries to that of the label.
+const BUTTON_STYLE: &str = "display: flex; align-items: center; margin: 0; padding: 0; border: none; \
+    background: none; color: inherit; font: inherit; cursor: pointer;";
+
+/// Side of the chart a legend is placed on.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LegendPosition {
+    Top,
+    Right,
+    #[default]
+    Bottom,
+    Left,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegendItem {
+    pub label: String,
+    pub color: String,
+}
+
+impl LegendItem {
+    pub fn new(label: impl Into<String>, color: impl Into<String>) -> Self {
+        LegendItem { label: label.into(), color: color.into() }
+    }
+}
+
+/// A list of coloured labels wrapping into further rows or columns when it runs out of space.
+/// Clicking an entry or pressing Enter or Space on it toggles its index in `hidden`.
+#[component]
+pub fn Legend(
+    cx: Scope,
+    #[prop(into)]
+    items: Signal<Vec<LegendItem>>,
+    #[prop(optional)]
+    position: LegendPosition,
+    /// Indices of the entries whose series are hidden, the entries are not clickable without it.
+    #[prop(optional)]
+    hidden: Option<RwSignal<BTreeSet<usize>>>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+
+    let direction = match position {
+        LegendPosition::Top | LegendPosition::Bottom => "row",
+        LegendPosition::Left | LegendPosition::Right => "column",
+    };
+    let style = move || theme.with(|theme| format!(
+        "display: flex; flex-direction: {}; flex-wrap: wrap; gap: 4px 12px; margin: 0; padding: 4px; \
+        list-style: none; color: {}; font-family: {}; font-size: {}px;",
+        direction, theme.text, theme.font_family, theme.font_size * 1.2
+    ));
+
+    let entries = move || {
+        items.with(|items| {
+            items.iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    let is_hidden = move || hidden.map_or(false, |hidden| hidden.with(|hidden| hidden.contains(&index)));
+                    let toggle = move |_| {
+                        if let Some(hidden) = hidden {
+                            hidden.update(|hidden| {
+                                if !hidden.remove(&index) {
+                                    hidden.insert(index);
+                                }
+                            });
+                        }
+                    };
+                    let entry_style = move || format!(
+                        "display: flex; align-items: center; opacity: {};",
+                        if is_hidden() { 0.4 } else { 1.0 }
+                    );
+                    let swatch = format!(
+                        "display: inline-block; width: 10px; height: 10px; margin-right: 4px; background: {};",
+                        item.color
+                    );
+                    let label = Clone::clone(&item.label);
+                    // Toggling entries are buttons, reachable and operable with the keyboard.
+                    let entry = if hidden.is_some() {
+                        view! { cx,
+                            <button
+                                type="button"
+                                style=BUTTON_STYLE
+                                aria-pressed=move || if is_hidden() { "false" } else { "true" }
+                                on:click=toggle
+                            >
+                                <span style=swatch></span>
+                                {label}
+                            </button>
+                        }.into_view(cx)
+                    }
+                    else {
+                        view! { cx,
+                            <>
+                                <span style=swatch></span>
+                                {label}
+                            </>
+                        }.into_view(cx)
+                    };
+                    view! { cx,
+                        <li style=entry_style>{entry}</li>
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    view! { cx,
+        <ul style=style>
+            {entries}
+        </ul>
+    }
+}
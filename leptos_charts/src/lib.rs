@@ -1,7 +1,51 @@
+mod a11y;
+mod annotation;
+mod axis;
 mod charts;
+mod color;
+mod curve;
+mod export;
+mod frame;
+mod interaction;
+mod legend;
+mod pointer;
+mod responsive;
+pub mod scale;
+mod spatial;
+mod stats;
+#[cfg(feature = "ssr")]
+mod ssr;
+mod theme;
+mod tooltip;
+mod transition;
 
 use std::fmt::Debug;
+pub use a11y::AccessibilityOptions;
+pub use annotation::{Annotation, AnnotationAxis, Annotations};
+pub use axis::{nice_range, nice_step, ticks};
 pub use charts::{BarChart, BarChartProps, BarChartPropsBuilder, BarChartData};
+pub use charts::{AreaChart, AreaChartProps, AreaChartPropsBuilder, LineChart, LineChartProps, LineChartPropsBuilder, LineChartData};
+pub use charts::{PieChart, PieChartProps, PieChartPropsBuilder, PieChartData, PieChartItem};
+pub use charts::{BubbleChart, BubbleChartProps, BubbleChartPropsBuilder, ScatterChart, ScatterChartProps, ScatterChartPropsBuilder, ScatterChartData, ScatterPoint, ScatterSeries};
+pub use charts::{Heatmap, HeatmapProps, HeatmapPropsBuilder, HeatmapData};
+pub use charts::{BoxPlot, BoxPlotProps, BoxPlotPropsBuilder, DistributionData, ViolinPlot, ViolinPlotProps, ViolinPlotPropsBuilder};
+pub use charts::{Histogram, HistogramProps, HistogramPropsBuilder, HistogramData};
+pub use charts::{RadarChart, RadarChartProps, RadarChartPropsBuilder, RadarChartData};
+pub use charts::{Candle, CandlestickChart, CandlestickChartProps, CandlestickChartPropsBuilder, CandlestickData};
+pub use color::{ColorPallet, ColorRamp, ColorScale, Interpolation};
+pub use curve::Curve;
+pub use export::ChartExport;
+pub use interaction::{ChartCallback, SelectCallback, Selection};
+pub use legend::{Legend, LegendItem, LegendPosition, LegendProps, LegendPropsBuilder};
+pub use responsive::ResponsiveOptions;
+#[cfg(feature = "ssr")]
+pub use ssr::{render_to_svg_string, ChartData, ChartKind, RenderError};
+#[cfg(feature = "resvg")]
+pub use ssr::render_to_png;
+pub use stats::{Bin, Binning, Whiskers};
+pub use theme::{provide_theme, use_theme, Theme};
+pub use tooltip::{Datum, TooltipRenderer};
+pub use transition::{Easing, TransitionOptions};
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct Extent2<A>
@@ -20,4 +64,278 @@ where A: PartialEq + PartialOrd + Clone + Debug {
 #[derive(Debug, Clone, PartialEq)]
 pub struct ChartOptions {
     pub extent: Extent2<f32>,
+    pub orientation: Orientation,
+    /// Pallet of the series and categories, `None` uses the categorical pallet of the theme.
+    pub color_pallet: Option<ColorPallet>,
+    pub bar: BarOptions,
+    pub pie: PieOptions,
+    pub line: LineOptions,
+    pub scatter: ScatterOptions,
+    pub heatmap: HeatmapOptions,
+    pub histogram: HistogramOptions,
+    pub distribution: DistributionOptions,
+    pub candlestick: CandlestickOptions,
+    pub radar: RadarOptions,
+    pub legend: LegendOptions,
+    pub transition: TransitionOptions,
+    /// Sizes the chart to its container, `extent` then only gives the size before it is measured.
+    pub responsive: Option<ResponsiveOptions>,
+    pub accessibility: AccessibilityOptions,
+    pub annotations: Annotations,
+}
+
+impl Default for ChartOptions {
+    fn default() -> Self {
+        ChartOptions {
+            extent: Extent2::new(500.0, 400.0),
+            orientation: Orientation::default(),
+            color_pallet: None,
+            bar: BarOptions::default(),
+            pie: PieOptions::default(),
+            line: LineOptions::default(),
+            scatter: ScatterOptions::default(),
+            heatmap: HeatmapOptions::default(),
+            histogram: HistogramOptions::default(),
+            distribution: DistributionOptions::default(),
+            candlestick: CandlestickOptions::default(),
+            radar: RadarOptions::default(),
+            legend: LegendOptions::default(),
+            transition: TransitionOptions::default(),
+            responsive: None,
+            accessibility: AccessibilityOptions::default(),
+            annotations: Annotations::default(),
+        }
+    }
+}
+
+/// Direction the categories of a chart are laid out in.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Orientation {
+    /// Categories along the x-axis, values growing upwards.
+    #[default]
+    Vertical,
+    /// Categories along the y-axis from top to bottom, values growing to the right.
+    Horizontal,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BarOptions {
+    pub layout: BarLayout,
+    /// Colours every bar by its value along the ramp instead of by its series.
+    pub value_colors: Option<ColorRamp>,
+}
+
+/// Arrangement of the bars of multiple series within a category.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BarLayout {
+    /// Bars of the series are placed side by side.
+    #[default]
+    Grouped,
+    /// Bars of the series are stacked on top of each other.
+    Stacked,
+    /// Like [`BarLayout::Stacked`] but every stack is scaled to 100%.
+    Normalized,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PieOptions {
+    /// Radius of the hole as a fraction of the outer radius, `0.0` renders a pie instead of a donut.
+    pub inner_radius: f32,
+    /// Angle in radians, clockwise from twelve o'clock, at which the first wedge starts.
+    pub start_angle: f32,
+    /// Angle in radians left blank between adjacent wedges.
+    pub pad_angle: f32,
+    /// Radius in pixels used to round the corners of the wedges.
+    pub corner_radius: f32,
+}
+
+impl Default for PieOptions {
+    fn default() -> Self {
+        PieOptions {
+            inner_radius: 0.5,
+            start_angle: 0.0,
+            pad_angle: 0.0,
+            corner_radius: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LineOptions {
+    pub curve: Curve,
+    /// Draws a marker at every data point.
+    pub markers: bool,
+    /// Stacks the series on top of each other.
+    pub stacked: bool,
+    pub domain_scale: DomainScale,
+}
+
+/// Interpretation of the numeric domain of line and area charts.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DomainScale {
+    #[default]
+    Linear,
+    /// Domain values are milliseconds since the unix epoch.
+    Time,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScatterOptions {
+    /// Radius of points without size encoding.
+    pub radius: f32,
+    /// Radii the smallest and the largest size of a bubble chart are mapped to.
+    pub radius_range: (f32, f32),
+    /// Ramp the colour values are mapped along, `None` uses the sequential ramp of the theme.
+    pub color_ramp: Option<ColorRamp>,
+    pub opacity: f32,
+}
+
+impl Default for ScatterOptions {
+    fn default() -> Self {
+        ScatterOptions {
+            radius: 3.0,
+            radius_range: (2.0, 20.0),
+            color_ramp: None,
+            opacity: 0.7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatmapOptions {
+    /// Ramp the values are mapped along, `None` uses the sequential ramp of the theme or the
+    /// diverging one if there is a `center`.
+    pub color_ramp: Option<ColorRamp>,
+    /// Value mapped onto the middle of a diverging ramp, e.g. `0.0` for correlations.
+    pub center: Option<f32>,
+    /// Values mapped onto the ends of the ramp, the range of the data if `None`.
+    pub value_range: Option<(f32, f32)>,
+    /// Writes its value into every cell large enough to hold it.
+    pub show_values: bool,
+    /// Space between cells as a fraction of their step.
+    pub cell_padding: f32,
+}
+
+impl Default for HeatmapOptions {
+    fn default() -> Self {
+        HeatmapOptions {
+            color_ramp: None,
+            center: None,
+            value_range: None,
+            show_values: false,
+            cell_padding: 0.05,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HistogramOptions {
+    pub binning: Binning,
+    /// Scales the bars so that their areas sum up to one instead of showing counts.
+    pub density: bool,
+    /// Accumulates the bars from left to right, up to the number or with `density` the share of samples.
+    pub cumulative: bool,
+}
+
+/// Options of box and violin plots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistributionOptions {
+    pub whiskers: Whiskers,
+    /// Bandwidth of the kernel density estimate of violins, Silverman's rule of thumb if `None`.
+    pub bandwidth: Option<f32>,
+    /// Radius of the circles marking outliers.
+    pub outlier_radius: f32,
+}
+
+impl Default for DistributionOptions {
+    fn default() -> Self {
+        DistributionOptions {
+            whiskers: Whiskers::default(),
+            bandwidth: None,
+            outlier_radius: 2.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandlestickOptions {
+    /// Draws the volume of the candles as bars below them, sharing their x-axis.
+    pub volume: bool,
+    /// Places the candles next to each other instead of leaving gaps for periods without trading.
+    pub collapse_gaps: bool,
+}
+
+impl Default for CandlestickOptions {
+    fn default() -> Self {
+        CandlestickOptions {
+            volume: true,
+            collapse_gaps: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadarOptions {
+    /// Opacity of the filled series, raised for the highlighted series and lowered for the others.
+    pub opacity: f32,
+    /// Approximate number of grid rings, placed at round values.
+    pub rings: usize,
+    /// Value of the outermost ring, the largest value extended to a round number if `None`.
+    pub max_value: Option<f32>,
+}
+
+impl Default for RadarOptions {
+    fn default() -> Self {
+        RadarOptions {
+            opacity: 0.25,
+            rings: 4,
+            max_value: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LegendOptions {
+    /// Side of the chart the legend is placed on, `None` renders no legend.
+    pub position: Option<LegendPosition>,
+}
+
+/// A named sequence of values, one per category of the chart's domain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    pub name: String,
+    pub values: Vec<f32>,
+}
+
+impl Series {
+    pub fn new(name: impl Into<String>, values: Vec<f32>) -> Self {
+        Series { name: name.into(), values }
+    }
+
+    /// Creates a series in which missing values are given as `None`.
+    pub fn with_gaps(name: impl Into<String>, values: Vec<Option<f32>>) -> Self {
+        Series::new(name, values.into_iter().map(|value| value.unwrap_or(f32::NAN)).collect())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Margin {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl Margin {
+    pub fn new(top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        Margin { top, right, bottom, left }
+    }
+
+    /// Returns the extent that remains of `extent` after subtracting this margin.
+    pub fn shrink(&self, extent: &Extent2<f32>) -> Extent2<f32> {
+        Extent2::new(
+            (extent.width - self.left - self.right).max(0.0),
+            (extent.height - self.top - self.bottom).max(0.0),
+        )
+    }
 }
@@ -0,0 +1,14 @@
+use leptos::ev::MouseEvent;
+use nalgebra::Point2;
+use wasm_bindgen::JsCast;
+use web_sys::Element;
+
+/// Returns the position of the pointer relative to the element the listener is attached to.
+pub(crate) fn pointer_position(event: &MouseEvent) -> Option<Point2<f32>> {
+    let target = event.current_target()?.dyn_into::<Element>().ok()?;
+    let rect = target.get_bounding_client_rect();
+    Some(Point2::new(
+        event.client_x() as f32 - rect.left() as f32,
+        event.client_y() as f32 - rect.top() as f32,
+    ))
+}
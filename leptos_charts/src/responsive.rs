@@ -0,0 +1,144 @@
+use leptos::{create_effect, create_rw_signal, on_cleanup, NodeRef, ReadSignal, RwSignal, Scope, SignalGet, SignalGetUntracked, SignalSet, SignalWithUntracked};
+use leptos::html::Div;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::ResizeObserver;
+
+use crate::{ChartOptions, Extent2};
+
+/// Sizes a chart to the element containing it. The chart takes the width of its container and
+/// its height if the container has one of its own, otherwise the height of `extent` or the
+/// height following from the aspect ratio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponsiveOptions {
+    pub min_extent: Extent2<f32>,
+    pub max_extent: Extent2<f32>,
+    /// Ratio of width to height kept while resizing. The height bounds win over the width bounds
+    /// if both can not be met.
+    pub aspect_ratio: Option<f32>,
+}
+
+impl Default for ResponsiveOptions {
+    fn default() -> Self {
+        ResponsiveOptions {
+            min_extent: Extent2::new(0.0, 0.0),
+            max_extent: Extent2::new(f32::INFINITY, f32::INFINITY),
+            aspect_ratio: None,
+        }
+    }
+}
+
+impl ResponsiveOptions {
+
+    /// Returns the extent of a chart given the space available to it, falling back to the height
+    /// of `extent` if the available space has no height.
+    pub fn fit(&self, available: &Extent2<f32>, extent: &Extent2<f32>) -> Extent2<f32> {
+        let width = available.width.min(self.max_extent.width).max(self.min_extent.width);
+        let clamp_height = |height: f32| height.min(self.max_extent.height).max(self.min_extent.height);
+        match self.aspect_ratio.filter(|ratio| *ratio > 0.0) {
+            Some(ratio) => {
+                let height = clamp_height(width / ratio);
+                Extent2::new(height * ratio, height)
+            },
+            None if available.height > 0.0 => Extent2::new(width, clamp_height(available.height)),
+            None => Extent2::new(width, clamp_height(extent.height)),
+        }
+    }
+}
+
+/// Returns the options with the extent replaced by the one fitting `size` if they ask for a
+/// responsive chart, unchanged otherwise.
+pub(crate) fn resolve_options(cx: Scope, options: ReadSignal<ChartOptions>, size: ReadSignal<Option<Extent2<f32>>>) -> ReadSignal<ChartOptions> {
+    let resolved = create_rw_signal(cx, options.get_untracked());
+
+    create_effect(cx, move |_| {
+        let mut options = options.get();
+        if let (Some(responsive), Some(size)) = (&options.responsive, size.get()) {
+            options.extent = responsive.fit(&size, &options.extent);
+        }
+        if resolved.with_untracked(|resolved| resolved != &options) {
+            resolved.set(options);
+        }
+    });
+
+    resolved.read_only()
+}
+
+/// Keeps `size` up to date with the content size of the element behind `node_ref`.
+pub(crate) fn observe_size(cx: Scope, node_ref: NodeRef<Div>, size: RwSignal<Option<Extent2<f32>>>) {
+    node_ref.on_load(cx, move |element| {
+        let target = Clone::clone(&*element);
+        let callback = {
+            let target = Clone::clone(&target);
+            Closure::<dyn FnMut()>::new(move || {
+                let extent = Some(Extent2::new(target.client_width() as f32, target.client_height() as f32));
+                if size.get_untracked() != extent {
+                    size.set(extent);
+                }
+            })
+        };
+        let Ok(observer) = ResizeObserver::new(callback.as_ref().unchecked_ref()) else {
+            return
+        };
+        observer.observe(&target);
+        on_cleanup(cx, move || {
+            observer.disconnect();
+            drop(callback);
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(min: (f32, f32), max: (f32, f32), aspect_ratio: Option<f32>) -> ResponsiveOptions {
+        ResponsiveOptions {
+            min_extent: Extent2::new(min.0, min.1),
+            max_extent: Extent2::new(max.0, max.1),
+            aspect_ratio,
+        }
+    }
+
+    const EXTENT: Extent2<f32> = Extent2 { width: 500.0, height: 400.0 };
+
+    #[test]
+    fn charts_take_the_available_space_by_default() {
+        assert_eq!(ResponsiveOptions::default().fit(&Extent2::new(640.0, 480.0), &EXTENT), Extent2::new(640.0, 480.0));
+    }
+
+    #[test]
+    fn the_extent_is_clamped_to_its_bounds() {
+        let options = options((200.0, 100.0), (800.0, 600.0), None);
+        assert_eq!(options.fit(&Extent2::new(100.0, 50.0), &EXTENT), Extent2::new(200.0, 100.0));
+        assert_eq!(options.fit(&Extent2::new(1000.0, 900.0), &EXTENT), Extent2::new(800.0, 600.0));
+        assert_eq!(options.fit(&Extent2::new(500.0, 0.0), &Extent2::new(500.0, 50.0)), Extent2::new(500.0, 100.0));
+    }
+
+    #[test]
+    fn containers_without_height_take_the_height_of_the_extent() {
+        assert_eq!(ResponsiveOptions::default().fit(&Extent2::new(640.0, 0.0), &EXTENT), Extent2::new(640.0, 400.0));
+        let options = options((0.0, 0.0), (f32::INFINITY, 300.0), None);
+        assert_eq!(options.fit(&Extent2::new(640.0, 0.0), &EXTENT), Extent2::new(640.0, 300.0));
+    }
+
+    #[test]
+    fn the_height_follows_the_aspect_ratio() {
+        let options = options((0.0, 0.0), (f32::INFINITY, f32::INFINITY), Some(2.0));
+        assert_eq!(options.fit(&Extent2::new(640.0, 0.0), &EXTENT), Extent2::new(640.0, 320.0));
+        assert_eq!(options.fit(&Extent2::new(640.0, 1000.0), &EXTENT), Extent2::new(640.0, 320.0));
+    }
+
+    #[test]
+    fn height_bounds_win_over_width_bounds_with_an_aspect_ratio() {
+        let options = options((0.0, 100.0), (600.0, 200.0), Some(2.0));
+        assert_eq!(options.fit(&Extent2::new(800.0, 0.0), &EXTENT), Extent2::new(400.0, 200.0));
+        assert_eq!(options.fit(&Extent2::new(100.0, 0.0), &EXTENT), Extent2::new(200.0, 100.0));
+    }
+
+    #[test]
+    fn invalid_aspect_ratios_are_ignored() {
+        let options = options((0.0, 0.0), (f32::INFINITY, f32::INFINITY), Some(0.0));
+        assert_eq!(options.fit(&Extent2::new(640.0, 480.0), &EXTENT), Extent2::new(640.0, 480.0));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::scale::Scale;
+
+/// Divides the output range into uniform bands, one per category of the domain.
+///
+/// The domain is addressed by index; [`BandScale::index_of`] looks up the index of a category.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandScale {
+    domain: Vec<String>,
+    range: (f32, f32),
+    padding_inner: f32,
+    padding_outer: f32,
+}
+
+impl BandScale {
+
+    pub fn new(domain: Vec<String>, range: (f32, f32)) -> Self {
+        BandScale { domain, range, padding_inner: 0.0, padding_outer: 0.0 }
+    }
+
+    /// Sets the padding between bands as a fraction of the step.
+    pub fn with_padding_inner(self, padding_inner: f32) -> Self {
+        BandScale { padding_inner: padding_inner.clamp(0.0, 1.0), ..self }
+    }
+
+    /// Sets the padding before the first and after the last band as a fraction of the step.
+    pub fn with_padding_outer(self, padding_outer: f32) -> Self {
+        BandScale { padding_outer: padding_outer.max(0.0), ..self }
+    }
+
+    pub fn domain(&self) -> &[String] {
+        &self.domain
+    }
+
+    pub fn index_of(&self, category: &str) -> Option<usize> {
+        self.domain.iter().position(|value| value == category)
+    }
+
+    /// Returns the distance between the starts of adjacent bands.
+    pub fn step(&self) -> f32 {
+        let count = self.domain.len() as f32;
+        (self.range.1 - self.range.0) / (count - self.padding_inner + self.padding_outer * 2.0).max(1.0)
+    }
+
+    /// Returns the width of a single band.
+    pub fn bandwidth(&self) -> f32 {
+        self.step() * (1.0 - self.padding_inner)
+    }
+
+    /// Returns the position of the centre of the band at `index`.
+    pub fn center(&self, index: usize) -> f32 {
+        self.map(index) + self.bandwidth() * 0.5
+    }
+}
+
+impl Scale for BandScale {
+    type Domain = usize;
+
+    /// Returns the start of the band at `index`.
+    fn map(&self, index: usize) -> f32 {
+        self.range.0 + self.step() * (self.padding_outer + index as f32)
+    }
+
+    /// Returns the index of the band closest to `position`.
+    fn invert(&self, position: f32) -> usize {
+        let step = self.step();
+        if self.domain.is_empty() || step == 0.0 {
+            return 0
+        }
+        let index = ((position - self.range.0) / step - self.padding_outer + self.padding_inner * 0.5).floor();
+        (index.max(0.0) as usize).min(self.domain.len() - 1)
+    }
+
+    fn ticks(&self, _count: usize) -> Vec<usize> {
+        (0..self.domain.len()).collect()
+    }
+
+    fn nice(self, _count: usize) -> Self {
+        self
+    }
+
+    fn range(&self) -> (f32, f32) {
+        self.range
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scale(padding_inner: f32, padding_outer: f32) -> BandScale {
+        let domain = ["a", "b", "c", "d"].iter().map(|category| String::from(*category)).collect();
+        BandScale::new(domain, (0.0, 100.0)).with_padding_inner(padding_inner).with_padding_outer(padding_outer)
+    }
+
+    #[test]
+    fn bands_fill_the_range_without_padding() {
+        let scale = scale(0.0, 0.0);
+        assert_eq!(scale.step(), 25.0);
+        assert_eq!(scale.bandwidth(), 25.0);
+        assert_eq!(scale.map(2), 50.0);
+        assert_eq!(scale.center(3), 87.5);
+    }
+
+    #[test]
+    fn padding_shrinks_the_bands() {
+        let scale = scale(0.2, 0.4);
+        assert_eq!(scale.step(), 100.0 / 4.6);
+        assert_eq!(scale.bandwidth(), scale.step() * 0.8);
+        assert_eq!(scale.map(0), scale.step() * 0.4);
+        assert!((scale.map(3) + scale.bandwidth() + scale.step() * 0.4 - 100.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn inverts_positions_to_bands() {
+        let scale = scale(0.2, 0.4);
+        (0..4).for_each(|index| assert_eq!(scale.invert(scale.center(index)), index));
+        assert_eq!(scale.invert(-50.0), 0);
+        assert_eq!(scale.invert(500.0), 3);
+        assert_eq!(scale.index_of("c"), Some(2));
+        assert_eq!(scale.ticks(2), vec![0, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::axis;
+use crate::scale::{interpolate, normalize, Scale};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearScale {
+    domain: (f32, f32),
+    range: (f32, f32),
+}
+
+impl LinearScale {
+
+    pub fn new(domain: (f32, f32), range: (f32, f32)) -> Self {
+        LinearScale { domain, range }
+    }
+
+    pub fn domain(&self) -> (f32, f32) {
+        self.domain
+    }
+
+    /// Returns the step between the values produced by [`Scale::ticks`] for `count`.
+    pub fn tick_step(&self, count: usize) -> f32 {
+        axis::nice_step((self.domain.1 - self.domain.0).abs(), count)
+    }
+}
+
+impl Scale for LinearScale {
+    type Domain = f32;
+
+    fn map(&self, value: f32) -> f32 {
+        interpolate(self.range, normalize(self.domain, value))
+    }
+
+    fn invert(&self, position: f32) -> f32 {
+        interpolate(self.domain, normalize(self.range, position))
+    }
+
+    fn ticks(&self, count: usize) -> Vec<f32> {
+        let (min, max) = (self.domain.0.min(self.domain.1), self.domain.0.max(self.domain.1));
+        axis::ticks(min, max, count)
+    }
+
+    fn nice(self, count: usize) -> Self {
+        LinearScale {
+            domain: axis::nice_range(self.domain.0, self.domain.1, count),
+            ..self
+        }
+    }
+
+    fn range(&self) -> (f32, f32) {
+        self.range
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_and_inverts() {
+        let scale = LinearScale::new((-10.0, 30.0), (100.0, 500.0));
+        assert_eq!(scale.map(-10.0), 100.0);
+        assert_eq!(scale.map(10.0), 300.0);
+        assert_eq!(scale.map(40.0), 600.0);
+        assert_eq!(scale.invert(300.0), 10.0);
+        assert_eq!(scale.invert(scale.map(17.5)), 17.5);
+    }
+
+    #[test]
+    fn maps_reversed_ranges() {
+        let scale = LinearScale::new((0.0, 10.0), (200.0, 0.0));
+        assert_eq!(scale.map(2.5), 150.0);
+        assert_eq!(scale.invert(150.0), 2.5);
+    }
+
+    #[test]
+    fn maps_empty_domains_to_the_middle() {
+        assert_eq!(LinearScale::new((3.0, 3.0), (0.0, 100.0)).map(3.0), 50.0);
+    }
+
+    #[test]
+    fn ticks_are_multiples_of_the_step() {
+        let scale = LinearScale::new((0.3, 9.7), (0.0, 1.0));
+        assert_eq!(scale.tick_step(5), 2.0);
+        assert_eq!(scale.ticks(5), vec![2.0, 4.0, 6.0, 8.0]);
+        assert_eq!(LinearScale::new((10.0, 0.0), (0.0, 1.0)).ticks(2), vec![0.0, 5.0, 10.0]);
+    }
+
+    #[test]
+    fn nice_extends_the_domain_to_ticks() {
+        assert_eq!(LinearScale::new((0.3, 9.7), (0.0, 1.0)).nice(5).domain(), (0.0, 10.0));
+        assert_eq!(LinearScale::new((-3.0, 17.0), (0.0, 1.0)).nice(4).domain(), (-5.0, 20.0));
+        assert_eq!(LinearScale::new((4.0, 4.0), (0.0, 1.0)).nice(5).domain(), (0.0, 4.0));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::scale::{interpolate, normalize, Scale};
+
+/// A logarithmic scale. The domain must be strictly positive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogScale {
+    domain: (f32, f32),
+    range: (f32, f32),
+    base: f32,
+}
+
+impl LogScale {
+
+    pub fn new(domain: (f32, f32), range: (f32, f32)) -> Self {
+        LogScale { domain, range, base: 10.0 }
+    }
+
+    pub fn with_base(self, base: f32) -> Self {
+        LogScale { base, ..self }
+    }
+
+    pub fn domain(&self) -> (f32, f32) {
+        self.domain
+    }
+
+    fn log(&self, value: f32) -> f32 {
+        value.log(self.base)
+    }
+
+    fn log_domain(&self) -> (f32, f32) {
+        (self.log(self.domain.0), self.log(self.domain.1))
+    }
+}
+
+impl Scale for LogScale {
+    type Domain = f32;
+
+    fn map(&self, value: f32) -> f32 {
+        interpolate(self.range, normalize(self.log_domain(), self.log(value)))
+    }
+
+    fn invert(&self, position: f32) -> f32 {
+        self.base.powf(interpolate(self.log_domain(), normalize(self.range, position)))
+    }
+
+    fn ticks(&self, count: usize) -> Vec<f32> {
+        let (min, max) = (self.domain.0.min(self.domain.1), self.domain.0.max(self.domain.1));
+        if !(min > 0.0) || !max.is_finite() {
+            return Vec::new()
+        }
+        let (start, end) = (self.log(min).floor() as i32, self.log(max).ceil() as i32);
+        let decades = (end - start).max(1) as usize;
+        // Subdivide decades with the integer multiples of the power when there are only few of them.
+        let multiples: Vec<f32> = if decades * 2 < count && self.base == 10.0 {
+            if decades * 9 <= count { (1..10).map(|m| m as f32).collect() } else { vec![1.0, 2.0, 5.0] }
+        }
+        else {
+            vec![1.0]
+        };
+        let stride = ((decades + count.max(1) - 1) / count.max(1)).max(1);
+        (start..=end)
+            .filter(|exponent| (exponent - start) as usize % stride == 0)
+            .flat_map(|exponent| {
+                let power = self.base.powi(exponent);
+                multiples.iter().map(move |multiple| multiple * power).collect::<Vec<_>>()
+            })
+            .filter(|value| *value >= min * 0.9999 && *value <= max * 1.0001)
+            .collect()
+    }
+
+    fn nice(self, _count: usize) -> Self {
+        let (log_min, log_max) = self.log_domain();
+        let domain = if self.domain.0 <= self.domain.1 {
+            (self.base.powf(log_min.floor()), self.base.powf(log_max.ceil()))
+        }
+        else {
+            (self.base.powf(log_min.ceil()), self.base.powf(log_max.floor()))
+        };
+        LogScale { domain, ..self }
+    }
+
+    fn range(&self) -> (f32, f32) {
+        self.range
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() <= 1e-3 * expected.abs().max(1.0), "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn maps_decades_evenly() {
+        let scale = LogScale::new((1.0, 1000.0), (0.0, 300.0));
+        assert_close(scale.map(10.0), 100.0);
+        assert_close(scale.map(100.0), 200.0);
+        assert_close(scale.invert(150.0), 10f32.powf(1.5));
+        assert_close(LogScale::new((1.0, 8.0), (0.0, 3.0)).with_base(2.0).map(4.0), 2.0);
+    }
+
+    #[test]
+    fn ticks_at_powers_of_ten() {
+        let ticks = LogScale::new((1.0, 1e6), (0.0, 1.0)).ticks(10);
+        assert_eq!(ticks.len(), 7);
+        ticks.iter().zip([1.0, 10.0, 100.0, 1e3, 1e4, 1e5, 1e6]).for_each(|(tick, expected)| assert_close(*tick, expected));
+    }
+
+    #[test]
+    fn ticks_subdivide_few_decades() {
+        let ticks = LogScale::new((1.0, 100.0), (0.0, 1.0)).ticks(6);
+        ticks.iter().zip([1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0]).for_each(|(tick, expected)| assert_close(*tick, expected));
+        assert_eq!(ticks.len(), 7);
+    }
+
+    #[test]
+    fn ticks_require_a_positive_domain() {
+        assert!(LogScale::new((0.0, 100.0), (0.0, 1.0)).ticks(5).is_empty());
+    }
+
+    #[test]
+    fn nice_rounds_to_powers() {
+        let (min, max) = LogScale::new((3.0, 420.0), (0.0, 1.0)).nice(5).domain();
+        assert_close(min, 1.0);
+        assert_close(max, 1000.0);
+    }
+}
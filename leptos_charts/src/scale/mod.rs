@@ -0,0 +1,43 @@
+mod band;
+mod linear;
+mod log;
+mod symlog;
+mod time;
+
+pub use band::BandScale;
+pub use linear::LinearScale;
+pub use log::LogScale;
+pub use symlog::SymLogScale;
+pub use time::{TimeInterval, TimeScale};
+
+/// Maps values of a domain onto a continuous output range, e.g. pixels along an axis.
+pub trait Scale {
+    type Domain;
+
+    /// Maps a domain value onto the output range.
+    fn map(&self, value: Self::Domain) -> f32;
+
+    /// Maps a position of the output range back onto the domain.
+    fn invert(&self, position: f32) -> Self::Domain;
+
+    /// Returns approximately `count` representative domain values for ticks and gridlines.
+    fn ticks(&self, count: usize) -> Vec<Self::Domain>;
+
+    /// Extends the domain so that it starts and ends on round values.
+    fn nice(self, count: usize) -> Self where Self: Sized;
+
+    fn range(&self) -> (f32, f32);
+}
+
+pub(crate) fn interpolate(range: (f32, f32), t: f32) -> f32 {
+    range.0 + t * (range.1 - range.0)
+}
+
+pub(crate) fn normalize(range: (f32, f32), value: f32) -> f32 {
+    if range.1 == range.0 {
+        0.5
+    }
+    else {
+        (value - range.0) / (range.1 - range.0)
+    }
+}
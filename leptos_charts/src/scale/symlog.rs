@@ -0,0 +1,162 @@
+use crate::axis;
+use crate::scale::{interpolate, normalize, Scale};
+
+/// A bi-symmetric logarithmic scale. Unlike [`LogScale`](crate::scale::LogScale) it accepts zero
+/// and negative values and behaves linearly within `-constant..constant`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymLogScale {
+    domain: (f32, f32),
+    range: (f32, f32),
+    constant: f32,
+}
+
+impl SymLogScale {
+
+    pub fn new(domain: (f32, f32), range: (f32, f32)) -> Self {
+        SymLogScale { domain, range, constant: 1.0 }
+    }
+
+    pub fn with_constant(self, constant: f32) -> Self {
+        SymLogScale { constant, ..self }
+    }
+
+    pub fn domain(&self) -> (f32, f32) {
+        self.domain
+    }
+
+    fn transform(&self, value: f32) -> f32 {
+        value.signum() * (value.abs() / self.constant).ln_1p()
+    }
+
+    fn untransform(&self, value: f32) -> f32 {
+        value.signum() * value.abs().exp_m1() * self.constant
+    }
+
+    /// Returns the powers of ten from the decade of the constant, where the scale turns
+    /// logarithmic, up to `limit`.
+    fn powers(&self, limit: f32) -> Vec<f32> {
+        let lowest = self.constant.log10().floor() as i32;
+        let highest = if limit > 0.0 { (limit.log10() + 1e-6).floor() as i32 } else { lowest - 1 };
+        (lowest..=highest).map(|exponent| 10f32.powi(exponent)).collect()
+    }
+
+    /// Rounds the lower or `upper` bound of the domain outwards to a power of ten when it lies in
+    /// the logarithmic part.
+    fn nice_bound(&self, value: f32, upper: bool) -> f32 {
+        if value.abs() <= self.constant {
+            return value
+        }
+        let exponent = value.abs().log10();
+        let exponent = if upper == (value > 0.0) { exponent.ceil() } else { exponent.floor() };
+        value.signum() * 10f32.powf(exponent)
+    }
+}
+
+impl Scale for SymLogScale {
+    type Domain = f32;
+
+    fn map(&self, value: f32) -> f32 {
+        let domain = (self.transform(self.domain.0), self.transform(self.domain.1));
+        interpolate(self.range, normalize(domain, self.transform(value)))
+    }
+
+    fn invert(&self, position: f32) -> f32 {
+        let domain = (self.transform(self.domain.0), self.transform(self.domain.1));
+        self.untransform(interpolate(domain, normalize(self.range, position)))
+    }
+
+    /// Places ticks at zero and at powers of ten on either side of it, which are evenly spaced
+    /// in the logarithmic parts. Domains within the linear part get linear ticks.
+    fn ticks(&self, count: usize) -> Vec<f32> {
+        let (min, max) = (self.domain.0.min(self.domain.1), self.domain.0.max(self.domain.1));
+        if !min.is_finite() || !max.is_finite() || min == max {
+            return Vec::new()
+        }
+        let positive = self.powers(max).into_iter().filter(|power| *power >= min).collect::<Vec<_>>();
+        let negative = self.powers(-min).into_iter().map(|power| -power).filter(|power| *power <= max).collect::<Vec<_>>();
+        if positive.len() + negative.len() < 2 {
+            return axis::ticks(min, max, count)
+        }
+        // Thin out the decades evenly on both sides when there are more of them than ticks.
+        let stride = ((positive.len() + negative.len() + count.max(1) - 1) / count.max(1)).max(1);
+        let thin = |powers: Vec<f32>| powers.into_iter().step_by(stride).collect::<Vec<_>>();
+        let zero = (min <= 0.0 && max >= 0.0).then_some(0.0);
+        thin(negative).into_iter().rev()
+            .chain(zero)
+            .chain(thin(positive))
+            .collect()
+    }
+
+    fn nice(self, _count: usize) -> Self {
+        let domain = if self.domain.0 <= self.domain.1 {
+            (self.nice_bound(self.domain.0, false), self.nice_bound(self.domain.1, true))
+        }
+        else {
+            (self.nice_bound(self.domain.0, true), self.nice_bound(self.domain.1, false))
+        };
+        SymLogScale { domain, ..self }
+    }
+
+    fn range(&self) -> (f32, f32) {
+        self.range
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() <= 1e-3 * expected.abs().max(1.0), "{} != {}", actual, expected);
+    }
+
+    fn assert_all_close(actual: Vec<f32>, expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len(), "{:?} != {:?}", actual, expected);
+        actual.iter().zip(expected).for_each(|(actual, expected)| assert_close(*actual, *expected));
+    }
+
+    #[test]
+    fn maps_symmetrically_around_zero() {
+        let scale = SymLogScale::new((-100.0, 100.0), (0.0, 200.0));
+        assert_close(scale.map(0.0), 100.0);
+        assert_close(scale.map(10.0) - 100.0, 100.0 - scale.map(-10.0));
+        assert_close(scale.invert(scale.map(42.0)), 42.0);
+        assert_close(scale.invert(scale.map(-0.5)), -0.5);
+    }
+
+    #[test]
+    fn ticks_at_zero_and_powers_of_ten() {
+        assert_all_close(SymLogScale::new((0.0, 1000.0), (0.0, 1.0)).ticks(10), &[0.0, 1.0, 10.0, 100.0, 1000.0]);
+        assert_all_close(
+            SymLogScale::new((-100.0, 1000.0), (0.0, 1.0)).ticks(10),
+            &[-100.0, -10.0, -1.0, 0.0, 1.0, 10.0, 100.0, 1000.0],
+        );
+    }
+
+    #[test]
+    fn ticks_spread_over_the_transformed_range() {
+        let scale = SymLogScale::new((0.0, 1e6), (0.0, 600.0));
+        let positions = scale.ticks(10).into_iter().map(|tick| scale.map(tick)).collect::<Vec<_>>();
+        // Away from the constant, where the scale is still partly linear, decades are equally far apart.
+        let gaps = positions.windows(2).skip(2).map(|pair| pair[1] - pair[0]).collect::<Vec<_>>();
+        gaps.windows(2).for_each(|pair| assert!((pair[0] - pair[1]).abs() < 5.0, "{:?}", gaps));
+    }
+
+    #[test]
+    fn ticks_thin_out_many_decades() {
+        assert_all_close(SymLogScale::new((0.0, 1e8), (0.0, 1.0)).ticks(3), &[0.0, 1.0, 1e3, 1e6]);
+    }
+
+    #[test]
+    fn ticks_within_the_linear_part_are_linear() {
+        assert_all_close(SymLogScale::new((0.0, 0.5), (0.0, 1.0)).ticks(5), &[0.0, 0.1, 0.2, 0.3, 0.4, 0.5]);
+    }
+
+    #[test]
+    fn nice_rounds_logarithmic_bounds_to_powers() {
+        let (min, max) = SymLogScale::new((-42.0, 420.0), (0.0, 1.0)).nice(5).domain();
+        assert_close(min, -100.0);
+        assert_close(max, 1000.0);
+        assert_eq!(SymLogScale::new((0.0, 0.5), (0.0, 1.0)).nice(5).domain(), (0.0, 0.5));
+    }
+}
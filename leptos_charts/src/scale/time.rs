@@ -0,0 +1,307 @@
+use crate::axis;
+use crate::scale::Scale;
+
+const SECOND: f64 = 1000.0;
+const MINUTE: f64 = SECOND * 60.0;
+const HOUR: f64 = MINUTE * 60.0;
+const DAY: f64 = HOUR * 24.0;
+const WEEK: f64 = DAY * 7.0;
+const MONTH: f64 = DAY * 30.0;
+const YEAR: f64 = DAY * 365.0;
+
+const INTERVALS: [TimeInterval; 18] = [
+    TimeInterval::Second(1),
+    TimeInterval::Second(5),
+    TimeInterval::Second(15),
+    TimeInterval::Second(30),
+    TimeInterval::Minute(1),
+    TimeInterval::Minute(5),
+    TimeInterval::Minute(15),
+    TimeInterval::Minute(30),
+    TimeInterval::Hour(1),
+    TimeInterval::Hour(3),
+    TimeInterval::Hour(6),
+    TimeInterval::Hour(12),
+    TimeInterval::Day(1),
+    TimeInterval::Day(2),
+    TimeInterval::Week,
+    TimeInterval::Month(1),
+    TimeInterval::Month(3),
+    TimeInterval::Year(1),
+];
+
+/// A calendar interval in UTC used to place time ticks on round dates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeInterval {
+    Second(u32),
+    Minute(u32),
+    Hour(u32),
+    Day(u32),
+    /// A week starting on monday.
+    Week,
+    Month(u32),
+    Year(u32),
+}
+
+impl TimeInterval {
+
+    /// Returns the interval whose duration is closest to `span / count` by ratio.
+    pub fn for_span(span: f64, count: usize) -> TimeInterval {
+        let target = span.abs() / count.max(1) as f64;
+        if target > YEAR {
+            let years = axis::nice_step((target / YEAR) as f32, 1).round().max(1.0);
+            return TimeInterval::Year(years as u32)
+        }
+        INTERVALS.iter()
+            .min_by(|a, b| {
+                let a = (a.duration() / target).ln().abs();
+                let b = (b.duration() / target).ln().abs();
+                a.total_cmp(&b)
+            })
+            .cloned()
+            .unwrap_or(TimeInterval::Day(1))
+    }
+
+    /// Returns the approximate duration of the interval in milliseconds.
+    pub fn duration(&self) -> f64 {
+        match self {
+            TimeInterval::Second(n) => SECOND * *n as f64,
+            TimeInterval::Minute(n) => MINUTE * *n as f64,
+            TimeInterval::Hour(n) => HOUR * *n as f64,
+            TimeInterval::Day(n) => DAY * *n as f64,
+            TimeInterval::Week => WEEK,
+            TimeInterval::Month(n) => MONTH * *n as f64,
+            TimeInterval::Year(n) => YEAR * *n as f64,
+        }
+    }
+
+    /// Rounds `timestamp` down to the start of the interval containing it.
+    pub fn floor(&self, timestamp: f64) -> f64 {
+        let floor_to = |unit: f64, n: u32| (timestamp / (unit * n as f64)).floor() * unit * n as f64;
+        match self {
+            TimeInterval::Second(n) => floor_to(SECOND, *n),
+            TimeInterval::Minute(n) => floor_to(MINUTE, *n),
+            TimeInterval::Hour(n) => floor_to(HOUR, *n),
+            TimeInterval::Day(n) => floor_to(DAY, *n),
+            TimeInterval::Week => {
+                let days = (timestamp / DAY).floor() as i64;
+                // 1970-01-01 was a thursday.
+                let weekday = (days + 3).rem_euclid(7);
+                (days - weekday) as f64 * DAY
+            },
+            TimeInterval::Month(n) => {
+                let (year, month, _) = civil_from_days((timestamp / DAY).floor() as i64);
+                let months = year * 12 + month as i64 - 1;
+                let months = months - months.rem_euclid(*n as i64);
+                days_from_civil(months.div_euclid(12), (months.rem_euclid(12) + 1) as u32, 1) as f64 * DAY
+            },
+            TimeInterval::Year(n) => {
+                let (year, _, _) = civil_from_days((timestamp / DAY).floor() as i64);
+                days_from_civil(year - year.rem_euclid(*n as i64), 1, 1) as f64 * DAY
+            },
+        }
+    }
+
+    /// Returns the start of the interval following the one starting at `timestamp`.
+    pub fn next(&self, timestamp: f64) -> f64 {
+        match self {
+            TimeInterval::Month(n) | TimeInterval::Year(n) => {
+                let months = if let TimeInterval::Year(_) = self { *n as i64 * 12 } else { *n as i64 };
+                let (year, month, day) = civil_from_days((timestamp / DAY).floor() as i64);
+                let months = year * 12 + month as i64 - 1 + months;
+                let time_of_day = timestamp.rem_euclid(DAY);
+                let (year, month) = (months.div_euclid(12), (months.rem_euclid(12) + 1) as u32);
+                // The 31st of a month is followed by the last day of shorter months.
+                days_from_civil(year, month, day.min(days_in_month(year, month))) as f64 * DAY + time_of_day
+            },
+            _ => timestamp + self.duration(),
+        }
+    }
+
+    /// Formats `timestamp` with the precision appropriate for this interval.
+    pub fn format(&self, timestamp: f64) -> String {
+        let days = (timestamp / DAY).floor() as i64;
+        let (year, month, day) = civil_from_days(days);
+        let time_of_day = (timestamp - days as f64 * DAY) as i64 / 1000;
+        let (hours, minutes, seconds) = (time_of_day / 3600, time_of_day / 60 % 60, time_of_day % 60);
+        match self {
+            TimeInterval::Second(_) => format!("{:02}:{:02}:{:02}", hours, minutes, seconds),
+            TimeInterval::Minute(_) | TimeInterval::Hour(_) => format!("{:02}:{:02}", hours, minutes),
+            TimeInterval::Day(_) | TimeInterval::Week => format!("{:04}-{:02}-{:02}", year, month, day),
+            TimeInterval::Month(_) => format!("{:04}-{:02}", year, month),
+            TimeInterval::Year(_) => format!("{:04}", year),
+        }
+    }
+}
+
+/// A linear scale over timestamps given as milliseconds since the unix epoch (UTC).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeScale {
+    domain: (f64, f64),
+    range: (f32, f32),
+}
+
+impl TimeScale {
+
+    pub fn new(domain: (f64, f64), range: (f32, f32)) -> Self {
+        TimeScale { domain, range }
+    }
+
+    pub fn domain(&self) -> (f64, f64) {
+        self.domain
+    }
+
+    /// Returns the interval used by [`Scale::ticks`] for `count`.
+    pub fn tick_interval(&self, count: usize) -> TimeInterval {
+        TimeInterval::for_span(self.domain.1 - self.domain.0, count)
+    }
+}
+
+impl Scale for TimeScale {
+    type Domain = f64;
+
+    fn map(&self, value: f64) -> f32 {
+        let (d0, d1) = self.domain;
+        let t = if d1 == d0 { 0.5 } else { (value - d0) / (d1 - d0) };
+        self.range.0 + t as f32 * (self.range.1 - self.range.0)
+    }
+
+    fn invert(&self, position: f32) -> f64 {
+        let (r0, r1) = self.range;
+        let t = if r1 == r0 { 0.5 } else { ((position - r0) / (r1 - r0)) as f64 };
+        self.domain.0 + t * (self.domain.1 - self.domain.0)
+    }
+
+    fn ticks(&self, count: usize) -> Vec<f64> {
+        let (min, max) = (self.domain.0.min(self.domain.1), self.domain.0.max(self.domain.1));
+        if !min.is_finite() || !max.is_finite() || min == max {
+            return Vec::new()
+        }
+        let interval = self.tick_interval(count);
+        let mut tick = interval.floor(min);
+        if tick < min {
+            tick = interval.next(tick);
+        }
+        let mut ticks = Vec::new();
+        while tick <= max {
+            ticks.push(tick);
+            tick = interval.next(tick);
+        }
+        ticks
+    }
+
+    fn nice(self, count: usize) -> Self {
+        let interval = self.tick_interval(count);
+        let (min, max) = (self.domain.0.min(self.domain.1), self.domain.0.max(self.domain.1));
+        let floor = interval.floor(min);
+        let ceil = if interval.floor(max) < max { interval.next(interval.floor(max)) } else { max };
+        let domain = if self.domain.0 <= self.domain.1 { (floor, ceil) } else { (ceil, floor) };
+        TimeScale { domain, ..self }
+    }
+
+    fn range(&self) -> (f32, f32) {
+        self.range
+    }
+}
+
+/// Converts days since 1970-01-01 into a proleptic gregorian (year, month, day).
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Returns the number of days of a month of the proleptic gregorian calendar.
+pub(crate) fn days_in_month(year: i64, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    (days_from_civil(next_year, next_month, 1) - days_from_civil(year, month, 1)) as u32
+}
+
+/// Converts a proleptic gregorian date into days since 1970-01-01.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamp(year: i64, month: u32, day: u32) -> f64 {
+        days_from_civil(year, month, day) as f64 * DAY
+    }
+
+    #[test]
+    fn converts_civil_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(civil_from_days(11017), (2000, 3, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        (-1000..1000).map(|days| days * 37).for_each(|days| {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        });
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2023, 12), 31);
+    }
+
+    #[test]
+    fn floors_to_calendar_intervals() {
+        let time = timestamp(2023, 8, 17) + 13.5 * HOUR;
+        assert_eq!(TimeInterval::Hour(6).floor(time), timestamp(2023, 8, 17) + 12.0 * HOUR);
+        assert_eq!(TimeInterval::Day(1).floor(time), timestamp(2023, 8, 17));
+        assert_eq!(TimeInterval::Week.floor(time), timestamp(2023, 8, 14));
+        assert_eq!(TimeInterval::Month(3).floor(time), timestamp(2023, 7, 1));
+        assert_eq!(TimeInterval::Year(10).floor(time), timestamp(2020, 1, 1));
+    }
+
+    #[test]
+    fn next_month_keeps_the_day_within_the_month() {
+        assert_eq!(TimeInterval::Month(1).next(timestamp(2023, 1, 31)), timestamp(2023, 2, 28));
+        assert_eq!(TimeInterval::Month(1).next(timestamp(2024, 1, 31)), timestamp(2024, 2, 29));
+        assert_eq!(TimeInterval::Month(3).next(timestamp(2023, 11, 15)), timestamp(2024, 2, 15));
+        assert_eq!(TimeInterval::Year(1).next(timestamp(2024, 2, 29)), timestamp(2025, 2, 28));
+        assert_eq!(TimeInterval::Month(1).next(timestamp(2023, 3, 1) + HOUR), timestamp(2023, 4, 1) + HOUR);
+    }
+
+    #[test]
+    fn picks_intervals_close_to_the_span() {
+        assert_eq!(TimeInterval::for_span(10.0 * DAY, 10), TimeInterval::Day(1));
+        assert_eq!(TimeInterval::for_span(12.0 * MONTH, 4), TimeInterval::Month(3));
+        assert_eq!(TimeInterval::for_span(50.0 * YEAR, 5), TimeInterval::Year(10));
+    }
+
+    #[test]
+    fn maps_and_inverts() {
+        let scale = TimeScale::new((timestamp(2023, 1, 1), timestamp(2023, 1, 11)), (0.0, 100.0));
+        assert_eq!(scale.map(timestamp(2023, 1, 6)), 50.0);
+        assert!((scale.invert(20.0) - timestamp(2023, 1, 3)).abs() < SECOND);
+    }
+
+    #[test]
+    fn ticks_on_round_dates() {
+        let scale = TimeScale::new((timestamp(2023, 1, 15), timestamp(2023, 7, 15)), (0.0, 1.0));
+        let ticks = scale.ticks(6);
+        assert_eq!(ticks, (2..=7).map(|month| timestamp(2023, month, 1)).collect::<Vec<_>>());
+        assert_eq!(scale.tick_interval(6).format(ticks[0]), "2023-02");
+    }
+
+    #[test]
+    fn nice_extends_to_interval_boundaries() {
+        let scale = TimeScale::new((timestamp(2023, 1, 15), timestamp(2023, 7, 15)), (0.0, 1.0)).nice(6);
+        assert_eq!(scale.domain(), (timestamp(2023, 1, 1), timestamp(2023, 8, 1)));
+    }
+}
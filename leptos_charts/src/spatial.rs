@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use nalgebra::Point2;
+
+/// A uniform grid over points in screen space for finding the point closest to the pointer
+/// without testing every point.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SpatialIndex {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    points: Vec<Point2<f32>>,
+}
+
+impl SpatialIndex {
+
+    pub fn new(points: Vec<Point2<f32>>, cell_size: f32) -> Self {
+        let cell_size = cell_size.max(1.0);
+        let mut cells: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+        points.iter()
+            .enumerate()
+            .filter(|(_, point)| point.x.is_finite() && point.y.is_finite())
+            .for_each(|(index, point)| {
+                cells.entry(Self::cell(point, cell_size)).or_default().push(index);
+            });
+        SpatialIndex { cell_size, cells, points }
+    }
+
+    /// Returns the index of the point closest to `position` within `max_distance`.
+    pub fn nearest(&self, position: Point2<f32>, max_distance: f32) -> Option<usize> {
+        let (column, row) = Self::cell(&position, self.cell_size);
+        let reach = (max_distance / self.cell_size).ceil() as i32;
+        (column - reach..=column + reach)
+            .flat_map(|column| (row - reach..=row + reach).map(move |row| (column, row)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .map(|index| (*index, nalgebra::distance(&self.points[*index], &position)))
+            .filter(|(_, distance)| *distance <= max_distance)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+    }
+
+    fn cell(point: &Point2<f32>, cell_size: f32) -> (i32, i32) {
+        ((point.x / cell_size).floor() as i32, (point.y / cell_size).floor() as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns reproducible points spread over `[-size, size]` in both directions.
+    fn points(count: usize, size: f32) -> Vec<Point2<f32>> {
+        let mut state = 12345u32;
+        let mut next = move || {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            (state >> 8) as f32 / (1 << 24) as f32 * 2.0 * size - size
+        };
+        (0..count).map(|_| Point2::new(next(), next())).collect()
+    }
+
+    #[test]
+    fn nearest_matches_testing_every_point() {
+        let points = points(500, 100.0);
+        let index = SpatialIndex::new(Clone::clone(&points), 8.0);
+        for position in self::points(200, 110.0) {
+            for max_distance in [3.0, 10.0, 25.0] {
+                let expected = points.iter()
+                    .map(|point| nalgebra::distance(point, &position))
+                    .filter(|distance| *distance <= max_distance)
+                    .min_by(|a, b| a.total_cmp(b));
+                let actual = index.nearest(position, max_distance).map(|index| nalgebra::distance(&points[index], &position));
+                assert_eq!(actual, expected, "nearest to {:?} within {}", position, max_distance);
+            }
+        }
+    }
+
+    #[test]
+    fn points_beyond_the_maximum_distance_are_ignored() {
+        let index = SpatialIndex::new(vec![Point2::new(0.0, 0.0), Point2::new(10.0, 0.0)], 4.0);
+        assert_eq!(index.nearest(Point2::new(6.0, 0.0), 5.0), Some(1));
+        assert_eq!(index.nearest(Point2::new(-3.0, 0.0), 5.0), Some(0));
+        assert_eq!(index.nearest(Point2::new(5.0, 6.0), 5.0), None);
+    }
+
+    #[test]
+    fn non_finite_points_are_never_found() {
+        let index = SpatialIndex::new(vec![Point2::new(f32::NAN, 0.0), Point2::new(1.0, f32::INFINITY), Point2::new(2.0, 2.0)], 1.0);
+        assert_eq!(index.nearest(Point2::new(0.0, 0.0), 10.0), Some(2));
+        assert_eq!(SpatialIndex::new(Vec::new(), 1.0).nearest(Point2::new(0.0, 0.0), 10.0), None);
+    }
+}
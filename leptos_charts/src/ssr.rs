@@ -0,0 +1,339 @@
+use leptos::{create_signal, IntoView, ReadSignal, Scope, View, view};
+use leptos::ssr::render_to_string;
+
+use crate::{AreaChart, BarChart, BarChartData, BoxPlot, BubbleChart, CandlestickChart, CandlestickData, ChartOptions, DistributionData, Heatmap, HeatmapData, Histogram, HistogramData, LineChart, LineChartData, PieChart, PieChartData, RadarChart, RadarChartData, ScatterChart, ScatterChartData, ViolinPlot};
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// The charts that can be rendered to a string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartKind {
+    Bar,
+    Line,
+    Area,
+    Pie,
+    Scatter,
+    Bubble,
+    Heatmap,
+    Histogram,
+    BoxPlot,
+    Violin,
+    Candlestick,
+    Radar,
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum RenderError {
+    #[error("{0:?} charts can not show this data.")]
+    UnsupportedData(ChartKind),
+    #[error("The chart did not render an svg element.")]
+    MissingSvg,
+    #[cfg(feature = "resvg")]
+    #[error("The chart could not be rasterized: {0}")]
+    Rasterize(String),
+}
+
+/// Data shown by one or more kinds of charts.
+pub trait ChartData: Clone + 'static {
+
+    fn supports(chart: ChartKind) -> bool;
+
+    /// Renders `chart`, which must be supported.
+    fn view(cx: Scope, chart: ChartKind, options: ReadSignal<ChartOptions>, data: ReadSignal<Self>) -> View;
+}
+
+impl ChartData for BarChartData {
+
+    fn supports(chart: ChartKind) -> bool {
+        chart == ChartKind::Bar
+    }
+
+    fn view(cx: Scope, _: ChartKind, options: ReadSignal<ChartOptions>, data: ReadSignal<Self>) -> View {
+        view! { cx, <BarChart options=options data=data/> }.into_view(cx)
+    }
+}
+
+impl ChartData for LineChartData {
+
+    fn supports(chart: ChartKind) -> bool {
+        matches!(chart, ChartKind::Line | ChartKind::Area)
+    }
+
+    fn view(cx: Scope, chart: ChartKind, options: ReadSignal<ChartOptions>, data: ReadSignal<Self>) -> View {
+        match chart {
+            ChartKind::Area => view! { cx, <AreaChart options=options data=data/> }.into_view(cx),
+            _ => view! { cx, <LineChart options=options data=data/> }.into_view(cx),
+        }
+    }
+}
+
+impl ChartData for PieChartData {
+
+    fn supports(chart: ChartKind) -> bool {
+        chart == ChartKind::Pie
+    }
+
+    fn view(cx: Scope, _: ChartKind, options: ReadSignal<ChartOptions>, data: ReadSignal<Self>) -> View {
+        view! { cx, <PieChart options=options data=data/> }.into_view(cx)
+    }
+}
+
+impl ChartData for ScatterChartData {
+
+    fn supports(chart: ChartKind) -> bool {
+        matches!(chart, ChartKind::Scatter | ChartKind::Bubble)
+    }
+
+    fn view(cx: Scope, chart: ChartKind, options: ReadSignal<ChartOptions>, data: ReadSignal<Self>) -> View {
+        match chart {
+            ChartKind::Bubble => view! { cx, <BubbleChart options=options data=data/> }.into_view(cx),
+            _ => view! { cx, <ScatterChart options=options data=data/> }.into_view(cx),
+        }
+    }
+}
+
+impl ChartData for HeatmapData {
+
+    fn supports(chart: ChartKind) -> bool {
+        chart == ChartKind::Heatmap
+    }
+
+    fn view(cx: Scope, _: ChartKind, options: ReadSignal<ChartOptions>, data: ReadSignal<Self>) -> View {
+        view! { cx, <Heatmap options=options data=data/> }.into_view(cx)
+    }
+}
+
+impl ChartData for HistogramData {
+
+    fn supports(chart: ChartKind) -> bool {
+        chart == ChartKind::Histogram
+    }
+
+    fn view(cx: Scope, _: ChartKind, options: ReadSignal<ChartOptions>, data: ReadSignal<Self>) -> View {
+        view! { cx, <Histogram options=options data=data/> }.into_view(cx)
+    }
+}
+
+impl ChartData for DistributionData {
+
+    fn supports(chart: ChartKind) -> bool {
+        matches!(chart, ChartKind::BoxPlot | ChartKind::Violin)
+    }
+
+    fn view(cx: Scope, chart: ChartKind, options: ReadSignal<ChartOptions>, data: ReadSignal<Self>) -> View {
+        match chart {
+            ChartKind::Violin => view! { cx, <ViolinPlot options=options data=data/> }.into_view(cx),
+            _ => view! { cx, <BoxPlot options=options data=data/> }.into_view(cx),
+        }
+    }
+}
+
+impl ChartData for CandlestickData {
+
+    fn supports(chart: ChartKind) -> bool {
+        chart == ChartKind::Candlestick
+    }
+
+    fn view(cx: Scope, _: ChartKind, options: ReadSignal<ChartOptions>, data: ReadSignal<Self>) -> View {
+        view! { cx, <CandlestickChart options=options data=data/> }.into_view(cx)
+    }
+}
+
+impl ChartData for RadarChartData {
+
+    fn supports(chart: ChartKind) -> bool {
+        chart == ChartKind::Radar
+    }
+
+    fn view(cx: Scope, _: ChartKind, options: ReadSignal<ChartOptions>, data: ReadSignal<Self>) -> View {
+        view! { cx, <RadarChart options=options data=data/> }.into_view(cx)
+    }
+}
+
+/// Renders a chart to standalone SVG markup without a browser, e.g. to embed it in emails or
+/// reports. Transitions, tooltips and legends are left out, responsive charts take `extent`.
+pub fn render_to_svg_string<D: ChartData>(chart: ChartKind, data: D, options: ChartOptions) -> Result<String, RenderError> {
+    if !D::supports(chart) {
+        return Err(RenderError::UnsupportedData(chart))
+    }
+    let markup = render_to_string(move |cx| {
+        let (options, _) = create_signal(cx, options);
+        let (data, _) = create_signal(cx, data);
+        D::view(cx, chart, options, data)
+    });
+    extract_svg(&markup).ok_or(RenderError::MissingSvg)
+}
+
+/// Renders a chart to a PNG image `scale` times the size of `extent`, with text set in the
+/// fonts installed on the system.
+#[cfg(feature = "resvg")]
+pub fn render_to_png<D: ChartData>(chart: ChartKind, data: D, options: ChartOptions, scale: f32) -> Result<Vec<u8>, RenderError> {
+    use resvg::{tiny_skia, usvg};
+    use resvg::usvg::{TreeParsing, TreeTextToPath};
+
+    let svg = render_to_svg_string(chart, data, options)?;
+    let mut tree = usvg::Tree::from_str(&svg, &usvg::Options::default())
+        .map_err(|error| RenderError::Rasterize(error.to_string()))?;
+    let mut fonts = usvg::fontdb::Database::new();
+    fonts.load_system_fonts();
+    tree.convert_text(&fonts);
+
+    let tree = resvg::Tree::from_usvg(&tree);
+    let size = tree.size.to_int_size().scale_by(scale)
+        .ok_or_else(|| RenderError::Rasterize(format!("invalid scale {}", scale)))?;
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| RenderError::Rasterize(String::from("empty image")))?;
+    tree.render(tiny_skia::Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+    pixmap.encode_png().map_err(|error| RenderError::Rasterize(error.to_string()))
+}
+
+/// Cuts the svg element out of the rendered page and drops the hydration markers of Leptos.
+fn extract_svg(markup: &str) -> Option<String> {
+    let start = markup.find("<svg")?;
+    let end = markup.rfind("</svg>")? + "</svg>".len();
+    let mut svg = String::with_capacity(end - start);
+    let mut rest = &markup[start..end];
+    while let Some(comment) = rest.find("<!--") {
+        svg.push_str(&rest[..comment]);
+        rest = rest[comment..].find("-->").map_or("", |close| &rest[comment + close + "-->".len()..]);
+    }
+    svg.push_str(rest);
+    Some(svg.replacen("<svg", &format!("<svg xmlns=\"{}\"", SVG_NAMESPACE), 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{Candle, PieChartItem, ScatterPoint, ScatterSeries, Series};
+    use super::*;
+
+    const DAY: f64 = 86_400_000.0;
+
+    fn labels(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|label| String::from(*label)).collect()
+    }
+
+    fn series() -> Vec<Series> {
+        vec![
+            Series::new("North", vec![3.0, 4.5, 4.0, 6.0, 7.5]),
+            Series::new("South", vec![2.0, 2.5, 3.5, 3.0, 4.0]),
+        ]
+    }
+
+    fn samples(count: usize, offset: f32) -> Vec<f32> {
+        (0..count).map(|index| offset + ((index * 37) % 23) as f32 * 0.5).collect()
+    }
+
+    fn scatter() -> ScatterChartData {
+        ScatterChartData::new(vec![ScatterSeries::new("Samples", (0..20)
+            .map(|index| ScatterPoint::new(index as f32, ((index * 7) % 11) as f32).with_size(1.0 + (index % 4) as f32))
+            .collect())])
+    }
+
+    /// Renders `chart` and compares it with the snapshot stored under `snapshots/`. Snapshots are
+    /// only written if `UPDATE_SNAPSHOTS` is set, a missing snapshot fails the test.
+    fn assert_snapshot<D: ChartData>(chart: ChartKind, data: D) {
+        let svg = render_to_svg_string(chart, data, ChartOptions::default()).unwrap();
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("snapshots")
+            .join(format!("{:?}.svg", chart).to_lowercase());
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, &svg).unwrap();
+            return
+        }
+        let snapshot = std::fs::read_to_string(&path).unwrap_or_else(|error| {
+            panic!("no snapshot of the {:?} chart at {} ({}), run with UPDATE_SNAPSHOTS=1 to record it:\n{}", chart, path.display(), error, svg)
+        });
+        assert!(svg == snapshot, "{:?} chart differs from {}, run with UPDATE_SNAPSHOTS=1 to accept the change:\n{}", chart, path.display(), svg);
+    }
+
+    #[test]
+    fn bar_chart() {
+        assert_snapshot(ChartKind::Bar, BarChartData::with_series(labels(&["A", "B", "C", "D", "E"]), series()));
+    }
+
+    #[test]
+    fn line_and_area_charts() {
+        let data = LineChartData::new((0..5).map(|day| day as f64 * DAY).collect(), series());
+        assert_snapshot(ChartKind::Line, Clone::clone(&data));
+        assert_snapshot(ChartKind::Area, data);
+    }
+
+    #[test]
+    fn pie_chart() {
+        let items = [("Rust", 5.0), ("Go", 3.0), ("Zig", 1.0)].iter()
+            .map(|(name, value)| PieChartItem { name: String::from(*name), value: *value })
+            .collect();
+        assert_snapshot(ChartKind::Pie, PieChartData { items, caption: String::from("Languages") });
+    }
+
+    #[test]
+    fn scatter_and_bubble_charts() {
+        assert_snapshot(ChartKind::Scatter, scatter());
+        assert_snapshot(ChartKind::Bubble, scatter());
+    }
+
+    #[test]
+    fn heatmap() {
+        let values = (0..3).map(|row| (0..4).map(|column| (row * 4 + column) as f32).collect()).collect();
+        assert_snapshot(ChartKind::Heatmap, HeatmapData::new(labels(&["x", "y", "z"]), labels(&["a", "b", "c", "d"]), values));
+    }
+
+    #[test]
+    fn histogram() {
+        assert_snapshot(ChartKind::Histogram, HistogramData::new(samples(100, 0.0)));
+    }
+
+    #[test]
+    fn box_and_violin_plots() {
+        let data = DistributionData::new(labels(&["A", "B"]), vec![samples(40, 0.0), samples(30, 2.0)]);
+        assert_snapshot(ChartKind::BoxPlot, Clone::clone(&data));
+        assert_snapshot(ChartKind::Violin, data);
+    }
+
+    #[test]
+    fn candlestick_chart() {
+        let candles = (0..6)
+            .map(|day| {
+                let open = 10.0 + ((day * 5) % 7) as f32;
+                let close = 10.0 + ((day * 3) % 7) as f32;
+                Candle::new(day as f64 * DAY, open, open.max(close) + 1.0, open.min(close) - 1.0, close).with_volume(100.0 + day as f32 * 10.0)
+            })
+            .collect();
+        assert_snapshot(ChartKind::Candlestick, CandlestickData::new(candles));
+    }
+
+    #[test]
+    fn radar_chart() {
+        assert_snapshot(ChartKind::Radar, RadarChartData::new(labels(&["A", "B", "C", "D", "E"]), series()));
+    }
+
+    #[test]
+    fn unsupported_data_is_rejected() {
+        let result = render_to_svg_string(ChartKind::Pie, HistogramData::new(vec![1.0]), ChartOptions::default());
+        assert!(matches!(result, Err(RenderError::UnsupportedData(ChartKind::Pie))));
+    }
+
+    #[test]
+    fn extract_svg_strips_comments_and_adds_the_namespace() {
+        let markup = "<div><!-- hk=0-0 --><svg width=\"10\"><!----><g><!--leptos-view|x-->text</g><!-- /hk --></svg></div><!-- end -->";
+        assert_eq!(
+            extract_svg(markup).unwrap(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"10\"><g>text</g></svg>",
+        );
+    }
+
+    #[test]
+    fn extract_svg_keeps_nested_svg_elements() {
+        let markup = "<svg><svg><rect/></svg></svg><p>after</p>";
+        assert_eq!(extract_svg(markup).unwrap(), "<svg xmlns=\"http://www.w3.org/2000/svg\"><svg><rect/></svg></svg>");
+    }
+
+    #[test]
+    fn extract_svg_requires_an_svg_element() {
+        assert_eq!(extract_svg("<div>no chart</div>"), None);
+        assert_eq!(extract_svg("<svg width=\"10\">"), None);
+    }
+}
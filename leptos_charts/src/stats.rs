@@ -0,0 +1,345 @@
+/// Upper bound on the number of bins, guarding against widths tiny relative to the range.
+const MAX_BINS: usize = 1000;
+
+/// Rule choosing the bins of a histogram from the samples.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Binning {
+    /// `log2(n) + 1` bins, suited to roughly normal data of moderate size.
+    #[default]
+    Sturges,
+    /// Bins `2 IQR / n^(1/3)` wide, robust against outliers.
+    FreedmanDiaconis,
+    /// Bins `3.49 σ / n^(1/3)` wide, optimal for normal data.
+    Scott,
+    /// A fixed number of bins spanning the samples.
+    Count(usize),
+    /// Bins of a fixed width, aligned to multiples of it.
+    Width(f32),
+}
+
+/// How far the whiskers of a box plot reach, samples beyond them are drawn as outliers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Whiskers {
+    /// To the furthest samples within the given multiple of the interquartile range from the box,
+    /// `1.5` for Tukey's box plot.
+    Iqr(f32),
+    /// To the lowest and the highest sample, leaving no outliers.
+    MinMax,
+}
+
+impl Default for Whiskers {
+    fn default() -> Self {
+        Whiskers::Iqr(1.5)
+    }
+}
+
+/// The five numbers drawn by a box plot and the samples beyond its whiskers.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BoxStats {
+    pub lower_whisker: f32,
+    pub first_quartile: f32,
+    pub median: f32,
+    pub third_quartile: f32,
+    pub upper_whisker: f32,
+    pub outliers: Vec<f32>,
+}
+
+/// A half-open interval `start..end` of a histogram and the number of samples within it. The last
+/// bin also contains samples equal to its end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bin {
+    pub start: f32,
+    pub end: f32,
+    pub count: usize,
+}
+
+impl Binning {
+
+    /// Sorts the finite samples into bins, e.g. to show them in a [`crate::BarChart`].
+    pub fn bins(&self, samples: &[f32]) -> Vec<Bin> {
+        let sorted = sorted(samples);
+        let (Some(&min), Some(&max)) = (sorted.first(), sorted.last()) else {
+            return Vec::new()
+        };
+        if min == max {
+            return vec![Bin { start: min - 0.5, end: max + 0.5, count: sorted.len() }]
+        }
+        let sturges = (sorted.len() as f32).log2().ceil() as usize + 1;
+        // Bins of a fixed count span the samples exactly, widths from a rule are aligned to round edges.
+        let fixed = |count: usize| (min, (max - min) / count as f32, count);
+        let aligned = |width: f32| {
+            // Rules based on the spread fail for samples that mostly share a value.
+            let width = if width.is_finite() && width > 0.0 { width } else { (max - min) / sturges as f32 };
+            let width = width.max((max - min) / MAX_BINS as f32);
+            let start = (min / width).floor() * width;
+            (start, width, (((max - start) / width).ceil() as usize).clamp(1, MAX_BINS))
+        };
+        let (start, width, count) = match *self {
+            Binning::Sturges => fixed(sturges),
+            Binning::Count(count) => fixed(count.clamp(1, MAX_BINS)),
+            Binning::FreedmanDiaconis => aligned(2.0 * iqr(&sorted) / (sorted.len() as f32).cbrt()),
+            Binning::Scott => aligned(3.49 * standard_deviation(&sorted) / (sorted.len() as f32).cbrt()),
+            Binning::Width(width) => aligned(width),
+        };
+
+        let mut bins = (0..count)
+            .map(|index| Bin { start: start + index as f32 * width, end: start + (index + 1) as f32 * width, count: 0 })
+            .collect::<Vec<_>>();
+        for sample in sorted {
+            let index = (((sample - start) / width).floor() as usize).min(count - 1);
+            bins[index].count += 1;
+        }
+        bins
+    }
+}
+
+impl BoxStats {
+
+    /// Computes the statistics of sorted samples, `None` if there are none.
+    pub fn new(sorted: &[f32], whiskers: Whiskers) -> Option<Self> {
+        let (&min, &max) = (sorted.first()?, sorted.last()?);
+        let (first_quartile, third_quartile) = (quantile(sorted, 0.25), quantile(sorted, 0.75));
+        let (low, high) = match whiskers {
+            Whiskers::Iqr(factor) => {
+                let reach = (third_quartile - first_quartile) * factor;
+                (first_quartile - reach, third_quartile + reach)
+            },
+            Whiskers::MinMax => (min, max),
+        };
+        let within = sorted.iter().cloned().filter(|sample| *sample >= low && *sample <= high);
+        Some(BoxStats {
+            lower_whisker: within.clone().next().unwrap_or(first_quartile),
+            first_quartile,
+            median: quantile(sorted, 0.5),
+            third_quartile,
+            upper_whisker: within.last().unwrap_or(third_quartile),
+            outliers: sorted.iter().cloned().filter(|sample| *sample < low || *sample > high).collect(),
+        })
+    }
+}
+
+/// Returns the finite samples in ascending order.
+pub(crate) fn sorted(samples: &[f32]) -> Vec<f32> {
+    let mut sorted = samples.iter()
+        .cloned()
+        .filter(|sample| sample.is_finite())
+        .collect::<Vec<_>>();
+    sorted.sort_by(f32::total_cmp);
+    sorted
+}
+
+/// Returns the `p`-quantile of sorted samples, interpolating linearly between neighbouring samples.
+pub(crate) fn quantile(sorted: &[f32], p: f32) -> f32 {
+    match sorted.len() {
+        0 => f32::NAN,
+        1 => sorted[0],
+        len => {
+            let position = p.clamp(0.0, 1.0) * (len - 1) as f32;
+            let index = (position.floor() as usize).min(len - 2);
+            sorted[index] + (sorted[index + 1] - sorted[index]) * (position - index as f32)
+        },
+    }
+}
+
+/// Returns the interquartile range of sorted samples.
+pub(crate) fn iqr(sorted: &[f32]) -> f32 {
+    quantile(sorted, 0.75) - quantile(sorted, 0.25)
+}
+
+/// Returns the sample standard deviation.
+pub(crate) fn standard_deviation(samples: &[f32]) -> f32 {
+    if samples.len() < 2 {
+        return 0.0
+    }
+    let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+    let variance = samples.iter().map(|sample| (sample - mean).powi(2)).sum::<f32>() / (samples.len() - 1) as f32;
+    variance.sqrt()
+}
+
+/// Returns the bandwidth of a Gaussian kernel for sorted samples by Silverman's rule of thumb.
+pub(crate) fn silverman_bandwidth(sorted: &[f32]) -> f32 {
+    let deviation = standard_deviation(sorted);
+    let spread = deviation.min(iqr(sorted) / 1.34);
+    // The interquartile range vanishes for samples that mostly share a value.
+    let spread = if spread > 0.0 { spread } else { deviation };
+    let bandwidth = 0.9 * spread * (sorted.len() as f32).powf(-0.2);
+    if bandwidth.is_finite() && bandwidth > 0.0 { bandwidth } else { 1.0 }
+}
+
+/// Estimates the probability density of the samples at `at` with a Gaussian kernel.
+pub(crate) fn kernel_density(samples: &[f32], at: f32, bandwidth: f32) -> f32 {
+    if samples.is_empty() || bandwidth <= 0.0 {
+        return 0.0
+    }
+    let sum = samples.iter()
+        .map(|sample| (-0.5 * ((at - sample) / bandwidth).powi(2)).exp())
+        .sum::<f32>();
+    sum / (samples.len() as f32 * bandwidth * (2.0 * std::f32::consts::PI).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() <= 1e-4 * expected.abs().max(1.0), "{} != {}", actual, expected);
+    }
+
+    fn total(bins: &[Bin]) -> usize {
+        bins.iter().map(|bin| bin.count).sum()
+    }
+
+    /// Deterministic samples spread over `[offset, offset + scale)`.
+    fn samples(count: usize, offset: f32, scale: f32) -> Vec<f32> {
+        (0..count).map(|index| offset + ((index as f32 * 12.9898).sin() * 43758.547).fract().abs() * scale).collect()
+    }
+
+    #[test]
+    fn sturges_spans_the_samples() {
+        let samples = (0..100).map(|index| index as f32).collect::<Vec<_>>();
+        let bins = Binning::Sturges.bins(&samples);
+        assert_eq!(bins.len(), 8);
+        assert_eq!(bins[0].start, 0.0);
+        assert_close(bins[7].end, 99.0);
+        assert_eq!(total(&bins), 100);
+    }
+
+    #[test]
+    fn count_returns_exactly_the_requested_bins() {
+        let bins = Binning::Count(20).bins(&[0.67, 1.3966666]);
+        assert_eq!(bins.len(), 20);
+        assert_eq!(bins[19].count, 1);
+        for count in 1..40 {
+            for seed in 0..50 {
+                let samples = samples(seed + 2, seed as f32 * 0.37 - 5.0, 0.1 + seed as f32 * 1.3);
+                let bins = Binning::Count(count).bins(&samples);
+                assert_eq!(bins.len(), count);
+                assert_eq!(total(&bins), samples.len());
+            }
+        }
+    }
+
+    #[test]
+    fn count_of_zero_yields_one_bin() {
+        let bins = Binning::Count(0).bins(&[1.0, 2.0, 3.0]);
+        assert_eq!(bins.len(), 1);
+        assert_eq!(bins[0].count, 3);
+    }
+
+    #[test]
+    fn width_aligns_bins_to_multiples() {
+        let bins = Binning::Width(2.5).bins(&[1.0, 9.0, 3.2]);
+        let edges = bins.iter().map(|bin| (bin.start, bin.end)).collect::<Vec<_>>();
+        assert_eq!(edges, vec![(0.0, 2.5), (2.5, 5.0), (5.0, 7.5), (7.5, 10.0)]);
+        assert_eq!(bins.iter().map(|bin| bin.count).collect::<Vec<_>>(), vec![1, 1, 0, 1]);
+    }
+
+    #[test]
+    fn last_bin_contains_its_end() {
+        let bins = Binning::Width(2.0).bins(&[0.0, 10.0]);
+        assert_eq!(bins.len(), 5);
+        assert_eq!(bins[4].count, 1);
+    }
+
+    #[test]
+    fn freedman_diaconis_uses_the_interquartile_range() {
+        let samples = (0..100).map(|index| index as f32).collect::<Vec<_>>();
+        let bins = Binning::FreedmanDiaconis.bins(&samples);
+        let width = 2.0 * 49.5 / 100f32.cbrt();
+        assert_close(bins[0].end - bins[0].start, width);
+        assert_eq!(bins[0].start, 0.0);
+        assert_eq!(bins.len(), (99.0 / width).ceil() as usize);
+        assert_eq!(total(&bins), 100);
+    }
+
+    #[test]
+    fn scott_uses_the_standard_deviation() {
+        let samples = samples(500, 10.0, 40.0);
+        let bins = Binning::Scott.bins(&samples);
+        let width = 3.49 * standard_deviation(&samples) / 500f32.cbrt();
+        assert_close(bins[0].end - bins[0].start, width);
+        assert_eq!(total(&bins), 500);
+    }
+
+    #[test]
+    fn rules_fall_back_to_sturges_without_spread() {
+        let bins = Binning::FreedmanDiaconis.bins(&[1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 5.0]);
+        assert_eq!(bins.len(), 4);
+        assert_eq!(bins[0].count, 7);
+        assert_eq!(bins[3].count, 1);
+    }
+
+    #[test]
+    fn equal_samples_share_one_bin() {
+        assert_eq!(Binning::Scott.bins(&[2.0, 2.0, 2.0]), vec![Bin { start: 1.5, end: 2.5, count: 3 }]);
+    }
+
+    #[test]
+    fn non_finite_samples_are_ignored() {
+        assert!(Binning::Sturges.bins(&[f32::NAN, f32::INFINITY]).is_empty());
+        assert_eq!(total(&Binning::Sturges.bins(&[1.0, f32::NAN, 2.0])), 2);
+    }
+
+    #[test]
+    fn quantiles_interpolate_between_samples() {
+        let sorted = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(quantile(&sorted, 0.0), 1.0);
+        assert_eq!(quantile(&sorted, 0.5), 2.5);
+        assert_eq!(quantile(&sorted, 1.0), 4.0);
+        assert_close(quantile(&sorted, 0.25), 1.75);
+        assert_eq!(quantile(&[7.0], 0.3), 7.0);
+        assert!(quantile(&[], 0.5).is_nan());
+        assert_close(iqr(&sorted), 1.5);
+    }
+
+    #[test]
+    fn standard_deviation_of_samples() {
+        assert_close(standard_deviation(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]), 2.1380899);
+        assert_eq!(standard_deviation(&[3.0]), 0.0);
+    }
+
+    #[test]
+    fn box_stats_separate_outliers() {
+        let sorted = sorted(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 30.0, -20.0]);
+        let stats = BoxStats::new(&sorted, Whiskers::default()).unwrap();
+        assert_close(stats.first_quartile, 2.25);
+        assert_close(stats.median, 4.5);
+        assert_close(stats.third_quartile, 6.75);
+        assert_eq!((stats.lower_whisker, stats.upper_whisker), (1.0, 8.0));
+        assert_eq!(stats.outliers, vec![-20.0, 30.0]);
+    }
+
+    #[test]
+    fn min_max_whiskers_leave_no_outliers() {
+        let sorted = sorted(&[1.0, 2.0, 3.0, 30.0, -20.0]);
+        let stats = BoxStats::new(&sorted, Whiskers::MinMax).unwrap();
+        assert_eq!((stats.lower_whisker, stats.upper_whisker), (-20.0, 30.0));
+        assert!(stats.outliers.is_empty());
+        assert_eq!(BoxStats::new(&[], Whiskers::MinMax), None);
+    }
+
+    #[test]
+    fn kernel_density_integrates_to_one() {
+        let sorted = sorted(&samples(200, -3.0, 6.0));
+        let bandwidth = silverman_bandwidth(&sorted);
+        let step = 0.01;
+        let integral = (-1500..1500).map(|index| kernel_density(&sorted, index as f32 * step, bandwidth) * step).sum::<f32>();
+        assert_close(integral, 1.0);
+    }
+
+    #[test]
+    fn kernel_density_of_a_single_sample_is_a_gaussian() {
+        let peak = 1.0 / (2.0 * std::f32::consts::PI).sqrt();
+        assert_close(kernel_density(&[0.0], 0.0, 1.0), peak);
+        assert_close(kernel_density(&[0.0], 1.0, 1.0), peak * (-0.5f32).exp());
+        assert_eq!(kernel_density(&[], 0.0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn silverman_bandwidth_stays_positive() {
+        let sorted = (0..100).map(|index| index as f32).collect::<Vec<_>>();
+        let spread = standard_deviation(&sorted).min(iqr(&sorted) / 1.34);
+        assert_close(silverman_bandwidth(&sorted), 0.9 * spread * 100f32.powf(-0.2));
+        assert_eq!(silverman_bandwidth(&[2.0, 2.0, 2.0]), 1.0);
+    }
+}
@@ -0,0 +1,106 @@
+use leptos::{create_signal, provide_context, use_context, Scope, Signal};
+
+use crate::{ChartOptions, ColorPallet, ColorRamp};
+
+/// Colours, fonts and strokes shared by all charts. Charts use the theme provided with
+/// [`provide_theme`] by an enclosing component, [`Theme::light`] if there is none.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// Colours of series and categories, unless a chart's options name a pallet of their own.
+    pub categorical: ColorPallet,
+    /// Ramp of values ordered from low to high.
+    pub sequential: ColorRamp,
+    /// Ramp of values deviating in both directions from a neutral middle.
+    pub diverging: ColorRamp,
+    pub background: String,
+    /// Background of overlays like tooltips, which must not be transparent.
+    pub surface: String,
+    /// Colour of axis lines and ticks.
+    pub axis: String,
+    pub grid: String,
+    pub text: String,
+    /// Colour of outlines and rings marking selected or hovered marks.
+    pub highlight: String,
+    /// Colour of rising prices, e.g. candles closing above their opening.
+    pub up: String,
+    /// Colour of falling prices.
+    pub down: String,
+    pub font_family: String,
+    /// Size in pixels of axis labels, legend entries and tooltips.
+    pub font_size: f32,
+    /// Size in pixels of captions and titles.
+    pub title_font_size: f32,
+    pub axis_stroke_width: f32,
+    pub grid_stroke_width: f32,
+    /// Width of the lines of line charts.
+    pub line_width: f32,
+}
+
+impl Theme {
+
+    pub fn light() -> Self {
+        Theme {
+            categorical: ColorPallet::Default,
+            sequential: ColorRamp::Blues,
+            diverging: ColorRamp::RedBlue,
+            background: String::from("transparent"),
+            surface: String::from("#ffffff"),
+            axis: String::from("#000000"),
+            grid: String::from("#e0e0e0"),
+            text: String::from("#000000"),
+            highlight: String::from("#000000"),
+            up: String::from("#26a69a"),
+            down: String::from("#ef5350"),
+            font_family: String::from("sans-serif"),
+            font_size: 10.0,
+            title_font_size: 13.0,
+            axis_stroke_width: 1.0,
+            grid_stroke_width: 1.0,
+            line_width: 2.0,
+        }
+    }
+
+    pub fn dark() -> Self {
+        Theme {
+            categorical: ColorPallet::Material,
+            sequential: ColorRamp::Viridis,
+            background: String::from("#1e1e1e"),
+            surface: String::from("#2b2b2b"),
+            axis: String::from("#c8c8c8"),
+            grid: String::from("#3a3a3a"),
+            text: String::from("#e6e6e6"),
+            highlight: String::from("#ffffff"),
+            ..Theme::light()
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::light()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ThemeContext(Signal<Theme>);
+
+/// Makes `theme` the theme of all charts rendered within the current scope and its children.
+pub fn provide_theme(cx: Scope, theme: impl Into<Signal<Theme>>) {
+    provide_context(cx, ThemeContext(theme.into()));
+}
+
+/// Returns the theme provided by an enclosing component or the default theme.
+pub fn use_theme(cx: Scope) -> Signal<Theme> {
+    match use_context::<ThemeContext>(cx) {
+        Some(ThemeContext(theme)) => theme,
+        None => create_signal(cx, Theme::default()).0.into(),
+    }
+}
+
+impl ChartOptions {
+
+    /// Returns the pallet of the options if they name one, the categorical pallet of the theme otherwise.
+    pub(crate) fn categorical_pallet(&self, theme: &Theme) -> ColorPallet {
+        self.color_pallet.unwrap_or(theme.categorical)
+    }
+}
@@ -0,0 +1,434 @@
+use std::f32::consts::PI;
+use std::rc::Rc;
+
+use leptos::{component, create_memo, create_rw_signal, IntoView, Memo, RwSignal, Scope, SignalGet, SignalGetUntracked, SignalSet, SignalWith, StoredValue, store_value, View, view};
+use leptos::ev::{KeyboardEvent, MouseEvent};
+use nalgebra::{Point2, Vector2};
+
+use crate::a11y::describe_value;
+use crate::interaction::Selection;
+use crate::pointer::pointer_position;
+use crate::theme::use_theme;
+
+/// Distance in pixels between the pointer and the tooltip.
+const TOOLTIP_OFFSET: f32 = 12.0;
+const CROSSHAIR_RING_RADIUS: f32 = 5.0;
+const CROSSHAIR_OPACITY: f32 = 0.5;
+
+/// The data behind a mark of a chart, as shown in tooltips.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Datum {
+    /// Index of the series, `0` for charts with a single series.
+    pub series: usize,
+    /// Index of the value within the series.
+    pub index: usize,
+    pub series_name: String,
+    /// Label of the domain value, e.g. the category of a bar.
+    pub label: String,
+    pub value: f32,
+    pub color: String,
+}
+
+impl Datum {
+
+    /// Describes the datum in words, e.g. for screen readers.
+    pub fn description(&self) -> String {
+        describe_value(&self.series_name, &self.label, self.value)
+    }
+}
+
+/// Area of a mark in screen space the pointer is tested against.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum HitShape {
+    Rect { min: Point2<f32>, max: Point2<f32> },
+    Wedge { center: Point2<f32>, inner_radius: f32, outer_radius: f32, start_angle: f32, end_angle: f32 },
+    Point { center: Point2<f32> },
+}
+
+impl HitShape {
+
+    /// Returns the distance between `position` and the shape, zero if the shape contains it.
+    fn distance(&self, position: &Point2<f32>, metric: Metric) -> f32 {
+        match self {
+            HitShape::Rect { min, max } => {
+                let dx = (min.x - position.x).max(position.x - max.x).max(0.0);
+                let dy = (min.y - position.y).max(position.y - max.y).max(0.0);
+                match metric {
+                    Metric::Euclidean => dx.hypot(dy),
+                    Metric::Horizontal => dx,
+                }
+            },
+            HitShape::Wedge { center, inner_radius, outer_radius, start_angle, end_angle } => {
+                let offset = position - center;
+                let radius = offset.norm();
+                // Angles are measured clockwise from twelve o'clock like the wedges are drawn.
+                let angle = (offset.x.atan2(-offset.y) - start_angle).rem_euclid(2.0 * PI) + start_angle;
+                let inside_angle = angle >= *start_angle && angle <= *end_angle;
+                if inside_angle && radius >= *inner_radius && radius <= *outer_radius {
+                    0.0
+                }
+                else {
+                    f32::INFINITY
+                }
+            },
+            HitShape::Point { center } => match metric {
+                Metric::Euclidean => nalgebra::distance(center, position),
+                Metric::Horizontal => (center.x - position.x).abs(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Metric {
+    Euclidean,
+    /// Only the horizontal distance counts, ties are resolved by the vertical distance.
+    Horizontal,
+}
+
+/// How the target under the pointer is found.
+#[derive(Clone)]
+pub(crate) enum HitTest {
+    /// The target nearest to the pointer within `max_distance` pixels.
+    Nearest { max_distance: f32, metric: Metric },
+    /// Looks the target up by the position of the pointer, e.g. in a spatial index.
+    Lookup(Rc<dyn Fn(Point2<f32>) -> Option<usize>>),
+}
+
+impl HitTest {
+
+    pub fn nearest(max_distance: f32, metric: Metric) -> Self {
+        HitTest::Nearest { max_distance, metric }
+    }
+}
+
+/// A hoverable mark of a chart.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Target {
+    /// Position in screen space the tooltip points at when the mark is focused by keyboard.
+    pub anchor: Point2<f32>,
+    pub shape: HitShape,
+    pub datum: Datum,
+}
+
+/// The hoverable marks of a chart in the order they are navigated by keyboard.
+#[derive(Clone, Copy)]
+pub(crate) enum Targets {
+    Eager(Memo<Vec<Target>>),
+    /// Marks hit at their anchor whose datum is only built once it is shown, for charts with
+    /// too many marks to build a datum for every one of them.
+    Lazy {
+        marks: Memo<Vec<(Point2<f32>, Selection)>>,
+        datum: StoredValue<Rc<dyn Fn(usize) -> Option<Datum>>>,
+    },
+}
+
+impl From<Memo<Vec<Target>>> for Targets {
+    fn from(targets: Memo<Vec<Target>>) -> Self {
+        Targets::Eager(targets)
+    }
+}
+
+impl Targets {
+
+    pub fn lazy(cx: Scope, marks: Memo<Vec<(Point2<f32>, Selection)>>, datum: impl Fn(usize) -> Option<Datum> + 'static) -> Self {
+        Targets::Lazy { marks, datum: store_value(cx, Rc::new(datum)) }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Targets::Eager(targets) => targets.with(Vec::len),
+            Targets::Lazy { marks, .. } => marks.with(Vec::len),
+        }
+    }
+
+    pub fn anchor(&self, index: usize) -> Option<Point2<f32>> {
+        match self {
+            Targets::Eager(targets) => targets.with(|targets| targets.get(index).map(|target| target.anchor)),
+            Targets::Lazy { marks, .. } => marks.with(|marks| marks.get(index).map(|(anchor, _)| *anchor)),
+        }
+    }
+
+    pub fn datum(&self, index: usize) -> Option<Datum> {
+        match self {
+            Targets::Eager(targets) => targets.with(|targets| targets.get(index).map(|target| Clone::clone(&target.datum))),
+            Targets::Lazy { datum, .. } => datum.with_value(|datum| datum(index)),
+        }
+    }
+
+    /// Returns the index of the mark identified by `selection`.
+    pub fn position_of(&self, selection: Selection) -> Option<usize> {
+        match self {
+            Targets::Eager(targets) => {
+                targets.with(|targets| targets.iter().position(|target| Selection::from(&target.datum) == selection))
+            },
+            Targets::Lazy { marks, .. } => marks.with(|marks| marks.iter().position(|(_, other)| *other == selection)),
+        }
+    }
+
+    /// Returns the index of the mark nearest to `position` within `max_distance`.
+    pub fn nearest(&self, position: Point2<f32>, max_distance: f32, metric: Metric) -> Option<usize> {
+        match self {
+            Targets::Eager(targets) => targets.with(|targets| {
+                nearest(targets.iter().map(|target| (Clone::clone(&target.shape), target.anchor)), position, max_distance, metric)
+            }),
+            Targets::Lazy { marks, .. } => marks.with(|marks| {
+                nearest(marks.iter().map(|(anchor, _)| (HitShape::Point { center: *anchor }, *anchor)), position, max_distance, metric)
+            }),
+        }
+    }
+}
+
+/// Returns the index of the shape nearest to `position` within `max_distance`, shapes are given
+/// along with their anchor.
+fn nearest(shapes: impl Iterator<Item = (HitShape, Point2<f32>)>, position: Point2<f32>, max_distance: f32, metric: Metric) -> Option<usize> {
+    shapes
+        .enumerate()
+        .map(|(index, (shape, anchor))| {
+            let distance = shape.distance(&position, metric);
+            let tie_breaker = match metric {
+                Metric::Euclidean => 0.0,
+                Metric::Horizontal => (anchor.y - position.y).abs(),
+            };
+            (index, distance, tie_breaker)
+        })
+        .filter(|(_, distance, _)| *distance <= max_distance)
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(a.2.total_cmp(&b.2)))
+        .map(|(index, _, _)| index)
+}
+
+/// Renders the content of a tooltip for the hovered or focused datum.
+#[derive(Clone)]
+pub struct TooltipRenderer(Rc<dyn Fn(Scope, Datum) -> View>);
+
+impl<F> From<F> for TooltipRenderer
+where F: Fn(Scope, Datum) -> View + 'static {
+    fn from(function: F) -> Self {
+        TooltipRenderer(Rc::new(function))
+    }
+}
+
+/// Tracks which target of a chart is active, either under the pointer or focused by keyboard.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Hover {
+    pub active: RwSignal<Option<usize>>,
+    /// Position of the pointer, `None` while navigating by keyboard.
+    pub pointer: RwSignal<Option<Point2<f32>>>,
+}
+
+impl Hover {
+
+    pub fn new(cx: Scope) -> Self {
+        Hover {
+            active: create_rw_signal(cx, None),
+            pointer: create_rw_signal(cx, None),
+        }
+    }
+
+    pub fn on_pointer_move(&self, event: &MouseEvent, targets: Targets, hit_test: &HitTest) {
+        let position = pointer_position(event);
+        let hit = position.and_then(|position| match hit_test {
+            HitTest::Nearest { max_distance, metric } => targets.nearest(position, *max_distance, *metric),
+            HitTest::Lookup(lookup) => lookup(position),
+        });
+        self.set(hit, position);
+    }
+
+    pub fn set(&self, active: Option<usize>, pointer: Option<Point2<f32>>) {
+        if self.active.get_untracked() != active {
+            self.active.set(active);
+        }
+        self.pointer.set(pointer);
+    }
+
+    pub fn on_pointer_leave(&self) {
+        self.set(None, None);
+    }
+
+    /// Moves the focus between the targets with the arrow keys, `Escape` clears it.
+    pub fn on_key_down(&self, event: &KeyboardEvent, count: usize) {
+        if count == 0 {
+            return
+        }
+        let active = self.active.get_untracked();
+        let next = match event.key().as_str() {
+            "ArrowRight" | "ArrowDown" => Some(active.map_or(0, |index| (index + 1) % count)),
+            "ArrowLeft" | "ArrowUp" => Some(active.map_or(count - 1, |index| (index + count - 1) % count)),
+            "Home" => Some(0),
+            "End" => Some(count - 1),
+            "Escape" => None,
+            _ => return,
+        };
+        event.prevent_default();
+        self.set(next, None);
+    }
+
+    pub fn is_active(&self, index: usize) -> bool {
+        self.active.get() == Some(index)
+    }
+
+    /// Returns the active target while it was reached by keyboard rather than by the pointer.
+    pub fn focused(&self) -> Option<usize> {
+        self.active.get().filter(|_| self.pointer.with(Option::is_none))
+    }
+}
+
+#[component]
+pub(crate) fn Tooltip(
+    cx: Scope,
+    hover: Hover,
+    targets: Targets,
+    content: Option<TooltipRenderer>,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+
+    // The content is only rendered again when another mark becomes active, moving the pointer
+    // over the same mark only moves the tooltip.
+    let datum = create_memo(cx, move |_| hover.active.get().and_then(|index| targets.datum(index)));
+
+    let style = move || {
+        let anchor = hover.active.get().and_then(|index| targets.anchor(index));
+        let position = hover.pointer.get().or(anchor).unwrap_or_else(Point2::origin);
+        let position = position - Vector2::new(0.0, TOOLTIP_OFFSET);
+        theme.with(|theme| format!(
+            "position: absolute; left: {}px; top: {}px; transform: translate(-50%, -100%); pointer-events: none; \
+            background: {}; color: {}; border: 1px solid {}; border-radius: 3px; padding: 4px 8px; \
+            font-family: {}; font-size: {}px; white-space: nowrap; box-shadow: 0 1px 3px rgba(0, 0, 0, 0.2);",
+            position.x, position.y, theme.surface, theme.text, theme.grid, theme.font_family, theme.font_size * 1.2
+        ))
+    };
+
+    move || {
+        datum.get().map(|datum| {
+            let body = match &content {
+                Some(TooltipRenderer(render)) => render(cx, datum),
+                None => default_content(cx, datum),
+            };
+            view! { cx,
+                <div role="tooltip" style=style>{body}</div>
+            }
+        })
+    }
+}
+
+fn default_content(cx: Scope, datum: Datum) -> View {
+    let swatch = format!("display: inline-block; width: 8px; height: 8px; margin-right: 4px; background: {};", datum.color);
+    let series_name = (!datum.series_name.is_empty()).then(|| view! { cx, <div>{datum.series_name}</div> });
+    view! { cx,
+        <div>
+            {series_name}
+            <span style=swatch></span>
+            {datum.label}": "
+            <b>{format!("{}", datum.value)}</b>
+        </div>
+    }.into_view(cx)
+}
+
+/// Horizontal and vertical guide lines through the active target, limited to the plot, and a ring
+/// around the target itself.
+#[component]
+pub(crate) fn Crosshair(
+    cx: Scope,
+    hover: Hover,
+    targets: Targets,
+    /// Top-left and bottom-right corner of the plot in screen space.
+    bounds: Memo<(Point2<f32>, Point2<f32>)>,
+    #[prop(optional)]
+    vertical_only: bool,
+) -> impl IntoView {
+
+    let theme = use_theme(cx);
+
+    move || {
+        hover.active.get()
+            .and_then(|index| targets.anchor(index))
+            .map(|anchor| {
+                let (min, max) = bounds.get();
+                let stroke = theme.with(|theme| Clone::clone(&theme.axis));
+                let horizontal = (!vertical_only).then(|| view! { cx,
+                    <line x1=min.x y1=anchor.y x2=max.x y2=anchor.y stroke=Clone::clone(&stroke) stroke-opacity=CROSSHAIR_OPACITY stroke-dasharray="4 4"/>
+                });
+                view! { cx,
+                    <g pointer-events="none">
+                        <line x1=anchor.x y1=min.y x2=anchor.x y2=max.y stroke=Clone::clone(&stroke) stroke-opacity=CROSSHAIR_OPACITY stroke-dasharray="4 4"/>
+                        {horizontal}
+                        <circle cx=anchor.x cy=anchor.y r=CROSSHAIR_RING_RADIUS fill="none" stroke=stroke stroke-opacity=CROSSHAIR_OPACITY stroke-width="1.5"/>
+                    </g>
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f32, y: f32) -> HitShape {
+        HitShape::Point { center: Point2::new(x, y) }
+    }
+
+    fn wedge(start_angle: f32, end_angle: f32) -> HitShape {
+        HitShape::Wedge { center: Point2::new(50.0, 50.0), inner_radius: 10.0, outer_radius: 40.0, start_angle, end_angle }
+    }
+
+    #[test]
+    fn distance_to_points_and_rects() {
+        let position = Point2::new(3.0, 4.0);
+        assert_eq!(point(0.0, 0.0).distance(&position, Metric::Euclidean), 5.0);
+        assert_eq!(point(0.0, 0.0).distance(&position, Metric::Horizontal), 3.0);
+
+        let rect = HitShape::Rect { min: Point2::new(0.0, 0.0), max: Point2::new(10.0, 20.0) };
+        assert_eq!(rect.distance(&Point2::new(5.0, 5.0), Metric::Euclidean), 0.0);
+        assert_eq!(rect.distance(&Point2::new(13.0, 24.0), Metric::Euclidean), 5.0);
+        assert_eq!(rect.distance(&Point2::new(13.0, 24.0), Metric::Horizontal), 3.0);
+        assert_eq!(rect.distance(&Point2::new(5.0, 30.0), Metric::Euclidean), 10.0);
+        assert_eq!(rect.distance(&Point2::new(5.0, 30.0), Metric::Horizontal), 0.0);
+        assert_eq!(rect.distance(&Point2::new(-4.0, -3.0), Metric::Euclidean), 5.0);
+    }
+
+    #[test]
+    fn wedges_are_hit_between_their_angles_and_radii() {
+        // From twelve to three o'clock.
+        let quarter = wedge(0.0, PI / 2.0);
+        assert_eq!(quarter.distance(&Point2::new(70.0, 30.0), Metric::Euclidean), 0.0);
+        assert_eq!(quarter.distance(&Point2::new(30.0, 50.0), Metric::Euclidean), f32::INFINITY);
+        assert_eq!(quarter.distance(&Point2::new(55.0, 45.0), Metric::Euclidean), f32::INFINITY);
+        assert_eq!(quarter.distance(&Point2::new(80.0, 10.0), Metric::Euclidean), f32::INFINITY);
+
+        // From nine to three o'clock across twelve o'clock, where angles wrap around.
+        let top = wedge(1.5 * PI, 2.5 * PI);
+        assert_eq!(top.distance(&Point2::new(50.0, 20.0), Metric::Euclidean), 0.0);
+        assert_eq!(top.distance(&Point2::new(30.0, 40.0), Metric::Euclidean), 0.0);
+        assert_eq!(top.distance(&Point2::new(70.0, 40.0), Metric::Euclidean), 0.0);
+        assert_eq!(top.distance(&Point2::new(50.0, 80.0), Metric::Euclidean), f32::INFINITY);
+    }
+
+    fn shapes(shapes: &[HitShape]) -> impl Iterator<Item = (HitShape, Point2<f32>)> + '_ {
+        shapes.iter().map(|shape| {
+            let anchor = match shape {
+                HitShape::Point { center } => *center,
+                _ => Point2::origin(),
+            };
+            (Clone::clone(shape), anchor)
+        })
+    }
+
+    #[test]
+    fn nearest_shape_within_the_distance() {
+        let points = [point(0.0, 0.0), point(10.0, 0.0), point(10.0, 30.0)];
+        assert_eq!(nearest(shapes(&points), Point2::new(6.0, 0.0), 5.0, Metric::Euclidean), Some(1));
+        assert_eq!(nearest(shapes(&points), Point2::new(2.0, 1.0), 5.0, Metric::Euclidean), Some(0));
+        assert_eq!(nearest(shapes(&points), Point2::new(5.0, 15.0), 5.0, Metric::Euclidean), None);
+        assert_eq!(nearest(shapes(&[]), Point2::new(0.0, 0.0), 5.0, Metric::Euclidean), None);
+    }
+
+    #[test]
+    fn horizontal_metric_ignores_the_height_but_breaks_ties_by_it() {
+        let points = [point(0.0, 0.0), point(10.0, 0.0), point(10.0, 30.0)];
+        assert_eq!(nearest(shapes(&points), Point2::new(9.0, 100.0), 5.0, Metric::Horizontal), Some(2));
+        assert_eq!(nearest(shapes(&points), Point2::new(9.0, 10.0), 5.0, Metric::Horizontal), Some(1));
+        assert_eq!(nearest(shapes(&points), Point2::new(3.0, 0.0), 5.0, Metric::Horizontal), Some(0));
+        assert_eq!(nearest(shapes(&points), Point2::new(20.0, 0.0), 5.0, Metric::Horizontal), None);
+    }
+}
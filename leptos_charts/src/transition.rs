@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+use std::f32::consts::PI;
+use std::hash::Hash;
+
+use leptos::{create_effect, create_memo, create_rw_signal, Memo, ReadSignal, request_animation_frame, RwSignal, Scope, SignalGet, SignalGetUntracked, SignalSet, SignalWith, SignalWithUntracked, StoredValue, store_value, window};
+
+use crate::ChartOptions;
+
+/// Progression of a transition over time.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Easing {
+    Linear,
+    QuadInOut,
+    #[default]
+    CubicInOut,
+    CubicOut,
+    SineInOut,
+}
+
+impl Easing {
+
+    /// Maps the elapsed fraction `t` of a transition onto the fraction of the change applied.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadInOut => if t < 0.5 { 2.0 * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(2) * 0.5 },
+            Easing::CubicInOut => if t < 0.5 { 4.0 * t.powi(3) } else { 1.0 - (-2.0 * t + 2.0).powi(3) * 0.5 },
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::SineInOut => -((PI * t).cos() - 1.0) * 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitionOptions {
+    /// Duration in milliseconds, `0.0` disables transitions.
+    pub duration: f32,
+    pub easing: Easing,
+    /// Disables transitions. They are disabled as well if the user asks the system to reduce motion.
+    pub reduced_motion: bool,
+}
+
+impl Default for TransitionOptions {
+    fn default() -> Self {
+        TransitionOptions {
+            duration: 300.0,
+            easing: Easing::default(),
+            reduced_motion: false,
+        }
+    }
+}
+
+impl TransitionOptions {
+
+    fn is_enabled(&self) -> bool {
+        self.duration > 0.0 && !self.reduced_motion && !prefers_reduced_motion()
+    }
+}
+
+fn prefers_reduced_motion() -> bool {
+    window().match_media("(prefers-reduced-motion: reduce)")
+        .ok()
+        .flatten()
+        .map_or(false, |query| query.matches())
+}
+
+fn now() -> f64 {
+    window().performance().map_or(0.0, |performance| performance.now())
+}
+
+/// Geometry that can be blended between two states.
+pub(crate) trait Tween: Clone + PartialEq + 'static {
+
+    /// Returns the state at `t` between `self` at `0.0` and `to` at `1.0`.
+    fn tween(&self, to: &Self, t: f32) -> Self;
+
+    /// Returns the state elements enter from and exit to, e.g. a bar of zero height.
+    fn collapsed(&self) -> Self {
+        Clone::clone(self)
+    }
+}
+
+impl Tween for f32 {
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Tween for (f32, f32) {
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        (self.0.tween(&to.0, t), self.1.tween(&to.1, t))
+    }
+}
+
+/// Sequences of equal length are blended element by element, others change at once.
+impl<T: Tween> Tween for Vec<T> {
+
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        if self.len() != to.len() {
+            return if t < 1.0 { Clone::clone(self) } else { Clone::clone(to) }
+        }
+        self.iter().zip(to.iter()).map(|(from, to)| from.tween(to, t)).collect()
+    }
+
+    fn collapsed(&self) -> Self {
+        self.iter().map(Tween::collapsed).collect()
+    }
+}
+
+/// Elements identified by a key, e.g. the domain label of a bar. Elements only present in the
+/// target enter from their collapsed state, elements missing from it exit to their collapsed state.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Keyed<K, V>(pub Vec<(K, V)>);
+
+impl<K, V> Keyed<K, V>
+where K: Clone + Eq + Hash {
+
+    /// Returns the position of every key among the elements.
+    pub fn positions(&self) -> HashMap<K, usize> {
+        self.0.iter()
+            .enumerate()
+            .map(|(position, (key, _))| (Clone::clone(key), position))
+            .collect()
+    }
+}
+
+impl<K, V> Tween for Keyed<K, V>
+where K: Clone + Eq + Hash + 'static, V: Tween {
+
+    fn tween(&self, to: &Self, t: f32) -> Self {
+        let starts = self.0.iter().map(|(key, value)| (key, value)).collect::<HashMap<_, _>>();
+        let targets = to.0.iter().map(|(key, value)| (key, value)).collect::<HashMap<_, _>>();
+        let updated = to.0.iter().map(|(key, target)| {
+            let start = starts.get(key).map_or_else(|| target.collapsed(), |start| Clone::clone(*start));
+            (Clone::clone(key), start.tween(target, t))
+        });
+        let exiting = self.0.iter()
+            .filter(|(key, _)| t < 1.0 && !targets.contains_key(key))
+            .map(|(key, start)| (Clone::clone(key), start.tween(&start.collapsed(), t)));
+        Keyed(updated.chain(exiting).collect())
+    }
+}
+
+/// Pairs every key with the number of equal keys before it, so that elements sharing a label
+/// still get keys of their own.
+pub(crate) fn unique_keys<K: Clone + Eq + Hash>(keys: impl IntoIterator<Item = K>) -> Vec<(K, usize)> {
+    let mut counts = HashMap::new();
+    keys.into_iter()
+        .map(|key| {
+            let count = counts.entry(Clone::clone(&key)).or_insert(0);
+            *count += 1;
+            (key, *count - 1)
+        })
+        .collect()
+}
+
+/// Returns a function creating a memo of the element with a given key, for views rendered once
+/// per key. Elements are found through an index of the keys built once per change rather than by
+/// searching all elements for every key.
+pub(crate) fn create_keyed_lookup<K, V>(cx: Scope, elements: ReadSignal<Keyed<K, V>>) -> impl Fn(Scope, K) -> Memo<Option<V>> + Copy
+where K: Clone + Eq + Hash + 'static, V: Clone + PartialEq + 'static {
+    let positions = create_memo(cx, move |_| elements.with(Keyed::positions));
+    move |cx, key| create_memo(cx, move |_| {
+        let position = positions.with(|positions| positions.get(&key).copied())?;
+        elements.with(|Keyed(elements)| elements.get(position).map(|(_, value)| Clone::clone(value)))
+    })
+}
+
+/// Returns a signal following `target`, blending from its previous to its new value whenever it
+/// changes. A change during a running transition starts over from the currently shown state.
+pub(crate) fn create_tween<T: Tween>(cx: Scope, target: Memo<T>, options: ReadSignal<ChartOptions>) -> ReadSignal<T> {
+    let value = create_rw_signal(cx, target.get_untracked());
+    let generation = store_value(cx, 0u64);
+
+    create_effect(cx, move |_| {
+        let to = target.get();
+        let transition = options.with_untracked(|options| Clone::clone(&options.transition));
+        let from = value.get_untracked();
+        generation.update_value(|generation| *generation += 1);
+        if from == to || !transition.is_enabled() {
+            value.set(to);
+            return
+        }
+        let animation = Animation { value, generation, current: generation.get_value(), from, to, start: now(), transition };
+        animation.next_frame();
+    });
+
+    value.read_only()
+}
+
+struct Animation<T: Tween> {
+    value: RwSignal<T>,
+    generation: StoredValue<u64>,
+    /// Generation of this animation, a newer one means it was interrupted.
+    current: u64,
+    from: T,
+    to: T,
+    start: f64,
+    transition: TransitionOptions,
+}
+
+impl<T: Tween> Animation<T> {
+
+    fn next_frame(self) {
+        request_animation_frame(move || {
+            if self.generation.get_value() != self.current {
+                return
+            }
+            let elapsed = ((now() - self.start) / self.transition.duration as f64).min(1.0) as f32;
+            if elapsed < 1.0 {
+                let t = self.transition.easing.apply(elapsed);
+                self.value.set(self.from.tween(&self.to, t));
+                self.next_frame();
+            }
+            else {
+                self.value.set(Clone::clone(&self.to));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A bar height growing from and shrinking to zero.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Height(f32);
+
+    impl Tween for Height {
+
+        fn tween(&self, to: &Self, t: f32) -> Self {
+            Height(self.0.tween(&to.0, t))
+        }
+
+        fn collapsed(&self) -> Self {
+            Height(0.0)
+        }
+    }
+
+    #[test]
+    fn keyed_elements_enter_update_and_exit() {
+        let from = Keyed(vec![("a", Height(2.0)), ("b", Height(4.0))]);
+        let to = Keyed(vec![("b", Height(8.0)), ("c", Height(6.0))]);
+        assert_eq!(from.tween(&to, 0.5).0, vec![("b", Height(6.0)), ("c", Height(3.0)), ("a", Height(1.0))]);
+        assert_eq!(from.tween(&to, 1.0), to);
+    }
+
+    #[test]
+    fn repeated_keys_are_numbered_in_order() {
+        assert_eq!(unique_keys(["a", "b", "a", "a", "c"]), vec![("a", 0), ("b", 0), ("a", 1), ("a", 2), ("c", 0)]);
+        assert_eq!(unique_keys(Vec::<&str>::new()), vec![]);
+    }
+
+    #[test]
+    fn positions_follow_the_order_of_the_elements() {
+        let elements = Keyed(vec![("b", Height(1.0)), ("a", Height(2.0))]);
+        assert_eq!(elements.positions(), HashMap::from([("b", 0), ("a", 1)]));
+    }
+}
@@ -0,0 +1,70 @@
+//! Tests against the DOM of a mounted chart, run in a browser with
+//! `wasm-pack test --headless --firefox --features csr`.
+#![cfg(target_arch = "wasm32")]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use leptos::{create_signal, document, mount_to, view, SignalUpdate, WriteSignal};
+use leptos_charts::{BarChart, BarChartData, ChartOptions};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+use web_sys::{Element, HtmlElement};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Mounts a bar chart into a new element and returns the element with the setter of its data.
+fn mount_bar_chart(data: BarChartData) -> (Element, WriteSignal<BarChartData>) {
+    let parent = document().create_element("div").unwrap();
+    document().body().unwrap().append_child(&parent).unwrap();
+    let set_data = Rc::new(Cell::new(None));
+    {
+        let set_data = Rc::clone(&set_data);
+        mount_to(Clone::clone(&parent).unchecked_into::<HtmlElement>(), move |cx| {
+            let (options, _) = create_signal(cx, ChartOptions::default());
+            let (data, set) = create_signal(cx, data);
+            set_data.set(Some(set));
+            view! { cx, <BarChart options=options data=data/> }
+        });
+    }
+    (parent, set_data.get().unwrap())
+}
+
+fn marks(parent: &Element) -> Vec<Element> {
+    let marks = parent.query_selector_all("svg [role=\"graphics-symbol\"]").unwrap();
+    (0..marks.length())
+        .filter_map(|index| marks.item(index))
+        .map(|node| node.unchecked_into::<Element>())
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn bars_keep_their_elements_when_values_change() {
+    let domain = vec![String::from("a"), String::from("b"), String::from("c")];
+    let (parent, set_data) = mount_bar_chart(BarChartData::new(domain, vec![1.0, 2.0, 3.0]));
+    let before = marks(&parent);
+    assert_eq!(before.len(), 3);
+
+    set_data.update(|data| data.set_values(vec![3.0, 1.0, 2.0]));
+    let after = marks(&parent);
+    assert_eq!(after.len(), 3);
+    before.iter()
+        .zip(&after)
+        .for_each(|(before, after)| assert!(before.is_same_node(Some(after))));
+}
+
+#[wasm_bindgen_test]
+fn only_added_bars_get_new_elements() {
+    let domain = vec![String::from("a"), String::from("b")];
+    let (parent, set_data) = mount_bar_chart(BarChartData::new(domain, vec![1.0, 2.0]));
+    let before = marks(&parent);
+
+    set_data.update(|data| {
+        data.set_domain(vec![String::from("a"), String::from("b"), String::from("c")]);
+        data.set_values(vec![1.0, 2.0, 3.0]);
+    });
+    let after = marks(&parent);
+    assert_eq!(after.len(), 3);
+    assert!(before[0].is_same_node(Some(&after[0])));
+    assert!(before[1].is_same_node(Some(&after[1])));
+}